@@ -0,0 +1,110 @@
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use rust_hash_finder::{
+    compute_hash, compute_hash_raw, compute_hash_with_algo, hash_bytes_end_with_zeros,
+    hash_matches_difficulty, HashAlgorithm, HashFinderConfig,
+};
+use std::hint::black_box;
+
+fn bench_compute_hash(c: &mut Criterion) {
+    let mut group = c.benchmark_group("compute_hash");
+    group.throughput(Throughput::Elements(1));
+
+    for &algo in HashAlgorithm::ALL {
+        group.bench_function(algo.name(), |b| {
+            let mut num = 0u64;
+            b.iter(|| {
+                num += 1;
+                compute_hash_with_algo(black_box(num), algo)
+            })
+        });
+    }
+
+    group.finish();
+}
+
+fn bench_hex_vs_raw(c: &mut Criterion) {
+    let mut group = c.benchmark_group("sha256_output");
+    group.throughput(Throughput::Elements(1));
+
+    group.bench_function("hex", |b| {
+        let mut num = 0u64;
+        b.iter(|| {
+            num += 1;
+            compute_hash(black_box(num))
+        })
+    });
+    group.bench_function("raw", |b| {
+        let mut num = 0u64;
+        b.iter(|| {
+            num += 1;
+            compute_hash_raw(black_box(num))
+        })
+    });
+
+    group.finish();
+}
+
+fn bench_trailing_zero_check(c: &mut Criterion) {
+    let mut group = c.benchmark_group("trailing_zero_check");
+    group.throughput(Throughput::Elements(1));
+
+    group.bench_function("hex_string", |b| {
+        let mut num = 0u64;
+        b.iter(|| {
+            num += 1;
+            hash_matches_difficulty(&compute_hash(black_box(num)), 5) == Ok(true)
+        })
+    });
+    group.bench_function("bytes", |b| {
+        let mut num = 0u64;
+        b.iter(|| {
+            num += 1;
+            hash_bytes_end_with_zeros(&compute_hash_raw(black_box(num)), 5)
+        })
+    });
+
+    group.finish();
+}
+
+fn bench_search_partitioning(c: &mut Criterion) {
+    let mut group = c.benchmark_group("search_partitioning");
+    group.sample_size(10);
+
+    for zeros in [3, 4, 5] {
+        let config = HashFinderConfig::new().zeros(zeros).max_results(5);
+        group.bench_with_input(BenchmarkId::new("par_bridge", zeros), &config, |b, config| {
+            b.iter(|| config.run().unwrap())
+        });
+        let config_64k = config.clone().chunk_size(1 << 16);
+        group.bench_with_input(BenchmarkId::new("chunks_64k", zeros), &config_64k, |b, config| {
+            b.iter(|| config.run().unwrap())
+        });
+        let config_1m = config.clone().chunk_size(1 << 20);
+        group.bench_with_input(BenchmarkId::new("chunks_1m", zeros), &config_1m, |b, config| {
+            b.iter(|| config.run().unwrap())
+        });
+    }
+
+    group.finish();
+}
+
+/// Many cheap matches, so the cost of collecting results shows: compare
+/// `--features atomics` against the default crossbeam build.
+fn bench_result_collection(c: &mut Criterion) {
+    let mut group = c.benchmark_group("result_collection");
+    group.sample_size(20);
+    group.throughput(Throughput::Elements(100));
+    let config = HashFinderConfig::new().zeros(2).max_results(100);
+    group.bench_function("zeros_2_results_100", |b| b.iter(|| config.run().unwrap()));
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    bench_compute_hash,
+    bench_hex_vs_raw,
+    bench_trailing_zero_check,
+    bench_search_partitioning,
+    bench_result_collection
+);
+criterion_main!(benches);
@@ -0,0 +1,291 @@
+#[cfg(feature = "argon2")]
+use crate::Argon2Params;
+use crate::hex_encode;
+use blake2::Blake2b512;
+use md5::Md5;
+use ripemd::Ripemd160;
+use sha1::Sha1;
+use sha2::digest::FixedOutputReset;
+use sha2::{Digest, Sha256, Sha512};
+#[cfg(feature = "sha3")]
+use sha3::{Keccak256, Sha3_256};
+use std::fmt;
+use std::ops::Deref;
+use std::str::FromStr;
+use xxhash_rust::xxh64::Xxh64;
+
+/// Largest digest produced by any [`HashAlgorithm`], in bytes.
+pub const MAX_DIGEST_LEN: usize = 64;
+
+/// A digest held inline, so hashing in the search loop does not allocate.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct DigestBytes {
+    buf: [u8; MAX_DIGEST_LEN],
+    len: usize,
+}
+
+impl DigestBytes {
+    pub(crate) fn new(bytes: &[u8]) -> Self {
+        let mut buf = [0; MAX_DIGEST_LEN];
+        buf[..bytes.len()].copy_from_slice(bytes);
+        DigestBytes {
+            buf,
+            len: bytes.len(),
+        }
+    }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.buf[..self.len]
+    }
+
+    pub fn to_hex(&self) -> String {
+        hex_encode(self.as_bytes())
+    }
+}
+
+impl Deref for DigestBytes {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        self.as_bytes()
+    }
+}
+
+impl fmt::Debug for DigestBytes {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "DigestBytes({})", self.to_hex())
+    }
+}
+
+/// Digest algorithm used to hash candidate numbers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum HashAlgorithm {
+    #[default]
+    Sha256,
+    /// SHA-256 applied twice, as Bitcoin does for block headers.
+    Sha256d,
+    Sha512,
+    Blake2b,
+    #[cfg(feature = "blake3")]
+    Blake3,
+    Sha1,
+    Md5,
+    /// Standard FIPS 202 SHA3-256.
+    #[cfg(feature = "sha3")]
+    Sha3_256,
+    /// Original Keccak-256 padding, as used by Ethereum's `keccak256` and
+    /// `web3.utils.sha3`; differs from SHA3-256.
+    #[cfg(feature = "sha3")]
+    Keccak256,
+    Ripemd160,
+    /// Non-cryptographic xxHash64, cheap enough that the search machinery dominates; useful
+    /// for benchmarks and fast tests.
+    XxHash64,
+    /// Non-cryptographic CRC-32 (IEEE); short enough that matches show up every few hundred
+    /// attempts, which suits demos.
+    Crc32,
+    /// Memory-hard Argon2id, for proof-of-work experiments where every attempt is costly.
+    #[cfg(feature = "argon2")]
+    Argon2id(Argon2Params),
+}
+
+impl HashAlgorithm {
+    pub const ALL: &'static [HashAlgorithm] = &[
+        HashAlgorithm::Sha256,
+        HashAlgorithm::Sha256d,
+        HashAlgorithm::Sha512,
+        HashAlgorithm::Blake2b,
+        #[cfg(feature = "blake3")]
+        HashAlgorithm::Blake3,
+        HashAlgorithm::Sha1,
+        HashAlgorithm::Md5,
+        #[cfg(feature = "sha3")]
+        HashAlgorithm::Sha3_256,
+        #[cfg(feature = "sha3")]
+        HashAlgorithm::Keccak256,
+        HashAlgorithm::Ripemd160,
+        HashAlgorithm::XxHash64,
+        HashAlgorithm::Crc32,
+        #[cfg(feature = "argon2")]
+        HashAlgorithm::Argon2id(Argon2Params::DEFAULT),
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            HashAlgorithm::Sha256 => "sha256",
+            HashAlgorithm::Sha256d => "sha256d",
+            HashAlgorithm::Sha512 => "sha512",
+            HashAlgorithm::Blake2b => "blake2b",
+            #[cfg(feature = "blake3")]
+            HashAlgorithm::Blake3 => "blake3",
+            HashAlgorithm::Sha1 => "sha1",
+            HashAlgorithm::Md5 => "md5",
+            #[cfg(feature = "sha3")]
+            HashAlgorithm::Sha3_256 => "sha3-256",
+            #[cfg(feature = "sha3")]
+            HashAlgorithm::Keccak256 => "keccak256",
+            HashAlgorithm::Ripemd160 => "ripemd160",
+            HashAlgorithm::XxHash64 => "xxhash",
+            HashAlgorithm::Crc32 => "crc32",
+            #[cfg(feature = "argon2")]
+            HashAlgorithm::Argon2id(_) => "argon2",
+        }
+    }
+
+    /// Length of the lowercase hex digest, i.e. the maximum number of trailing zeros.
+    pub fn hex_len(self) -> usize {
+        match self {
+            HashAlgorithm::Sha256 | HashAlgorithm::Sha256d => 64,
+            #[cfg(feature = "sha3")]
+            HashAlgorithm::Sha3_256 | HashAlgorithm::Keccak256 => 64,
+            #[cfg(feature = "blake3")]
+            HashAlgorithm::Blake3 => 64,
+            HashAlgorithm::Sha512 | HashAlgorithm::Blake2b => 128,
+            HashAlgorithm::Sha1 | HashAlgorithm::Ripemd160 => 40,
+            HashAlgorithm::Md5 => 32,
+            HashAlgorithm::XxHash64 => 16,
+            HashAlgorithm::Crc32 => 8,
+            #[cfg(feature = "argon2")]
+            HashAlgorithm::Argon2id(_) => 64,
+        }
+    }
+
+    pub fn hash_hex(self, input: &[u8]) -> String {
+        self.hash_parts_hex(&[input])
+    }
+
+    /// Hashes the concatenation of `parts` without building it in memory first.
+    pub fn hash_parts_hex(self, parts: &[&[u8]]) -> String {
+        self.digest_parts(parts).to_hex()
+    }
+
+    /// Raw digest of the concatenation of `parts`, without allocating.
+    pub fn digest_parts(self, parts: &[&[u8]]) -> DigestBytes {
+        self.digest_iterated(parts, 1)
+    }
+
+    /// Hashes `parts`, then re-hashes the raw digest until the algorithm has been applied
+    /// `iterations` times in total; `iterations` of 0 is treated as 1.
+    ///
+    /// A single hasher is reset between rounds and intermediate digests are never hex
+    /// encoded.
+    pub fn digest_iterated(self, parts: &[&[u8]], iterations: u32) -> DigestBytes {
+        match self {
+            HashAlgorithm::Sha256 => iterate::<Sha256>(parts, iterations),
+            // Each round is two SHA-256 passes.
+            HashAlgorithm::Sha256d => iterate::<Sha256>(parts, iterations.max(1) * 2),
+            HashAlgorithm::Sha512 => iterate::<Sha512>(parts, iterations),
+            HashAlgorithm::Blake2b => iterate::<Blake2b512>(parts, iterations),
+            // The native API is faster than going through the `Digest` trait.
+            #[cfg(feature = "blake3")]
+            HashAlgorithm::Blake3 => iterate_with(parts, iterations, |parts| {
+                let mut hasher = blake3::Hasher::new();
+                for part in parts {
+                    hasher.update(part);
+                }
+                DigestBytes::new(hasher.finalize().as_bytes())
+            }),
+            HashAlgorithm::Sha1 => iterate::<Sha1>(parts, iterations),
+            HashAlgorithm::Md5 => iterate::<Md5>(parts, iterations),
+            #[cfg(feature = "sha3")]
+            HashAlgorithm::Sha3_256 => iterate::<Sha3_256>(parts, iterations),
+            #[cfg(feature = "sha3")]
+            HashAlgorithm::Keccak256 => iterate::<Keccak256>(parts, iterations),
+            HashAlgorithm::Ripemd160 => iterate::<Ripemd160>(parts, iterations),
+            HashAlgorithm::XxHash64 => iterate_with(parts, iterations, |parts| {
+                let mut hasher = Xxh64::new(0);
+                for part in parts {
+                    hasher.update(part);
+                }
+                DigestBytes::new(&hasher.digest().to_be_bytes())
+            }),
+            HashAlgorithm::Crc32 => iterate_with(parts, iterations, |parts| {
+                let mut hasher = crc32fast::Hasher::new();
+                for part in parts {
+                    hasher.update(part);
+                }
+                DigestBytes::new(&hasher.finalize().to_be_bytes())
+            }),
+            #[cfg(feature = "argon2")]
+            HashAlgorithm::Argon2id(params) => iterate_with(parts, iterations, |parts| {
+                DigestBytes::new(&params.digest(&parts.concat()))
+            }),
+        }
+    }
+}
+
+fn iterate<D: Digest + FixedOutputReset>(parts: &[&[u8]], iterations: u32) -> DigestBytes {
+    let mut hasher = D::new();
+    for part in parts {
+        Digest::update(&mut hasher, part);
+    }
+    let mut out = hasher.finalize_reset();
+    for _ in 1..iterations {
+        Digest::update(&mut hasher, &out);
+        Digest::finalize_into_reset(&mut hasher, &mut out);
+    }
+    DigestBytes::new(&out)
+}
+
+/// [`iterate`] for hashers outside the `Digest` trait; `hash` is called once per round.
+fn iterate_with(
+    parts: &[&[u8]],
+    iterations: u32,
+    hash: impl Fn(&[&[u8]]) -> DigestBytes,
+) -> DigestBytes {
+    let mut out = hash(parts);
+    for _ in 1..iterations {
+        out = hash(&[&out]);
+    }
+    out
+}
+
+impl fmt::Display for HashAlgorithm {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// Serialized as its display form, e.g. `sha256`.
+#[cfg(feature = "serde")]
+impl serde::Serialize for HashAlgorithm {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+/// Algorithms left out of this build, with the cargo feature that enables each, so asking
+/// for one gives a better error than "unknown algorithm".
+const NOT_COMPILED_IN: &[(&str, &str)] = &[
+    #[cfg(not(feature = "blake3"))]
+    ("blake3", "blake3"),
+    #[cfg(not(feature = "sha3"))]
+    ("sha3-256", "sha3"),
+    #[cfg(not(feature = "sha3"))]
+    ("keccak256", "sha3"),
+    #[cfg(not(feature = "argon2"))]
+    ("argon2", "argon2"),
+];
+
+impl FromStr for HashAlgorithm {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        HashAlgorithm::ALL
+            .iter()
+            .copied()
+            .find(|algo| algo.name().eq_ignore_ascii_case(s))
+            .ok_or_else(|| {
+                let names: Vec<&str> = HashAlgorithm::ALL.iter().map(|a| a.name()).collect();
+                let available = names.join(", ");
+                match NOT_COMPILED_IN.iter().find(|(name, _)| name.eq_ignore_ascii_case(s)) {
+                    Some((name, feature)) => format!(
+                        "algorithm '{}' was not compiled in; rebuild with `--features {}` \
+                         (available: {})",
+                        name, feature, available
+                    ),
+                    None => format!("unknown algorithm '{}' (available: {})", s, available),
+                }
+            })
+    }
+}
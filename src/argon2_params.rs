@@ -0,0 +1,110 @@
+use argon2::{Algorithm, Argon2, Params, Version};
+use std::fmt;
+
+/// Salt for every Argon2id hash; the search input is passed as the password.
+const ARGON2_SALT: &[u8] = b"rust-hash-finder";
+const ARGON2_OUTPUT_LEN: usize = 32;
+
+/// Cost parameters for [`HashAlgorithm::Argon2id`](crate::HashAlgorithm::Argon2id), with a
+/// single lane and a 32-byte output.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Argon2Params {
+    mem_kib: u32,
+    iterations: u32,
+}
+
+impl Argon2Params {
+    /// 64 MiB and 3 passes.
+    pub const DEFAULT: Argon2Params = Argon2Params {
+        mem_kib: 64 * 1024,
+        iterations: 3,
+    };
+
+    /// Memory in KiB (at least 8) and number of passes (at least 1).
+    pub fn new(mem_kib: u32, iterations: u32) -> Result<Self, String> {
+        Params::new(mem_kib, iterations, 1, Some(ARGON2_OUTPUT_LEN))
+            .map_err(|e| format!("invalid Argon2 parameters: {}", e))?;
+        Ok(Argon2Params {
+            mem_kib,
+            iterations,
+        })
+    }
+
+    pub fn mem_kib(self) -> u32 {
+        self.mem_kib
+    }
+
+    pub fn iterations(self) -> u32 {
+        self.iterations
+    }
+
+    pub(crate) fn digest(self, password: &[u8]) -> [u8; ARGON2_OUTPUT_LEN] {
+        let params = Params::new(self.mem_kib, self.iterations, 1, Some(ARGON2_OUTPUT_LEN))
+            .expect("parameters are checked by Argon2Params::new");
+        let mut out = [0; ARGON2_OUTPUT_LEN];
+        Argon2::new(Algorithm::Argon2id, Version::V0x13, params)
+            .hash_password_into(password, ARGON2_SALT, &mut out)
+            .expect("salt and output lengths are valid");
+        out
+    }
+}
+
+impl Default for Argon2Params {
+    fn default() -> Self {
+        Argon2Params::DEFAULT
+    }
+}
+
+/// `m=65536KiB,t=3`
+impl fmt::Display for Argon2Params {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "m={}KiB,t={}", self.mem_kib, self.iterations)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{HashAlgorithm, HashFinderConfig};
+
+    fn tiny() -> Argon2Params {
+        Argon2Params::new(8, 1).unwrap()
+    }
+
+    #[test]
+    fn test_argon2_known_value() {
+        let algo = HashAlgorithm::Argon2id(tiny());
+        assert_eq!(
+            algo.hash_hex(b"4163"),
+            "6dcafd783fe97bc3b12085ea3ff3eaa21c64fc2c3f406b0e8b102d24ed04e2ca"
+        );
+        assert_eq!(
+            HashAlgorithm::Argon2id(Argon2Params::new(16, 2).unwrap()).hash_hex(b"4163"),
+            "854ef9017e7e33ae3787ac1dab575b48ee783401d31fb1f89d9346793eec48de"
+        );
+    }
+
+    #[test]
+    fn test_argon2_params_validation() {
+        assert!(Argon2Params::new(7, 1).is_err());
+        assert!(Argon2Params::new(8, 0).is_err());
+        assert_eq!(tiny().to_string(), "m=8KiB,t=1");
+        assert_eq!(Argon2Params::default(), Argon2Params::DEFAULT);
+    }
+
+    #[test]
+    fn test_argon2_search_reports_attempts() {
+        let config = HashFinderConfig::new()
+            .zeros(1)
+            .max_results(2)
+            .algorithm(HashAlgorithm::Argon2id(tiny()))
+            .progress(crate::SearchProgress::new());
+        let results = config.run().unwrap();
+        assert_eq!(results.len(), 2);
+        for result in &results {
+            assert_eq!(result.hash, config.hash(result.number));
+            assert!(result.hash.ends_with('0'));
+            assert!(result.attempts.is_some_and(|attempts| attempts >= 1));
+        }
+    }
+}
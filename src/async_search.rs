@@ -0,0 +1,107 @@
+use crate::{CancellationToken, HashFinderConfig, HashFinderError, HashResult, HashStream};
+use tokio::sync::mpsc;
+use tokio_stream::wrappers::ReceiverStream;
+use tokio_stream::Stream;
+use tracing::debug;
+
+const ASYNC_STREAM_CAPACITY: usize = 100;
+
+/// Cancels the search when the future awaiting it is dropped.
+struct CancelOnDrop(CancellationToken);
+
+impl Drop for CancelOnDrop {
+    fn drop(&mut self) {
+        self.0.cancel();
+    }
+}
+
+/// Runs [`find_hashes`](crate::find_hashes) on Tokio's blocking pool so the executor stays
+/// free.
+///
+/// Dropping the returned future cancels the search.
+///
+/// Fails under the same conditions as [`find_hashes`](crate::find_hashes).
+pub async fn find_hashes_async(
+    zeros: usize,
+    max_results: usize,
+) -> Result<Vec<HashResult>, HashFinderError> {
+    let token = CancellationToken::new();
+    let _cancel_on_drop = CancelOnDrop(token.clone());
+    let config = HashFinderConfig::default()
+        .zeros(zeros)
+        .max_results(max_results)
+        .cancellation(token);
+
+    match tokio::task::spawn_blocking(move || config.run()).await {
+        Ok(results) => results,
+        Err(e) => std::panic::resume_unwind(e.into_panic()),
+    }
+}
+
+/// Streams SHA-256 matches ending with `zeros` zeros as they are found.
+///
+/// The search runs on Tokio's blocking pool and stops once the stream is dropped. Must be
+/// called from within a Tokio runtime. Fails like [`HashStream::new`].
+///
+/// ```no_run
+/// use rust_hash_finder::find_hashes_stream;
+/// use tokio_stream::StreamExt;
+///
+/// # async fn example() {
+/// let mut stream = find_hashes_stream(4).unwrap();
+/// while let Some(result) = stream.next().await {
+///     println!("{}", result);
+/// }
+/// # }
+/// ```
+pub fn find_hashes_stream(
+    zeros: usize,
+) -> Result<impl Stream<Item = HashResult>, HashFinderError> {
+    let stream = HashStream::new(zeros)?;
+    let (tx, rx) = mpsc::channel(ASYNC_STREAM_CAPACITY);
+
+    tokio::task::spawn_blocking(move || {
+        for result in stream {
+            // A send error means the async stream was dropped; dropping the
+            // `HashStream` then stops the search.
+            if tx.blocking_send(result).is_err() {
+                debug!("Async hash stream dropped, stopping search");
+                break;
+            }
+        }
+    });
+
+    Ok(ReceiverStream::new(rx))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{compute_hash, hash_matches_difficulty};
+    use tokio_stream::StreamExt;
+
+    #[tokio::test]
+    async fn test_find_hashes_async() {
+        let results = find_hashes_async(3, 2).await.unwrap();
+        assert_eq!(results.len(), 2);
+        for result in &results {
+            assert_eq!(result.hash, compute_hash(result.number));
+            assert_eq!(hash_matches_difficulty(&result.hash, 3), Ok(true));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_find_hashes_stream() {
+        let mut stream = find_hashes_stream(2).unwrap();
+        let mut count = 0;
+        while let Some(result) = stream.next().await {
+            assert_eq!(hash_matches_difficulty(&result.hash, 2), Ok(true));
+            count += 1;
+            if count == 5 {
+                break;
+            }
+        }
+        assert_eq!(count, 5);
+        assert!(find_hashes_stream(0).is_err());
+    }
+}
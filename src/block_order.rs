@@ -0,0 +1,88 @@
+/// Odd multiplier for [`BlockOrder`]'s mixing rounds; any odd number is a bijection
+/// modulo a power of two.
+const MIX: u64 = 0x9e37_79b9_7f4a_7c15;
+
+/// Visits the indices `0..len` once each in an order fixed by a seed.
+///
+/// A keyed bijection on the smallest power-of-two domain covering `len` is applied to
+/// `0, 1, 2, ..` and values of `len` or more are skipped, so every index comes up exactly
+/// once and at most half the domain is skipped.
+#[derive(Debug, Clone)]
+pub(crate) struct BlockOrder {
+    len: u64,
+    mask: u64,
+    shift: u32,
+    keys: [u64; 3],
+    next: u128,
+}
+
+impl BlockOrder {
+    pub(crate) fn new(len: u64, seed: u64) -> Self {
+        let bits = 64 - len.saturating_sub(1).leading_zeros();
+        let mut state = seed;
+        BlockOrder {
+            len,
+            mask: u64::MAX.checked_shr(64 - bits).unwrap_or(0),
+            shift: bits.div_ceil(2).max(1),
+            keys: [(); 3].map(|()| splitmix64(&mut state)),
+            next: 0,
+        }
+    }
+
+    fn permute(&self, mut x: u64) -> u64 {
+        for key in self.keys {
+            x = x.wrapping_add(key) & self.mask;
+            x = x.wrapping_mul(MIX) & self.mask;
+            x ^= x >> self.shift;
+        }
+        x
+    }
+}
+
+impl Iterator for BlockOrder {
+    type Item = u64;
+
+    fn next(&mut self) -> Option<u64> {
+        while self.next <= u128::from(self.mask) && self.len > 0 {
+            let index = self.permute(self.next as u64);
+            self.next += 1;
+            if index < self.len {
+                return Some(index);
+            }
+        }
+        None
+    }
+}
+
+/// Derives well-spread round keys from a seed, so nearby seeds give unrelated orders.
+fn splitmix64(state: &mut u64) -> u64 {
+    *state = state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    let mut z = *state;
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_block_order_visits_every_index_once() {
+        for len in [0, 1, 2, 3, 7, 64, 1000] {
+            let mut order: Vec<u64> = BlockOrder::new(len, 42).collect();
+            order.sort_unstable();
+            assert_eq!(order, (0..len).collect::<Vec<_>>());
+        }
+    }
+
+    #[test]
+    fn test_block_order_is_fixed_by_seed() {
+        let order: Vec<u64> = BlockOrder::new(1000, 7).collect();
+        assert_eq!(BlockOrder::new(1000, 7).collect::<Vec<_>>(), order);
+        assert_ne!(BlockOrder::new(1000, 8).collect::<Vec<_>>(), order);
+        assert_ne!(order, (0..1000).collect::<Vec<_>>());
+        // A huge space still starts promptly.
+        assert!(BlockOrder::new(u64::MAX, 7).next().is_some());
+    }
+}
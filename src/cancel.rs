@@ -0,0 +1,42 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+/// Shared flag for stopping a running search from another thread.
+///
+/// Clones share the same flag, so one clone can be handed to the search while another
+/// is kept to cancel it.
+///
+/// ```
+/// use rust_hash_finder::{find_hashes_cancellable, CancellationToken};
+///
+/// let token = CancellationToken::new();
+/// token.cancel();
+/// assert!(find_hashes_cancellable(3, 10, token).unwrap().is_empty());
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken(Arc<AtomicBool>);
+
+impl CancellationToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Asks every search holding a clone of this token to stop; they return what they
+    /// have found so far.
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+/// Tokens are equal when they are clones sharing the same flag.
+impl PartialEq for CancellationToken {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl Eq for CancellationToken {}
@@ -0,0 +1,226 @@
+use crate::{hex_encode, HashAlgorithm, HashFinderConfig, HashResult};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Progress of an interrupted search, so it can be resumed later.
+///
+/// ```no_run
+/// use rust_hash_finder::{HashFinderConfig, SearchCheckpoint};
+/// use std::path::Path;
+///
+/// let path = Path::new("search.checkpoint");
+/// let mut checkpoint = SearchCheckpoint::load(path).unwrap_or_default();
+/// let results = HashFinderConfig::new()
+///     .zeros(7)
+///     .max_results(1)
+///     .run_resumable(&mut checkpoint, Some(path))
+///     .unwrap();
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SearchCheckpoint {
+    /// Every number up to and including this one has been checked; 0 means none have.
+    pub last_checked: u64,
+    /// Matches found so far.
+    pub found: Vec<HashResult>,
+    /// Parameters of the search that saved it; `None` until a search has run, and in
+    /// checkpoints written before they were recorded.
+    #[serde(default)]
+    pub params: Option<CheckpointParams>,
+}
+
+impl SearchCheckpoint {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records `config`'s parameters, or fails with [`io::ErrorKind::InvalidInput`] if the
+    /// checkpoint was saved by a search with different ones, whose progress means nothing
+    /// for this one.
+    pub fn check_params(&mut self, config: &HashFinderConfig) -> io::Result<()> {
+        let params = CheckpointParams::of(config);
+        if let Some(saved) = &mut self.params
+            && saved.criteria.is_none()
+        {
+            // Saved before the other criteria were recorded, so only the fields that were
+            // can be checked.
+            saved.criteria.clone_from(&params.criteria);
+        }
+        match &self.params {
+            Some(saved) if *saved != params => Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("checkpoint was saved for {}, not {}", saved, params),
+            )),
+            Some(_) => Ok(()),
+            None => {
+                self.params = Some(params);
+                Ok(())
+            }
+        }
+    }
+
+    /// Writes the checkpoint as JSON.
+    ///
+    /// The data goes to a `.tmp` sibling first and is renamed over `path`, so an
+    /// interrupted save leaves the previous checkpoint intact.
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        let tmp = tmp_path(path);
+        fs::write(&tmp, serde_json::to_string_pretty(self)?)?;
+        fs::rename(&tmp, path)
+    }
+
+    pub fn load(path: &Path) -> io::Result<SearchCheckpoint> {
+        let contents = fs::read_to_string(path)?;
+        Ok(serde_json::from_str(&contents)?)
+    }
+}
+
+/// The parameters that decide which numbers match, so a checkpoint is only resumed by the
+/// search that saved it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CheckpointParams {
+    pub algorithm: String,
+    pub zeros: usize,
+    /// Salt as hex.
+    pub salt: Option<String>,
+    pub nonce_encoding: String,
+    /// SHA-256, as hex, of every other setting that decides which numbers match, such as
+    /// the prefix, suffix, regex, template, iterations, pad width and HMAC key; `None` in
+    /// checkpoints written before it was recorded.
+    #[serde(default)]
+    pub criteria: Option<String>,
+}
+
+impl CheckpointParams {
+    pub fn of(config: &HashFinderConfig) -> Self {
+        CheckpointParams {
+            algorithm: config.algorithm.to_string(),
+            zeros: config.zeros,
+            salt: config.salt.as_deref().map(hex_encode),
+            nonce_encoding: config.nonce_encoding.to_string(),
+            criteria: Some(criteria_digest(config)),
+        }
+    }
+}
+
+/// Digest of `config` without the settings that only decide where, how fast or for how
+/// long to search. The HMAC key, which the config serializes redacted, is hashed in
+/// separately so it never reaches the checkpoint file.
+fn criteria_digest(config: &HashFinderConfig) -> String {
+    let mut criteria = config.clone();
+    criteria.max_results = 1;
+    criteria.start = 1;
+    criteria.end = None;
+    criteria.threads = None;
+    criteria.chunk_size = None;
+    criteria.randomize = None;
+    criteria.shard = None;
+    criteria.timeout = None;
+    criteria.checkpoint_interval = None;
+    let json = serde_json::to_vec(&criteria).expect("configs always serialize to JSON");
+    let key = config.hmac_key.as_ref().map_or(&[][..], |key| key.as_bytes());
+    HashAlgorithm::Sha256.hash_parts_hex(&[&json, key])
+}
+
+impl fmt::Display for CheckpointParams {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "algorithm={}, zeros={}, salt={}, nonce encoding={}, criteria={}",
+            self.algorithm,
+            self.zeros,
+            self.salt.as_deref().unwrap_or("none"),
+            self.nonce_encoding,
+            self.criteria
+                .as_deref()
+                .map_or("unknown", |digest| digest.get(..12).unwrap_or(digest))
+        )
+    }
+}
+
+fn tmp_path(path: &Path) -> PathBuf {
+    let mut tmp = path.as_os_str().to_owned();
+    tmp.push(".tmp");
+    PathBuf::from(tmp)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_save_and_load_round_trip() {
+        let path =
+            std::env::temp_dir().join(format!("hash-finder-{}.checkpoint", std::process::id()));
+        let checkpoint = SearchCheckpoint {
+            last_checked: 5000,
+            found: vec![HashResult::new(
+                4163,
+                "95d4362bd3cd4315d0bbe38dfa5d7fb8f0aed5f1a31d98d510907279194e3000".to_string(),
+            )],
+            params: Some(CheckpointParams::of(&HashFinderConfig::new().zeros(3))),
+        };
+
+        checkpoint.save(&path).unwrap();
+        assert!(!tmp_path(&path).exists());
+        assert_eq!(SearchCheckpoint::load(&path).unwrap(), checkpoint);
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_check_params_rejects_other_search() {
+        let config = HashFinderConfig::new().zeros(3).salt("run1-");
+        let mut checkpoint = SearchCheckpoint::new();
+        checkpoint.check_params(&config).unwrap();
+        checkpoint.check_params(&config.clone().max_results(5)).unwrap();
+
+        checkpoint.check_params(&config.clone().end(1_000).threads(2).chunk_size(64)).unwrap();
+
+        let key = |key: &str| crate::HmacKey::new(key.as_bytes());
+        let keyed = config.clone().hmac_key(key("secret"));
+        let mut keyed_checkpoint = SearchCheckpoint::new();
+        keyed_checkpoint.check_params(&keyed).unwrap();
+        let saved = serde_json::to_string(&keyed_checkpoint).unwrap();
+        assert!(!saved.contains(&hex_encode(b"secret")) && !saved.contains("secret"));
+        assert!(keyed_checkpoint.check_params(&config.clone().hmac_key(key("other"))).is_err());
+
+        for other in [
+            config.clone().zeros(4),
+            config.clone().salt("run2-"),
+            config.clone().algorithm(crate::HashAlgorithm::Sha512),
+            config.clone().nonce_encoding(crate::NonceEncoding::HexString),
+            config.clone().hmac_key(key("secret")),
+            config.clone().suffix("abc".parse().unwrap()),
+            config.clone().prefix("abc".parse().unwrap()),
+            config.clone().contains("abc".parse().unwrap()),
+            #[cfg(feature = "regex")]
+            config.clone().regex("^ab".parse().unwrap()),
+            config.clone().leading_zero_bits(12),
+            config.clone().template("<{n}>".parse().unwrap()),
+            config.clone().iterations(2),
+            config.clone().pad_width(8),
+            config.clone().direction(crate::MatchDirection::Leading),
+        ] {
+            let err = checkpoint.check_params(&other).unwrap_err();
+            assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+            assert!(err.to_string().contains("zeros=3, salt=72756e312d"), "{}", err);
+        }
+    }
+
+    #[test]
+    fn test_check_params_accepts_checkpoints_without_criteria() {
+        let config = HashFinderConfig::new().zeros(3).suffix("abc".parse().unwrap());
+        let mut old = SearchCheckpoint::new();
+        old.check_params(&config).unwrap();
+        old.params.as_mut().unwrap().criteria = None;
+        let old: SearchCheckpoint =
+            serde_json::from_str(&serde_json::to_string(&old).unwrap()).unwrap();
+
+        let mut resumed = old.clone();
+        resumed.check_params(&config).unwrap();
+        assert_eq!(resumed.params, Some(CheckpointParams::of(&config)));
+        assert!(resumed.check_params(&config.clone().suffix("def".parse().unwrap())).is_err());
+    }
+}
@@ -0,0 +1,103 @@
+use crate::{compute_hash, compute_hash_raw, HashFinderError};
+use rayon::prelude::*;
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+/// Longest shared suffix [`find_suffix_collision`] accepts: 16 hex digits, the low 64 bits
+/// of the digest.
+pub const MAX_COLLISION_DIGITS: usize = 16;
+
+/// Locks guarding the seen-suffix map, so workers rarely wait on each other.
+const SHARDS: usize = 64;
+
+/// Numbers handed to the workers per round.
+const CHUNK: u64 = 1 << 16;
+
+/// Finds two distinct numbers from 1 whose SHA-256 hashes share their last `k` hex digits,
+/// a birthday-style partial collision. Returns the smaller number, the larger one and the
+/// shared suffix.
+///
+/// Every hash checked is remembered until a collision turns up. By the birthday bound that
+/// takes about `1.25 * 4^k` hashes ([`collision_expected_attempts`]) at roughly 32 bytes
+/// each: a few MiB for `k = 8`, over 1 GiB from `k = 13`. At most `16^k + 1` are needed.
+/// Numbers are checked in parallel, so which pair turns up first can differ between runs.
+///
+/// Fails with [`HashFinderError::InvalidCollisionDigits`] if `k` is 0 or above
+/// [`MAX_COLLISION_DIGITS`].
+///
+/// ```
+/// use rust_hash_finder::{compute_hash, find_suffix_collision};
+///
+/// let (a, b, suffix) = find_suffix_collision(3).unwrap();
+/// assert!(a < b);
+/// assert!(compute_hash(a).ends_with(&suffix) && compute_hash(b).ends_with(&suffix));
+/// ```
+pub fn find_suffix_collision(k: usize) -> Result<(u64, u64, String), HashFinderError> {
+    if !(1..=MAX_COLLISION_DIGITS).contains(&k) {
+        return Err(HashFinderError::InvalidCollisionDigits {
+            digits: k,
+            max: MAX_COLLISION_DIGITS,
+        });
+    }
+    let mask = u64::MAX >> (64 - 4 * k);
+    let shards: Vec<Mutex<HashMap<u64, u64>>> = (0..SHARDS).map(|_| Mutex::default()).collect();
+
+    let mut next = 1;
+    loop {
+        let end = next + CHUNK;
+        let found = (next..end).into_par_iter().find_map_any(|num| {
+            let digest = compute_hash_raw(num);
+            let tail = u64::from_be_bytes(digest[24..].try_into().unwrap()) & mask;
+            let mut seen = shards[(tail % SHARDS as u64) as usize].lock().unwrap();
+            seen.insert(tail, num).map(|other| (other.min(num), other.max(num)))
+        });
+        if let Some((a, b)) = found {
+            let hash = compute_hash(a);
+            return Ok((a, b, hash[hash.len() - k..].to_string()));
+        }
+        next = end;
+    }
+}
+
+/// Expected hashes [`find_suffix_collision`] checks, and so keeps in memory, before two
+/// share their last `k` hex digits: `sqrt(pi / 2 * 16^k)`, saturating at `u64::MAX`.
+///
+/// ```
+/// assert_eq!(rust_hash_finder::collision_expected_attempts(8), 82137);
+/// ```
+pub fn collision_expected_attempts(k: usize) -> u64 {
+    let space = 16f64.powi(i32::try_from(k).unwrap_or(i32::MAX));
+    (std::f64::consts::FRAC_PI_2 * space).sqrt().round() as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_suffix_collision() {
+        for k in 1..=4 {
+            let (a, b, suffix) = find_suffix_collision(k).unwrap();
+            assert!(a < b);
+            assert_eq!(suffix.len(), k);
+            let (hash_a, hash_b) = (compute_hash(a), compute_hash(b));
+            assert!(hash_a.ends_with(&suffix), "{} {}", hash_a, suffix);
+            assert!(hash_b.ends_with(&suffix), "{} {}", hash_b, suffix);
+            // Pigeonhole: a collision exists among the first 16^k + 1 numbers.
+            assert!(b <= 16u64.pow(k as u32) + 1 + CHUNK, "{}", b);
+        }
+    }
+
+    #[test]
+    fn test_find_suffix_collision_rejects_zero() {
+        let err = find_suffix_collision(0).unwrap_err();
+        assert_eq!(err.to_string(), "k must be between 1 and 16 (got 0)");
+    }
+
+    #[test]
+    fn test_collision_expected_attempts() {
+        assert_eq!(collision_expected_attempts(1), 5);
+        assert_eq!(collision_expected_attempts(4), 321);
+        assert_eq!(collision_expected_attempts(1000), u64::MAX);
+    }
+}
@@ -0,0 +1,3000 @@
+use crate::block_order::BlockOrder;
+use crate::encoding::MAX_ENCODED_LEN;
+use crate::search::{self, SearchTimer};
+use crate::{
+    digest_below_target, digest_ends_with_zeros, AlgorithmConstraint, digest_leading_zero_bits,
+    digest_ends_with_palindrome, digest_starts_with_zeros, digest_zero_count,
+    digest_ends_with_number, hash_ends_with_palindrome, hash_matches_difficulty,
+    digest_trailing_zero_nibbles, hash_starts_with_zeros, hash_zero_count, hex_decode,
+    CancellationToken, DigestBytes, HashAlgorithm, HashFinderError, HashResult, HexPattern,
+    HmacKey, InputTemplate, NonceEncoding, SearchProgress, SearchStats, Shard, SuffixSet,
+    WildcardPattern, WordResult, SHARD_BLOCK,
+};
+use rayon::prelude::*;
+use std::cmp::{self, Reverse};
+use std::collections::BinaryHeap;
+use std::fmt;
+
+#[cfg(feature = "regex")]
+use crate::HashRegex;
+use std::time::{Duration, Instant};
+use tracing::{debug, info, instrument, warn};
+
+#[cfg(feature = "checkpoint")]
+use crate::SearchCheckpoint;
+#[cfg(feature = "checkpoint")]
+use std::{collections::HashSet, path::Path};
+
+/// Numbers [`HashFinderConfig::run_resumable`] checks in full before moving its frontier,
+/// unless `chunk_size` is set.
+#[cfg(feature = "checkpoint")]
+const CHECKPOINT_CHUNK: u64 = 1 << 24;
+
+/// Block size for [`HashFinderConfig::randomize`] unless `chunk_size` is set.
+const RANDOM_BLOCK: u64 = 1 << 20;
+
+/// First and largest chunk sizes for [`HashFinderConfig::run_sorted`]; chunks double in
+/// between so easy searches finish quickly.
+const SORTED_CHUNK_MIN: u64 = 1 << 16;
+const SORTED_CHUNK_MAX: u64 = 1 << 24;
+
+/// Reason a [`HashFinderConfig`] was rejected by [`HashFinderConfig::build`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConfigError {
+    ZeroDifficulty,
+    ZeroResults,
+    ZeroThreads,
+    ZeroIterations,
+    DifficultyTooHigh {
+        zeros: usize,
+        algorithm: HashAlgorithm,
+    },
+    EmptyRange {
+        start: u64,
+        end: u64,
+    },
+    HmacRequiresSha256 {
+        algorithm: HashAlgorithm,
+    },
+    EmptyHmacKey,
+    PadWidthOutOfRange {
+        width: usize,
+    },
+    PadWidthRequiresDecimal {
+        encoding: NonceEncoding,
+    },
+    NonceExceedsPadWidth {
+        num: u64,
+        width: usize,
+    },
+    PatternTooLong {
+        digits: usize,
+        algorithm: HashAlgorithm,
+    },
+    ZeroChunkSize,
+    InvalidShard {
+        index: u64,
+        count: u64,
+    },
+    BitsTooHigh {
+        bits: u32,
+        algorithm: HashAlgorithm,
+    },
+    BitsWithPattern,
+    EmptySuffixSet,
+    ZeroTarget,
+    TargetRequires256BitDigest {
+        algorithm: HashAlgorithm,
+    },
+    TargetWithOtherCriteria,
+    WildcardWithOtherCriteria,
+    PalindromeWithOtherCriteria,
+    ZeroCountTooHigh {
+        count: usize,
+        algorithm: HashAlgorithm,
+    },
+    #[cfg(feature = "regex")]
+    RegexWithOtherCriteria,
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigError::ZeroDifficulty => write!(f, "zeros must be greater than 0"),
+            ConfigError::ZeroResults => write!(f, "max_results must be greater than 0"),
+            ConfigError::ZeroThreads => write!(f, "threads must be greater than 0"),
+            ConfigError::ZeroIterations => write!(f, "iterations must be greater than 0"),
+            ConfigError::DifficultyTooHigh { zeros, algorithm } => write!(
+                f,
+                "zeros must not exceed {} for {} (got {})",
+                algorithm.hex_len(),
+                algorithm,
+                zeros
+            ),
+            ConfigError::EmptyRange { start, end } => {
+                write!(f, "search range {}..{} is empty", start, end)
+            }
+            ConfigError::HmacRequiresSha256 { algorithm } => {
+                write!(f, "HMAC mode only supports sha256 (got {})", algorithm)
+            }
+            ConfigError::EmptyHmacKey => write!(f, "HMAC key must not be empty"),
+            ConfigError::PadWidthOutOfRange { width } => write!(
+                f,
+                "pad width must be between 1 and {} (got {})",
+                MAX_ENCODED_LEN, width
+            ),
+            ConfigError::PadWidthRequiresDecimal { encoding } => {
+                write!(f, "pad width only applies to decimal nonces (got {})", encoding)
+            }
+            ConfigError::NonceExceedsPadWidth { num, width } => {
+                write!(f, "nonce {} does not fit in {} digits", num, width)
+            }
+            ConfigError::PatternTooLong { digits, algorithm } => write!(
+                f,
+                "hex pattern needs {} digits but {} hashes have only {}",
+                digits,
+                algorithm,
+                algorithm.hex_len()
+            ),
+            ConfigError::ZeroChunkSize => write!(f, "chunk size must be greater than 0"),
+            ConfigError::InvalidShard { index, count } => write!(
+                f,
+                "shard index must be below the shard count (got {} of {})",
+                index, count
+            ),
+            ConfigError::BitsTooHigh { bits, algorithm } => write!(
+                f,
+                "leading zero bits must not exceed {} for {} (got {})",
+                algorithm.hex_len() * 4,
+                algorithm,
+                bits
+            ),
+            ConfigError::BitsWithPattern => {
+                write!(f, "leading zero bits cannot be combined with a prefix or suffix")
+            }
+            ConfigError::EmptySuffixSet => write!(f, "suffix set must not be empty"),
+            ConfigError::ZeroTarget => write!(f, "no digest is below an all-zero target"),
+            ConfigError::TargetRequires256BitDigest { algorithm } => write!(
+                f,
+                "a target needs a 256-bit digest but {} has {} bits",
+                algorithm,
+                algorithm.hex_len() * 4
+            ),
+            ConfigError::TargetWithOtherCriteria => write!(
+                f,
+                "a target cannot be combined with leading zero bits, a prefix or a suffix"
+            ),
+            ConfigError::WildcardWithOtherCriteria => write!(
+                f,
+                "a wildcard pattern cannot be combined with leading zero bits, a prefix or a suffix"
+            ),
+            ConfigError::PalindromeWithOtherCriteria => write!(
+                f,
+                "a palindrome cannot be combined with leading zero bits, a prefix or a suffix"
+            ),
+            ConfigError::ZeroCountTooHigh { count, algorithm } => write!(
+                f,
+                "zero count must not exceed {} for {} (got {})",
+                algorithm.hex_len(),
+                algorithm,
+                count
+            ),
+            #[cfg(feature = "regex")]
+            ConfigError::RegexWithOtherCriteria => write!(
+                f,
+                "a regex cannot be combined with leading zero bits, a prefix or a suffix"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+/// Which end of the hex digest the zeros must appear at.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize), serde(rename_all = "lowercase"))]
+pub enum MatchDirection {
+    Leading,
+    #[default]
+    Trailing,
+}
+
+/// How a search from [`HashFinderConfig::run_with_outcome`] or one of its siblings ended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SearchOutcome {
+    /// Found `max_results` matches, or every match in the range.
+    Completed,
+    /// The timeout expired first; the results found by then are returned.
+    TimedOut,
+    /// The cancellation token was cancelled first.
+    Cancelled,
+}
+
+/// Search parameters for a hash finder run.
+///
+/// ```
+/// use rust_hash_finder::{HashAlgorithm, HashFinderConfig};
+///
+/// let config = HashFinderConfig::new()
+///     .zeros(2)
+///     .max_results(3)
+///     .algorithm(HashAlgorithm::Sha512)
+///     .build()
+///     .unwrap();
+/// assert_eq!(config.run().unwrap().len(), 3);
+/// ```
+///
+/// With the `serde` feature it serializes for inspection, e.g. by `--dry-run`: the salt and
+/// target as hex, the HMAC key redacted, and the cancellation token and progress counters
+/// left out.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct HashFinderConfig {
+    /// Ignored while `prefix`, `suffix`, `suffixes`, `contains`, `wildcard`, `palindrome`,
+    /// `leading_zero_bits`, `regex` or `target` is set. May be 0 when `min_zero_count` or
+    /// `self_suffix` is set, which then is the only criterion.
+    pub zeros: usize,
+    /// Zero bits matching digests must start with, for difficulty finer than whole hex
+    /// digits.
+    pub leading_zero_bits: Option<u32>,
+    /// Hex digits matching hashes must start with, in place of `zeros` zeros.
+    pub prefix: Option<HexPattern>,
+    /// Hex digits matching hashes must end with, in place of `zeros` zeros; with a
+    /// `prefix` as well, both must match.
+    pub suffix: Option<HexPattern>,
+    /// Alternative hex suffixes, any one of which a matching hash must end with; results
+    /// record which one it was.
+    pub suffixes: Option<SuffixSet>,
+    /// Hex digits matching hashes must contain anywhere; with a `prefix` or suffix as well,
+    /// all must match.
+    pub contains: Option<HexPattern>,
+    /// Hex digits and `?` wildcards matching hashes must end with, or start with when
+    /// `direction` is [`MatchDirection::Leading`].
+    pub wildcard: Option<WildcardPattern>,
+    /// Number of trailing hex digits of matching hashes that must read the same backwards;
+    /// the algorithm's full hex length asks for a whole-digest palindrome.
+    pub palindrome: Option<usize>,
+    /// Regex the lowercase hex hash must match, in place of any other criterion.
+    #[cfg(feature = "regex")]
+    pub regex: Option<HashRegex>,
+    /// Matching digests, read as 256-bit big-endian integers, must be strictly below this,
+    /// as in real proof of work.
+    #[cfg_attr(feature = "serde", serde(serialize_with = "serialize_hex"))]
+    pub target: Option<[u8; 32]>,
+    /// Fewest `'0'` digits matching hashes must contain anywhere, on top of the other
+    /// criteria.
+    pub min_zero_count: Option<usize>,
+    /// Matching hashes must also end with the decimal digits of their own number, a suffix
+    /// that grows with the number; see [`HashFinderConfig::matches_self_suffix`].
+    pub self_suffix: bool,
+    pub max_results: usize,
+    /// First number to hash.
+    pub start: u64,
+    /// Exclusive upper bound; `None` searches until `max_results` are found, or until the
+    /// nonces no longer fit in `pad_width`.
+    pub end: Option<u64>,
+    pub algorithm: HashAlgorithm,
+    pub nonce_encoding: NonceEncoding,
+    /// Decimal nonces are left-padded with zeros to this many digits, e.g. `0000123`.
+    pub pad_width: Option<usize>,
+    pub direction: MatchDirection,
+    /// Bytes fed to the hasher before each encoded nonce; a different salt gives a
+    /// completely different hash sequence.
+    #[cfg_attr(feature = "serde", serde(serialize_with = "serialize_hex"))]
+    pub salt: Option<Vec<u8>>,
+    /// Surrounding input the encoded nonce is substituted into, after any salt.
+    pub template: Option<InputTemplate>,
+    /// Size of a dedicated thread pool; `None` uses Rayon's global pool.
+    pub threads: Option<usize>,
+    /// When set, [`HashFinderConfig::run`] searches the aligned ranges
+    /// `[n * chunk_size, (n + 1) * chunk_size)` one after another, each with an indexed
+    /// parallel iterator, instead of bridging an unbounded iterator.
+    pub chunk_size: Option<u64>,
+    /// When set, [`HashFinderConfig::run`] visits aligned blocks of `chunk_size`, or 2^20,
+    /// numbers in an order shuffled by this seed instead of counting up from `start`; see
+    /// [`HashFinderConfig::randomize`].
+    pub randomize: Option<u64>,
+    /// When set, [`HashFinderConfig::run`] only searches this shard's blocks of
+    /// `chunk_size`, or [`SHARD_BLOCK`], numbers; see [`HashFinderConfig::shard`].
+    pub shard: Option<Shard>,
+    /// Stop searching after this long and return whatever was found.
+    pub timeout: Option<Duration>,
+    /// Least time between checkpoint saves in [`HashFinderConfig::run_resumable`]; `None`
+    /// saves after every chunk.
+    #[cfg(feature = "checkpoint")]
+    pub checkpoint_interval: Option<Duration>,
+    /// When set, matches are searched in the HMAC-SHA256 output under this key.
+    pub hmac_key: Option<HmacKey>,
+    /// Times the hash is applied; each round after the first hashes the previous raw digest.
+    pub iterations: u32,
+    /// Token another thread can cancel to stop the search early.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub cancellation: Option<CancellationToken>,
+    /// Counters updated for every hash computed and match found.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub progress: Option<SearchProgress>,
+    /// Further algorithms whose digests of the same input must also match, checked only
+    /// once the main algorithm matches.
+    pub also: Vec<AlgorithmConstraint>,
+}
+
+impl Default for HashFinderConfig {
+    fn default() -> Self {
+        HashFinderConfig {
+            zeros: 0,
+            leading_zero_bits: None,
+            prefix: None,
+            suffix: None,
+            suffixes: None,
+            contains: None,
+            wildcard: None,
+            palindrome: None,
+            #[cfg(feature = "regex")]
+            regex: None,
+            target: None,
+            min_zero_count: None,
+            self_suffix: false,
+            max_results: 0,
+            start: 1,
+            end: None,
+            algorithm: HashAlgorithm::default(),
+            nonce_encoding: NonceEncoding::default(),
+            pad_width: None,
+            direction: MatchDirection::default(),
+            salt: None,
+            template: None,
+            threads: None,
+            chunk_size: None,
+            randomize: None,
+            shard: None,
+            timeout: None,
+            #[cfg(feature = "checkpoint")]
+            checkpoint_interval: None,
+            hmac_key: None,
+            iterations: 1,
+            cancellation: None,
+            progress: None,
+            also: Vec::new(),
+        }
+    }
+}
+
+impl HashFinderConfig {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn zeros(mut self, zeros: usize) -> Self {
+        self.zeros = zeros;
+        self
+    }
+
+    pub fn leading_zero_bits(mut self, bits: u32) -> Self {
+        self.leading_zero_bits = Some(bits);
+        self
+    }
+
+    pub fn prefix(mut self, prefix: HexPattern) -> Self {
+        self.prefix = Some(prefix);
+        self
+    }
+
+    pub fn suffix(mut self, suffix: HexPattern) -> Self {
+        self.suffix = Some(suffix);
+        self
+    }
+
+    /// Matches hashes ending with any of `suffixes`, in a single pass.
+    pub fn suffixes(mut self, suffixes: SuffixSet) -> Self {
+        self.suffixes = Some(suffixes);
+        self
+    }
+
+    pub fn contains(mut self, needle: HexPattern) -> Self {
+        self.contains = Some(needle);
+        self
+    }
+
+    /// Matches hashes ending with `wildcard`, or starting with it when the direction is
+    /// [`MatchDirection::Leading`].
+    pub fn wildcard(mut self, wildcard: WildcardPattern) -> Self {
+        self.wildcard = Some(wildcard);
+        self
+    }
+
+    /// Matches hashes whose last `len` hex digits form a palindrome; see
+    /// [`hash_ends_with_palindrome`](crate::hash_ends_with_palindrome).
+    ///
+    /// Only every other digit is free, so each match takes about `16^(len / 2)` hashes: a
+    /// whole SHA-256 palindrome would take `16^32`.
+    pub fn palindrome(mut self, len: usize) -> Self {
+        self.palindrome = Some(len);
+        self
+    }
+
+    /// Matches hashes against `regex`; see [`HashRegex`] for the throughput cost.
+    #[cfg(feature = "regex")]
+    pub fn regex(mut self, regex: HashRegex) -> Self {
+        self.regex = Some(regex);
+        self
+    }
+
+    /// Matches digests numerically below `target`; see
+    /// [`digest_below_target`](crate::digest_below_target).
+    pub fn target(mut self, target: [u8; 32]) -> Self {
+        self.target = Some(target);
+        self
+    }
+
+    /// Also requires at least `count` `'0'` digits anywhere in the hash; see
+    /// [`hash_zero_count`](crate::hash_zero_count).
+    pub fn min_zero_count(mut self, count: usize) -> Self {
+        self.min_zero_count = Some(count);
+        self
+    }
+
+    /// Also requires each hash to end with its own number's decimal digits, e.g. `4163`
+    /// for the hash of 4163; see [`hash_ends_with_number`](crate::hash_ends_with_number).
+    pub fn self_suffix(mut self, self_suffix: bool) -> Self {
+        self.self_suffix = self_suffix;
+        self
+    }
+
+    pub fn max_results(mut self, max_results: usize) -> Self {
+        self.max_results = max_results;
+        self
+    }
+
+    pub fn start_from(mut self, start: u64) -> Self {
+        self.start = start;
+        self
+    }
+
+    pub fn end(mut self, end: u64) -> Self {
+        self.end = Some(end);
+        self
+    }
+
+    pub fn algorithm(mut self, algorithm: HashAlgorithm) -> Self {
+        self.algorithm = algorithm;
+        self
+    }
+
+    pub fn nonce_encoding(mut self, nonce_encoding: NonceEncoding) -> Self {
+        self.nonce_encoding = nonce_encoding;
+        self
+    }
+
+    pub fn pad_width(mut self, width: usize) -> Self {
+        self.pad_width = Some(width);
+        self
+    }
+
+    pub fn direction(mut self, direction: MatchDirection) -> Self {
+        self.direction = direction;
+        self
+    }
+
+    pub fn salt(mut self, salt: impl Into<Vec<u8>>) -> Self {
+        self.salt = Some(salt.into());
+        self
+    }
+
+    pub fn template(mut self, template: InputTemplate) -> Self {
+        self.template = Some(template);
+        self
+    }
+
+    pub fn threads(mut self, threads: usize) -> Self {
+        self.threads = Some(threads);
+        self
+    }
+
+    pub fn chunk_size(mut self, chunk_size: u64) -> Self {
+        self.chunk_size = Some(chunk_size);
+        self
+    }
+
+    /// Visits the search space in blocks shuffled by `seed`, scanning each block in order,
+    /// so different seeds surface different results while the same seed repeats its block
+    /// order. Every block of a bounded range is still visited, so the scan stays exhaustive.
+    ///
+    /// Without an `end` the blocks span all of `start..u64::MAX`, so results are usually
+    /// very large numbers.
+    ///
+    /// ```
+    /// use rust_hash_finder::{hash_matches_difficulty, HashFinderConfig};
+    ///
+    /// let results = HashFinderConfig::new().zeros(2).max_results(3).randomize(42).run().unwrap();
+    /// assert!(results.iter().all(|r| hash_matches_difficulty(&r.hash, 2) == Ok(true)));
+    /// ```
+    pub fn randomize(mut self, seed: u64) -> Self {
+        self.randomize = Some(seed);
+        self
+    }
+
+    /// Splits the search into `count` shards and searches only shard `index`, so `count`
+    /// machines given the same config and each index in `0..count` cover the space between
+    /// them with no overlap and no gaps. Block `b`, the numbers
+    /// `[b * chunk_size, (b + 1) * chunk_size)` with `chunk_size` defaulting to
+    /// [`SHARD_BLOCK`], belongs to shard `b % count`; every shard must use the same
+    /// `chunk_size`. Results are tagged with the shard for merging.
+    ///
+    /// ```
+    /// use rust_hash_finder::HashFinderConfig;
+    ///
+    /// let config = HashFinderConfig::new().zeros(3).max_results(10).end(20_000).chunk_size(1000);
+    /// let mut numbers: Vec<u64> = (0..3)
+    ///     .flat_map(|index| config.clone().shard(index, 3).run().unwrap())
+    ///     .map(|r| r.number)
+    ///     .collect();
+    /// numbers.sort_unstable();
+    /// assert_eq!(numbers, [4163, 11848, 12843, 13467]);
+    /// ```
+    pub fn shard(mut self, index: u64, count: u64) -> Self {
+        self.shard = Some(Shard::new(index, count));
+        self
+    }
+
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    #[cfg(feature = "checkpoint")]
+    pub fn checkpoint_interval(mut self, interval: Duration) -> Self {
+        self.checkpoint_interval = Some(interval);
+        self
+    }
+
+    pub fn hmac_key(mut self, key: HmacKey) -> Self {
+        self.hmac_key = Some(key);
+        self
+    }
+
+    pub fn iterations(mut self, iterations: u32) -> Self {
+        self.iterations = iterations;
+        self
+    }
+
+    pub fn cancellation(mut self, token: CancellationToken) -> Self {
+        self.cancellation = Some(token);
+        self
+    }
+
+    pub fn progress(mut self, progress: SearchProgress) -> Self {
+        self.progress = Some(progress);
+        self
+    }
+
+    /// Adds a constraint every match must also satisfy; may be called repeatedly.
+    ///
+    /// Only nonces matching the main algorithm are hashed with `constraint.algorithm`, so
+    /// the cheaper algorithm should be the main one.
+    pub fn also(mut self, constraint: AlgorithmConstraint) -> Self {
+        self.also.push(constraint);
+        self
+    }
+
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        match self.leading_zero_bits {
+            Some(0) => return Err(ConfigError::ZeroDifficulty),
+            None if self.zeros == 0
+                && self.pattern_len().is_none()
+                && self.wildcard.is_none()
+                && self.palindrome.is_none()
+                && !self.has_regex()
+                && self.target.is_none()
+                && self.min_zero_count.is_none()
+                && !self.self_suffix =>
+            {
+                return Err(ConfigError::ZeroDifficulty);
+            }
+            _ => {}
+        }
+        match self.min_zero_count {
+            Some(0) => return Err(ConfigError::ZeroDifficulty),
+            Some(count) if count > self.algorithm.hex_len() => {
+                return Err(ConfigError::ZeroCountTooHigh {
+                    count,
+                    algorithm: self.algorithm,
+                });
+            }
+            _ => {}
+        }
+        if self.max_results == 0 {
+            return Err(ConfigError::ZeroResults);
+        }
+        if self.threads == Some(0) {
+            return Err(ConfigError::ZeroThreads);
+        }
+        if self.chunk_size == Some(0) {
+            return Err(ConfigError::ZeroChunkSize);
+        }
+        if let Some(Shard { index, count }) = self.shard
+            && index >= count
+        {
+            return Err(ConfigError::InvalidShard { index, count });
+        }
+        if self.iterations == 0 {
+            return Err(ConfigError::ZeroIterations);
+        }
+        if self.suffixes.as_ref().is_some_and(SuffixSet::is_empty) {
+            return Err(ConfigError::EmptySuffixSet);
+        }
+        #[cfg(feature = "regex")]
+        if self.regex.is_some() && self.criteria_count() > 1 {
+            return Err(ConfigError::RegexWithOtherCriteria);
+        }
+        if let Some(wildcard) = &self.wildcard {
+            if self.criteria_count() > 1 {
+                return Err(ConfigError::WildcardWithOtherCriteria);
+            }
+            if wildcard.len() > self.algorithm.hex_len() {
+                return Err(ConfigError::PatternTooLong {
+                    digits: wildcard.len(),
+                    algorithm: self.algorithm,
+                });
+            }
+        }
+        if let Some(len) = self.palindrome {
+            if len == 0 {
+                return Err(ConfigError::ZeroDifficulty);
+            }
+            if self.criteria_count() > 1 {
+                return Err(ConfigError::PalindromeWithOtherCriteria);
+            }
+            if len > self.algorithm.hex_len() {
+                return Err(ConfigError::PatternTooLong {
+                    digits: len,
+                    algorithm: self.algorithm,
+                });
+            }
+        }
+        if let Some(target) = &self.target {
+            if self.criteria_count() > 1 {
+                return Err(ConfigError::TargetWithOtherCriteria);
+            }
+            if *target == [0; 32] {
+                return Err(ConfigError::ZeroTarget);
+            }
+            if self.algorithm.hex_len() != 64 {
+                return Err(ConfigError::TargetRequires256BitDigest {
+                    algorithm: self.algorithm,
+                });
+            }
+        }
+        if let Some(bits) = self.leading_zero_bits {
+            if self.pattern_len().is_some() {
+                return Err(ConfigError::BitsWithPattern);
+            }
+            if bits as usize > self.algorithm.hex_len() * 4 {
+                return Err(ConfigError::BitsTooHigh {
+                    bits,
+                    algorithm: self.algorithm,
+                });
+            }
+        }
+        match self.longest_pattern_len() {
+            Some(digits) if digits > self.algorithm.hex_len() => {
+                return Err(ConfigError::PatternTooLong {
+                    digits,
+                    algorithm: self.algorithm,
+                });
+            }
+            Some(_) => {}
+            None if !self.has_regex()
+                && self.target.is_none()
+                && self.wildcard.is_none()
+                && self.palindrome.is_none()
+                && self.zeros > self.algorithm.hex_len() =>
+            {
+                return Err(ConfigError::DifficultyTooHigh {
+                    zeros: self.zeros,
+                    algorithm: self.algorithm,
+                });
+            }
+            None => {}
+        }
+        for constraint in &self.also {
+            if constraint.zeros == 0 {
+                return Err(ConfigError::ZeroDifficulty);
+            }
+            if constraint.zeros > constraint.algorithm.hex_len() {
+                return Err(ConfigError::DifficultyTooHigh {
+                    zeros: constraint.zeros,
+                    algorithm: constraint.algorithm,
+                });
+            }
+        }
+        if self.hmac_key.as_ref().is_some_and(|key| key.as_bytes().is_empty()) {
+            return Err(ConfigError::EmptyHmacKey);
+        }
+        if self.hmac_key.is_some() && self.algorithm != HashAlgorithm::Sha256 {
+            return Err(ConfigError::HmacRequiresSha256 {
+                algorithm: self.algorithm,
+            });
+        }
+        if let Some(end) = self.end
+            && end <= self.start
+        {
+            return Err(ConfigError::EmptyRange {
+                start: self.start,
+                end,
+            });
+        }
+        if let Some(width) = self.pad_width {
+            if width == 0 || width > MAX_ENCODED_LEN {
+                return Err(ConfigError::PadWidthOutOfRange { width });
+            }
+            if self.nonce_encoding != NonceEncoding::DecimalString {
+                return Err(ConfigError::PadWidthRequiresDecimal {
+                    encoding: self.nonce_encoding,
+                });
+            }
+            let last = self.end.map_or(self.start, |end| end - 1);
+            if !self.fits_pad_width(last) {
+                return Err(ConfigError::NonceExceedsPadWidth { num: last, width });
+            }
+        }
+        Ok(())
+    }
+
+    pub fn build(self) -> Result<Self, ConfigError> {
+        self.validate()?;
+        Ok(self)
+    }
+
+    /// Runs the search described by this config.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the config is invalid; see [`HashFinderConfig::build`].
+    pub fn run(&self) -> Result<Vec<HashResult>, HashFinderError> {
+        self.run_with_outcome().map(|(results, _)| results)
+    }
+
+    /// Like [`HashFinderConfig::run`], but accepts the numbers for which
+    /// `predicate(number, hash)` holds, where `hash` is the number's digest as lowercase
+    /// hex, in place of the config's own match criteria.
+    ///
+    /// Everything else, such as the range, algorithm, salt, threads and timeout, applies
+    /// as usual. The predicate runs once per candidate on every search thread at once, so
+    /// it should be cheap and must not rely on being called in order.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the config is invalid once its match criteria are dropped; see
+    /// [`HashFinderConfig::build`].
+    #[instrument(skip_all, fields(max_results = self.max_results, start = self.start, algo = %self.algorithm))]
+    pub fn run_where<F>(&self, predicate: F) -> Result<Vec<HashResult>, HashFinderError>
+    where
+        F: Fn(u64, &str) -> bool + Sync,
+    {
+        self.without_criteria().zeros(1).validate()?;
+
+        let timer = SearchTimer::start(self.timeout, self.cancellation.clone());
+        let matcher = self.matcher_where(|num, digest| predicate(num, &digest.to_hex()));
+        self.search_all(&timer, matcher)
+    }
+
+    /// Like [`HashFinderConfig::run`], but also reports whether the search completed or was
+    /// stopped by the timeout or cancellation token, in which case the results are partial.
+    ///
+    /// The timeout is watched by a separate thread that raises a flag the scan polls, so
+    /// the deadline costs no clock reads per hash.
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use rust_hash_finder::{HashFinderConfig, SearchOutcome};
+    ///
+    /// let config = HashFinderConfig::new().zeros(30).max_results(1);
+    /// let config = config.timeout(Duration::from_millis(100));
+    /// let (results, outcome) = config.run_with_outcome().unwrap();
+    /// assert!(results.is_empty());
+    /// assert_eq!(outcome, SearchOutcome::TimedOut);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Fails if the config is invalid; see [`HashFinderConfig::build`].
+    #[instrument(name = "run", skip_all, fields(zeros = self.zeros, max_results = self.max_results, start = self.start, algo = %self.algorithm, encoding = %self.nonce_encoding))]
+    pub fn run_with_outcome(&self) -> Result<(Vec<HashResult>, SearchOutcome), HashFinderError> {
+        self.validate()?;
+
+        debug!(
+            "Searching for {} hashes with {} {:?} zeros",
+            self.algorithm, self.zeros, self.direction
+        );
+
+        let timer = SearchTimer::start(self.timeout, self.cancellation.clone());
+        let results = self.search_all(&timer, self.matcher())?;
+        let outcome = self.outcome(results.len(), &timer);
+        Ok((results, outcome))
+    }
+
+    /// How a search that found `found` results under `timer` ended.
+    fn outcome(&self, found: usize, timer: &SearchTimer) -> SearchOutcome {
+        if found >= self.max_results {
+            SearchOutcome::Completed
+        } else if timer.timed_out() {
+            SearchOutcome::TimedOut
+        } else if timer.should_stop() {
+            SearchOutcome::Cancelled
+        } else {
+            SearchOutcome::Completed
+        }
+    }
+
+    /// Like [`HashFinderConfig::run`], but fails rather than returning fewer than
+    /// `max_results` results.
+    ///
+    /// Returns [`HashFinderError::SearchCancelled`] if the timeout expires or the search is
+    /// cancelled first, and [`HashFinderError::RangeExhausted`] if the bounded range holds
+    /// fewer matches.
+    #[instrument(skip_all, fields(zeros = self.zeros, max_results = self.max_results, start = self.start, algo = %self.algorithm))]
+    pub fn run_exact(&self) -> Result<Vec<HashResult>, HashFinderError> {
+        self.validate()?;
+
+        let timer = SearchTimer::start(self.timeout, self.cancellation.clone());
+        let results = self.search_all(&timer, self.matcher())?;
+        match results.len().cmp(&self.max_results) {
+            cmp::Ordering::Equal => Ok(results),
+            cmp::Ordering::Greater => unreachable!(
+                "search returned {} results for max_results {}",
+                results.len(),
+                self.max_results
+            ),
+            cmp::Ordering::Less if timer.should_stop() => Err(HashFinderError::SearchCancelled),
+            cmp::Ordering::Less => Err(HashFinderError::RangeExhausted {
+                found: results.len(),
+                max_results: self.max_results,
+            }),
+        }
+    }
+
+    /// Counts the numbers from `start` up to `end` that match, without collecting them, e.g.
+    /// to calibrate a difficulty. `max_results` and `timeout` do not apply: the whole range
+    /// is always scanned.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the config is invalid apart from `max_results`, or with
+    /// [`HashFinderError::UnboundedRange`] if neither `end` nor `pad_width` bounds the range.
+    pub fn count_matches(&self) -> Result<u64, HashFinderError> {
+        self.clone().max_results(1).validate()?;
+        let end = self.search_end().ok_or(HashFinderError::UnboundedRange)?;
+        let matcher = self.matcher();
+        self.install(|| {
+            (self.start..end)
+                .into_par_iter()
+                .fold(|| 0, |count, num| count + u64::from(matcher(num).is_some()))
+                .sum()
+        })
+    }
+
+    /// The `k` numbers from `start` up to `end` whose hashes have the most trailing zeros,
+    /// most zeros first with ties going to the lower number, each with `trailing_zeros` set.
+    ///
+    /// Ranks hashes instead of matching them, so the match criteria, `max_results` and
+    /// `timeout` do not apply: the whole range is always scanned.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the config is invalid apart from `zeros` and `max_results`, or with
+    /// [`HashFinderError::UnboundedRange`] if neither `end` nor `pad_width` bounds the range.
+    pub fn run_top_k_zeros(&self, k: usize) -> Result<Vec<HashResult>, HashFinderError> {
+        // Ranking needs no difficulty or result count, but the other settings must be valid.
+        self.clone().zeros(1).max_results(1).validate()?;
+        let end = self.search_end().ok_or(HashFinderError::UnboundedRange)?;
+        // Min-heaps of the best `k` ranks seen, so the worst is the one to evict.
+        let top = self.install(|| {
+            (self.start..end)
+                .into_par_iter()
+                .fold(BinaryHeap::new, |mut top, num| {
+                    let zeros = digest_trailing_zero_nibbles(&self.hash_digest(num));
+                    push_top_k(&mut top, k, (zeros, Reverse(num)));
+                    top
+                })
+                .reduce(BinaryHeap::new, |mut top, other| {
+                    for Reverse(rank) in other {
+                        push_top_k(&mut top, k, rank);
+                    }
+                    top
+                })
+        })?;
+        Ok(top
+            .into_sorted_vec()
+            .into_iter()
+            .map(|Reverse((zeros, Reverse(num)))| {
+                HashResult::new(num, self.hash(num)).with_trailing_zeros(zeros)
+            })
+            .collect())
+    }
+
+    /// The search behind [`HashFinderConfig::run`], reporting the numbers `matcher` accepts
+    /// and stopping early when `timer` does.
+    fn search_all<M>(
+        &self,
+        timer: &SearchTimer,
+        matcher: M,
+    ) -> Result<Vec<HashResult>, HashFinderError>
+    where
+        M: Fn(u64) -> Option<HashResult> + Sync,
+    {
+        let pool = self.thread_pool()?;
+        let results = self.search_candidates(pool.as_ref(), timer, matcher);
+
+        if timer.should_stop() {
+            info!(
+                "Search stopped early with {} of {} results",
+                results.len(),
+                self.max_results
+            );
+        } else if self.search_end().is_none() && results.len() < self.max_results {
+            warn!(
+                "Search space exhausted at u64::MAX with {} of {} results",
+                results.len(),
+                self.max_results
+            );
+        }
+        Ok(match self.shard {
+            Some(shard) => results.into_iter().map(|r| r.with_shard(shard)).collect(),
+            None => results,
+        })
+    }
+
+    /// Scans the numbers this config visits, in the order `randomize`, `shard` and
+    /// `chunk_size` give them, on `pool` until `max_results` are accepted by `matcher` or
+    /// `timer` stops; shared by [`HashFinderConfig::run`] and [`HashStream`].
+    ///
+    /// [`HashStream`]: crate::HashStream
+    pub(crate) fn search_candidates<M>(
+        &self,
+        pool: Option<&rayon::ThreadPool>,
+        timer: &SearchTimer,
+        matcher: M,
+    ) -> Vec<HashResult>
+    where
+        M: Fn(u64) -> Option<HashResult> + Sync,
+    {
+        let running = |_: &u64| !timer.should_stop();
+        install_on(pool, || match (self.randomize, self.chunk_size, self.search_end()) {
+            (Some(seed), chunk_size, end) => self.search_blocks(
+                Some(seed),
+                chunk_size.unwrap_or(RANDOM_BLOCK),
+                end.unwrap_or(u64::MAX),
+                timer,
+                &matcher,
+            ),
+            (None, chunk_size, end) if self.shard.is_some() => self.search_blocks(
+                None,
+                chunk_size.unwrap_or(SHARD_BLOCK),
+                end.unwrap_or(u64::MAX),
+                timer,
+                &matcher,
+            ),
+            (None, Some(chunk_size), end) => {
+                self.search_chunks(chunk_size, end.unwrap_or(u64::MAX), timer, &matcher)
+            }
+            (None, None, Some(end)) => search::search(
+                (self.start..end).into_par_iter().take_any_while(running),
+                self.max_results,
+                &matcher,
+            ),
+            // Inclusive, so the last number is searched and the space ends without
+            // overflowing.
+            (None, None, None) => search::search(
+                (self.start..=u64::MAX).par_bridge().take_any_while(running),
+                self.max_results,
+                &matcher,
+            ),
+        })
+    }
+
+    /// Runs the search starting after `checkpoint.last_checked`, recording progress into
+    /// `checkpoint` and saving it to `save_to`.
+    ///
+    /// Numbers are checked in chunks of `chunk_size`, or 2^24, and `last_checked` only
+    /// moves past a chunk once all of it has been checked, so a search stopped at any point
+    /// resumes without skipping any number. The checkpoint is saved at most once per
+    /// `checkpoint_interval` and again when the search stops.
+    ///
+    /// Matches already in the checkpoint count towards `max_results` and are included in
+    /// the returned results.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the config is invalid (see [`HashFinderConfig::build`]), and with
+    /// [`HashFinderError::IoError`] of kind [`std::io::ErrorKind::InvalidInput`] if the checkpoint
+    /// was saved by a search with different match criteria (see
+    /// [`SearchCheckpoint::check_params`]), or if saving it fails.
+    #[cfg(feature = "checkpoint")]
+    pub fn run_resumable(
+        &self,
+        checkpoint: &mut SearchCheckpoint,
+        save_to: Option<&Path>,
+    ) -> Result<Vec<HashResult>, HashFinderError> {
+        self.run_resumable_with_outcome(checkpoint, save_to).map(|(results, _)| results)
+    }
+
+    /// Like [`HashFinderConfig::run_resumable`], but also reports how the search ended, as
+    /// [`HashFinderConfig::run_with_outcome`] does.
+    ///
+    /// # Errors
+    ///
+    /// Fails like [`HashFinderConfig::run_resumable`].
+    #[cfg(feature = "checkpoint")]
+    #[instrument(name = "run_resumable", skip_all, fields(zeros = self.zeros, max_results = self.max_results, resume_from = checkpoint.last_checked))]
+    pub fn run_resumable_with_outcome(
+        &self,
+        checkpoint: &mut SearchCheckpoint,
+        save_to: Option<&Path>,
+    ) -> Result<(Vec<HashResult>, SearchOutcome), HashFinderError> {
+        self.validate()?;
+        checkpoint.check_params(self)?;
+
+        let mut next = match checkpoint.last_checked {
+            0 => self.start,
+            last => self.start.max(last.saturating_add(1)),
+        };
+        let end = self.search_end().unwrap_or(u64::MAX);
+        info!(
+            "Resuming search at {} with {} results already found",
+            next,
+            checkpoint.found.len()
+        );
+
+        let pool = self.thread_pool()?;
+        let timer = SearchTimer::start(self.timeout, self.cancellation.clone());
+        let running = |_: &u64| !timer.should_stop();
+        let chunk = self.chunk_size.unwrap_or(CHECKPOINT_CHUNK);
+        let mut saved_at = Instant::now();
+        while next < end && checkpoint.found.len() < self.max_results && !timer.should_stop() {
+            let chunk_end = next.saturating_add(chunk).min(end);
+            let remaining = self.max_results - checkpoint.found.len();
+
+            // A chunk cut short by reaching `max_results` is searched again on resume, so
+            // skip the matches it already produced.
+            let known: HashSet<u64> = checkpoint.found.iter().map(|r| r.number).collect();
+            let matcher = self.matcher();
+            let mut found = install_on(pool.as_ref(), || {
+                let candidates = (next..chunk_end).into_par_iter().take_any_while(running);
+                search::search(candidates, remaining, |num| {
+                    if known.contains(&num) {
+                        None
+                    } else {
+                        matcher(num)
+                    }
+                })
+            });
+            found.sort_unstable_by_key(|r| r.number);
+            if found.len() < remaining && !timer.should_stop() {
+                checkpoint.last_checked = chunk_end - 1;
+            }
+            checkpoint.found.extend(found);
+            next = chunk_end;
+
+            let due = self.checkpoint_interval.is_none_or(|every| saved_at.elapsed() >= every);
+            if let Some(path) = save_to.filter(|_| due) {
+                checkpoint.save(path)?;
+                saved_at = Instant::now();
+                debug!("Saved checkpoint at {}", checkpoint.last_checked);
+            }
+        }
+
+        if let Some(path) = save_to {
+            checkpoint.save(path)?;
+        }
+        let outcome = self.outcome(checkpoint.found.len(), &timer);
+        Ok((checkpoint.found.clone(), outcome))
+    }
+
+    /// Hashes each of `numbers` in parallel and returns the matches in input order, e.g. to
+    /// filter candidates produced elsewhere. `start`, `end` and `max_results` do not apply.
+    ///
+    /// ```
+    /// use rust_hash_finder::HashFinderConfig;
+    ///
+    /// let config = HashFinderConfig::new().zeros(3).max_results(1);
+    /// let found = config.filter_numbers(&[13467, 5, 4163]).unwrap();
+    /// assert_eq!(found.iter().map(|r| r.number).collect::<Vec<_>>(), [13467, 4163]);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Fails if the config is invalid; see [`HashFinderConfig::build`].
+    pub fn filter_numbers(&self, numbers: &[u64]) -> Result<Vec<HashResult>, HashFinderError> {
+        self.validate()?;
+
+        let matcher = self.matcher();
+        self.install(|| numbers.par_iter().filter_map(|&num| matcher(num)).collect())
+    }
+
+    /// Hashes each of `words` in parallel in place of the nonce, see
+    /// [`HashFinderConfig::hash_word`], and returns the matches in input order.
+    /// `start`, `end`, `max_results` and `self_suffix`, which need a number, do not apply.
+    ///
+    /// ```
+    /// use rust_hash_finder::HashFinderConfig;
+    ///
+    /// let config = HashFinderConfig::new().zeros(4).max_results(1);
+    /// let found = config.filter_words(&["apple", "word1690", "banana"]).unwrap();
+    /// assert_eq!(found[0].word, "word1690");
+    /// assert!(found[0].hash.ends_with("0000"));
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Fails if the config is invalid; see [`HashFinderConfig::build`].
+    pub fn filter_words<W: AsRef<str> + Sync>(
+        &self,
+        words: &[W],
+    ) -> Result<Vec<WordResult>, HashFinderError> {
+        self.validate()?;
+
+        self.install(|| {
+            words
+                .par_iter()
+                .filter_map(|word| {
+                    let word = word.as_ref();
+                    let digest = self.digest_input_as(word.as_bytes(), None);
+                    let matched = self.matches_digest(&digest)
+                        && self.input_matches_also(word.as_bytes());
+                    matched.then(|| WordResult::new(word.to_string(), digest.to_hex()))
+                })
+                .collect()
+        })
+    }
+
+    /// Runs the search once per salt in `salts`, replacing any salt of the config, and
+    /// returns the results in the same order as the salts.
+    ///
+    /// With `threads` set, one pool is built and shared by every search rather than one per
+    /// salt. The timeout applies to each search separately.
+    ///
+    /// ```
+    /// use rust_hash_finder::HashFinderConfig;
+    ///
+    /// let config = HashFinderConfig::new().zeros(2).max_results(1);
+    /// let results = config.run_per_salt(&[b"a".to_vec(), b"b".to_vec()]).unwrap();
+    /// assert_eq!(results[1][0].hash, config.clone().salt("b").hash(results[1][0].number));
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Fails if the config is invalid; see [`HashFinderConfig::build`].
+    pub fn run_per_salt(&self, salts: &[Vec<u8>]) -> Result<Vec<Vec<HashResult>>, HashFinderError> {
+        self.validate()?;
+
+        let mut shared = self.clone();
+        shared.threads = None;
+        self.install(|| {
+            salts
+                .iter()
+                .map(|salt| {
+                    debug!("Searching with salt {}", crate::hex_encode(salt));
+                    shared.clone().salt(salt.clone()).run()
+                })
+                .collect()
+        })?
+    }
+
+    /// Like [`HashFinderConfig::run`], but returns the `max_results` smallest matching
+    /// numbers in ascending order, so every run gives the same output.
+    ///
+    /// Numbers are searched in chunks, each checked in full before the next, which costs a
+    /// little throughput. If the timeout expires or the search is cancelled, the results
+    /// are still sorted but may skip smaller matches in the interrupted chunk.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the config is invalid; see [`HashFinderConfig::build`].
+    pub fn run_sorted(&self) -> Result<Vec<HashResult>, HashFinderError> {
+        self.run_sorted_with_outcome().map(|(results, _)| results)
+    }
+
+    /// Like [`HashFinderConfig::run_sorted`], but also reports how the search ended, as
+    /// [`HashFinderConfig::run_with_outcome`] does.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the config is invalid; see [`HashFinderConfig::build`].
+    #[instrument(name = "run_sorted", skip_all, fields(zeros = self.zeros, max_results = self.max_results, start = self.start, algo = %self.algorithm))]
+    pub fn run_sorted_with_outcome(
+        &self,
+    ) -> Result<(Vec<HashResult>, SearchOutcome), HashFinderError> {
+        self.validate()?;
+
+        let end = self.search_end().unwrap_or(u64::MAX);
+        let timer = SearchTimer::start(self.timeout, self.cancellation.clone());
+        let running = |_: &u64| !timer.should_stop();
+        let mut results = self.install(|| {
+            let mut results = Vec::new();
+            let mut next = self.start;
+            let mut chunk = SORTED_CHUNK_MIN;
+            while next < end && results.len() < self.max_results && !timer.should_stop() {
+                let chunk_end = next.saturating_add(chunk).min(end);
+                let candidates = (next..chunk_end).into_par_iter().take_any_while(running);
+                let mut found = search::search(candidates, usize::MAX, self.matcher());
+                found.sort_unstable_by_key(|r| r.number);
+                results.extend(found);
+                next = chunk_end;
+                chunk = (chunk * 2).min(SORTED_CHUNK_MAX);
+            }
+            results
+        })?;
+
+        results.truncate(self.max_results);
+        let outcome = self.outcome(results.len(), &timer);
+        Ok((results, outcome))
+    }
+
+    /// Searches the chunks of `chunk_size` numbers overlapping `start..end` in order until
+    /// `max_results` matches are found.
+    fn search_chunks<M>(
+        &self,
+        chunk_size: u64,
+        end: u64,
+        timer: &SearchTimer,
+        matcher: &M,
+    ) -> Vec<HashResult>
+    where
+        M: Fn(u64) -> Option<HashResult> + Sync,
+    {
+        let running = |_: &u64| !timer.should_stop();
+        let mut results = Vec::new();
+        let mut next = self.start;
+        while next < end && results.len() < self.max_results && !timer.should_stop() {
+            let chunk_end = (next / chunk_size + 1).saturating_mul(chunk_size).min(end);
+            let candidates = (next..chunk_end).into_par_iter().take_any_while(running);
+            let remaining = self.max_results - results.len();
+            results.extend(search::search(candidates, remaining, matcher));
+            next = chunk_end;
+        }
+        results
+    }
+
+    /// Searches the aligned blocks `[n * block, (n + 1) * block)` overlapping
+    /// `start..end` that belong to the shard, all of them without one, each in full before
+    /// the next: in the order `seed` gives them, or in ascending order without a seed.
+    fn search_blocks<M>(
+        &self,
+        seed: Option<u64>,
+        block: u64,
+        end: u64,
+        timer: &SearchTimer,
+        matcher: &M,
+    ) -> Vec<HashResult>
+    where
+        M: Fn(u64) -> Option<HashResult> + Sync,
+    {
+        if end <= self.start {
+            return Vec::new();
+        }
+        let running = |_: &u64| !timer.should_stop();
+        let shard = self.shard.unwrap_or(Shard::new(0, 1));
+        let (first, len) = shard.blocks(self.start / block, (end - 1) / block);
+        let order: Box<dyn Iterator<Item = u64>> = match seed {
+            Some(seed) => Box::new(BlockOrder::new(len, seed)),
+            None => Box::new(0..len),
+        };
+        let mut results = Vec::new();
+        for index in order {
+            if results.len() >= self.max_results || timer.should_stop() {
+                break;
+            }
+            let n = first + index * shard.count;
+            let block_start = (n * block).max(self.start);
+            let block_end = (n + 1).saturating_mul(block).min(end);
+            debug!("Searching block {}..{}", block_start, block_end);
+            let candidates = (block_start..block_end).into_par_iter().take_any_while(running);
+            let remaining = self.max_results - results.len();
+            results.extend(search::search(candidates, remaining, matcher));
+        }
+        results
+    }
+
+    /// Returns the first match found, ignoring `max_results`.
+    ///
+    /// Returns `None` when the bounded range holds no match, or when the timeout expires or
+    /// the search is cancelled first.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the config is invalid apart from `max_results`; see
+    /// [`HashFinderConfig::build`].
+    #[instrument(skip_all, fields(zeros = self.zeros, start = self.start, algo = %self.algorithm))]
+    pub fn run_first(&self) -> Result<Option<HashResult>, HashFinderError> {
+        self.clone().max_results(1).validate()?;
+
+        let timer = SearchTimer::start(self.timeout, self.cancellation.clone());
+        self.install(|| self.search_first(&timer))
+    }
+
+    /// Finds a chain of `max_results` links, where each link's input is prefixed with the
+    /// hex hash of the link before it.
+    ///
+    /// Links are found one after another, each searching from `start` again, so the
+    /// result is ordered by height. The timeout covers the whole chain; if it expires or
+    /// the search is cancelled, the links found so far are returned.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the config is invalid; see [`HashFinderConfig::build`].
+    pub fn run_chained(&self) -> Result<Vec<HashResult>, HashFinderError> {
+        self.run_chained_with_outcome().map(|(chain, _)| chain)
+    }
+
+    /// Like [`HashFinderConfig::run_chained`], but also reports how the search ended, as
+    /// [`HashFinderConfig::run_with_outcome`] does.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the config is invalid; see [`HashFinderConfig::build`].
+    #[instrument(name = "run_chained", skip_all, fields(zeros = self.zeros, links = self.max_results, algo = %self.algorithm))]
+    pub fn run_chained_with_outcome(
+        &self,
+    ) -> Result<(Vec<HashResult>, SearchOutcome), HashFinderError> {
+        self.validate()?;
+
+        let timer = SearchTimer::start(self.timeout, self.cancellation.clone());
+        let chain = self.install(|| {
+            let mut chain: Vec<HashResult> = Vec::with_capacity(self.max_results);
+            while chain.len() < self.max_results {
+                match self.chain_link(chain.last()).search_first(&timer) {
+                    Some(link) => {
+                        debug!("Found link {} at {}", chain.len(), link.number);
+                        chain.push(link);
+                    }
+                    None => break,
+                }
+            }
+            chain
+        })?;
+
+        if chain.len() < self.max_results {
+            info!(
+                "Chain stopped early with {} of {} links",
+                chain.len(),
+                self.max_results
+            );
+        }
+        let outcome = self.outcome(chain.len(), &timer);
+        Ok((chain, outcome))
+    }
+
+    /// The config that searches for the link following `previous`, or the first link when
+    /// `previous` is `None`.
+    ///
+    /// The previous link's hex hash is appended to the salt, so a link's input is
+    /// `salt || previous_hash || nonce`.
+    pub fn chain_link(&self, previous: Option<&HashResult>) -> Self {
+        let mut link = self.clone();
+        if let Some(previous) = previous {
+            let salt = self.salt.as_deref().unwrap_or_default();
+            link.salt = Some([salt, previous.hash.as_bytes()].concat());
+        }
+        link
+    }
+
+    fn search_first(&self, timer: &SearchTimer) -> Option<HashResult> {
+        let running = |_: &u64| !timer.should_stop();
+        match self.search_end() {
+            Some(end) => search::search_first(
+                (self.start..end).into_par_iter().take_any_while(running),
+                self.matcher(),
+            ),
+            None => search::search_first(
+                (self.start..=u64::MAX).par_bridge().take_any_while(running),
+                self.matcher(),
+            ),
+        }
+    }
+
+    /// Like [`HashFinderConfig::run`], but also reports how long the search took and how
+    /// many candidates it hashed.
+    ///
+    /// Counting uses a [`SearchProgress`], the config's own if set, so the results carry
+    /// their `attempts`.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the config is invalid; see [`HashFinderConfig::build`].
+    pub fn run_with_stats(&self) -> Result<(Vec<HashResult>, SearchStats), HashFinderError> {
+        let progress = self.progress.clone().unwrap_or_default();
+        let before = progress.hashes();
+        let started = Instant::now();
+        let results = self.clone().progress(progress.clone()).run()?;
+        let stats = SearchStats::new(started.elapsed(), progress.hashes() - before, results.len());
+        Ok((results, stats))
+    }
+
+    /// Hashes per second this config achieves, measured by searching for `duration` with
+    /// a difficulty that practically never matches.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the config is invalid apart from `zeros` and `max_results`.
+    pub fn measure_hashes_per_sec(&self, duration: Duration) -> Result<f64, HashFinderError> {
+        let progress = SearchProgress::new();
+        let started = Instant::now();
+        self.without_criteria()
+            .zeros(self.algorithm.hex_len())
+            .max_results(usize::MAX)
+            .timeout(duration)
+            .progress(progress.clone())
+            .run()?;
+        Ok(progress.hashes() as f64 / started.elapsed().as_secs_f64())
+    }
+
+    /// This config with every match criterion but `zeros` and `also` cleared.
+    fn without_criteria(&self) -> Self {
+        let mut config = self.clone();
+        config.leading_zero_bits = None;
+        config.prefix = None;
+        config.suffix = None;
+        config.suffixes = None;
+        config.contains = None;
+        config.wildcard = None;
+        config.palindrome = None;
+        config.target = None;
+        config.min_zero_count = None;
+        config.self_suffix = false;
+        #[cfg(feature = "regex")]
+        {
+            config.regex = None;
+        }
+        config
+    }
+
+    /// Exclusive bound the search actually stops at: `end`, or else the first nonce too
+    /// wide for `pad_width`.
+    pub(crate) fn search_end(&self) -> Option<u64> {
+        self.end.or_else(|| {
+            let width = u32::try_from(self.pad_width?).ok()?;
+            10u64.checked_pow(width)
+        })
+    }
+
+    /// Whether `num` fits in `pad_width` digits; always true without a pad width.
+    pub fn fits_pad_width(&self, num: u64) -> bool {
+        match self.pad_width {
+            Some(width) => (num.checked_ilog10().unwrap_or(0) as usize) < width,
+            None => true,
+        }
+    }
+
+    /// Hex digits a match must have fixed: the prefix, suffix and `contains` lengths, the
+    /// wildcard pattern's fixed digits, half a palindrome's length, or else `zeros`.
+    ///
+    /// Leading zero bits are rounded up to whole hex digits; see
+    /// [`HashFinderConfig::expected_attempts`] for the exact difficulty. A regex has no
+    /// fixed difficulty and gives 0, and a target gives its own leading zero digits.
+    pub fn difficulty(&self) -> usize {
+        if self.has_regex() {
+            return 0;
+        }
+        if let Some(target) = &self.target {
+            return digest_leading_zero_bits(target) as usize / 4;
+        }
+        if let Some(wildcard) = &self.wildcard {
+            return wildcard.fixed_len();
+        }
+        if let Some(len) = self.palindrome {
+            return len / 2;
+        }
+        match self.leading_zero_bits {
+            Some(bits) => bits.div_ceil(4) as usize,
+            None => self.pattern_len().unwrap_or(self.zeros),
+        }
+    }
+
+    /// Expected hashes per match, `2^bits` for leading zero bits and `16^digits` otherwise,
+    /// saturating at `u64::MAX`. Alternative suffixes and each position a `contains` needle
+    /// could occupy add their own chance to match, and a target `t` takes `2^256 / t`.
+    ///
+    /// A minimum zero count divides by the binomial chance of that many zeros, exactly
+    /// when combined with zeros and assuming independence from any other criterion. A self
+    /// suffix multiplies by 16 per decimal digit of `start`, a lower bound since later
+    /// numbers are longer.
+    pub fn expected_attempts(&self) -> u64 {
+        let attempts = self.hash_attempts();
+        if !self.self_suffix {
+            return attempts;
+        }
+        let digits = self.start.checked_ilog10().unwrap_or(0) as usize + 1;
+        attempts.saturating_mul(crate::estimate_attempts(digits))
+    }
+
+    /// [`HashFinderConfig::expected_attempts`] ignoring `self_suffix`.
+    fn hash_attempts(&self) -> u64 {
+        let Some(min) = self.min_zero_count else {
+            return self.criteria_attempts();
+        };
+        let digits = self.algorithm.hex_len();
+        if self.criteria_count() > 0 {
+            return (self.criteria_attempts() as f64 / zero_count_probability(digits, min)) as u64;
+        }
+        // The zeros at the matched end count towards `min` as well.
+        let zeros = self.zeros.min(digits);
+        let probability = 16f64.powi(-(zeros as i32))
+            * zero_count_probability(digits - zeros, min.saturating_sub(zeros));
+        (1.0 / probability) as u64
+    }
+
+    /// [`HashFinderConfig::expected_attempts`] ignoring `min_zero_count`.
+    fn criteria_attempts(&self) -> u64 {
+        if let Some(target) = &self.target {
+            let value = target.iter().fold(0f64, |acc, &byte| acc * 256.0 + byte as f64);
+            return (2f64.powi(256) / value) as u64;
+        }
+        if let Some(bits) = self.leading_zero_bits {
+            return 1u64.checked_shl(bits).unwrap_or(u64::MAX);
+        }
+        if self.has_regex() || self.pattern_len().is_none() {
+            return crate::estimate_attempts(self.difficulty());
+        }
+        let fixed = [&self.prefix, &self.suffix]
+            .into_iter()
+            .flatten()
+            .map(HexPattern::len)
+            .sum::<usize>();
+        let mut probability = 16f64.powi(-(fixed as i32));
+        if let Some(suffixes) = &self.suffixes {
+            probability *= suffixes.match_probability();
+        }
+        if let Some(needle) = &self.contains {
+            let positions = (self.algorithm.hex_len() + 1).saturating_sub(needle.len());
+            probability *= positions as f64 / 16f64.powi(needle.len() as i32);
+        }
+        (1.0 / probability) as u64
+    }
+
+    /// Expected time to find one match at `hashes_per_sec`, like
+    /// [`estimate_duration`](crate::estimate_duration) but exact for leading zero bits.
+    pub fn expected_duration(&self, hashes_per_sec: f64) -> Duration {
+        crate::attempts_duration(self.expected_attempts(), hashes_per_sec)
+    }
+
+    /// Combined length of `prefix`, `suffix`, `contains` and the shortest of `suffixes`, or
+    /// `None` when matching zeros instead.
+    fn pattern_len(&self) -> Option<usize> {
+        self.combined_pattern_len(SuffixSet::min_len)
+    }
+
+    /// Like [`HashFinderConfig::pattern_len`], counting the longest of `suffixes`.
+    fn longest_pattern_len(&self) -> Option<usize> {
+        self.combined_pattern_len(SuffixSet::max_len)
+    }
+
+    fn combined_pattern_len(&self, suffixes_len: fn(&SuffixSet) -> usize) -> Option<usize> {
+        match (&self.prefix, &self.suffix, &self.suffixes, &self.contains) {
+            (None, None, None, None) => None,
+            (prefix, suffix, suffixes, contains) => Some(
+                prefix.as_ref().map_or(0, HexPattern::len)
+                    + suffix.as_ref().map_or(0, HexPattern::len)
+                    + suffixes.as_ref().map_or(0, suffixes_len)
+                    + contains.as_ref().map_or(0, HexPattern::len),
+            ),
+        }
+    }
+
+    /// Whether a regex replaces the other match criteria.
+    fn has_regex(&self) -> bool {
+        #[cfg(feature = "regex")]
+        let has_regex = self.regex.is_some();
+        #[cfg(not(feature = "regex"))]
+        let has_regex = false;
+        has_regex
+    }
+
+    /// How many of leading zero bits, a prefix or suffix, a wildcard pattern, a palindrome, a
+    /// regex and a target are set; at most one may be.
+    fn criteria_count(&self) -> usize {
+        [
+            self.leading_zero_bits.is_some(),
+            self.pattern_len().is_some(),
+            self.wildcard.is_some(),
+            self.palindrome.is_some(),
+            self.has_regex(),
+            self.target.is_some(),
+        ]
+        .into_iter()
+        .filter(|&set| set)
+        .count()
+    }
+
+    /// Runs `op` on a dedicated pool when `threads` is set, otherwise on the global pool.
+    ///
+    /// Builds the pool on every call, so a search that installs repeatedly should build it
+    /// once with [`HashFinderConfig::thread_pool`] and use [`install_on`] instead.
+    fn install<R, OP>(&self, op: OP) -> Result<R, HashFinderError>
+    where
+        R: Send,
+        OP: FnOnce() -> R + Send,
+    {
+        Ok(install_on(self.thread_pool()?.as_ref(), op))
+    }
+
+    /// The dedicated pool for `threads`, or `None` when searches use the global pool.
+    pub(crate) fn thread_pool(&self) -> Result<Option<rayon::ThreadPool>, HashFinderError> {
+        self.threads
+            .map(|threads| rayon::ThreadPoolBuilder::new().num_threads(threads).build())
+            .transpose()
+            .map_err(HashFinderError::ThreadPool)
+    }
+
+    /// Hashes `num` exactly as the search does, honouring the algorithm, salt, template,
+    /// nonce encoding, HMAC key and iteration count.
+    pub fn hash(&self, num: u64) -> String {
+        self.hash_digest(num).to_hex()
+    }
+
+    /// Raw form of [`HashFinderConfig::hash`], computed without allocating.
+    pub fn hash_digest(&self, num: u64) -> DigestBytes {
+        self.digest_as(num, None)
+    }
+
+    /// The hex digests of `num` under each of the `also` constraints' algorithms, in order.
+    ///
+    /// The input is built as for [`HashFinderConfig::hash`], but the HMAC key only applies
+    /// to the main algorithm.
+    pub fn also_hashes(&self, num: u64) -> Vec<String> {
+        self.also
+            .iter()
+            .map(|c| self.digest_as(num, Some(c.algorithm)).to_hex())
+            .collect()
+    }
+
+    /// Whether `digest`, the hash of `num`, ends with the decimal digits of `num`; always
+    /// true unless `self_suffix` is set.
+    pub fn matches_self_suffix(&self, num: u64, digest: &[u8]) -> bool {
+        !self.self_suffix || digest_ends_with_number(digest, num)
+    }
+
+    /// Whether `num` satisfies every `also` constraint; true when there are none.
+    pub fn matches_also(&self, num: u64) -> bool {
+        let mut buf = [0; MAX_ENCODED_LEN];
+        let width = self.pad_width.unwrap_or(0);
+        self.input_matches_also(self.nonce_encoding.encode_padded_into(num, width, &mut buf))
+    }
+
+    fn input_matches_also(&self, nonce: &[u8]) -> bool {
+        self.also.iter().all(|c| {
+            let digest = self.digest_input_as(nonce, Some(c.algorithm));
+            self.digest_has_zeros(&digest, c.zeros)
+        })
+    }
+
+    /// Hashes `word` in place of the encoded nonce, honouring the algorithm, salt,
+    /// template, HMAC key and iteration count, so `hash_word("4163")` equals `hash(4163)`
+    /// under the default decimal encoding.
+    pub fn hash_word(&self, word: &str) -> String {
+        self.digest_input_as(word.as_bytes(), None).to_hex()
+    }
+
+    /// Digest of `num` under `algorithm`, or under the configured algorithm and HMAC key
+    /// when `None`.
+    fn digest_as(&self, num: u64, algorithm: Option<HashAlgorithm>) -> DigestBytes {
+        let mut buf = [0; MAX_ENCODED_LEN];
+        let width = self.pad_width.unwrap_or(0);
+        let nonce = self.nonce_encoding.encode_padded_into(num, width, &mut buf);
+        self.digest_input_as(nonce, algorithm)
+    }
+
+    /// Like [`HashFinderConfig::digest_as`], for already encoded nonce bytes.
+    fn digest_input_as(&self, nonce: &[u8], algorithm: Option<HashAlgorithm>) -> DigestBytes {
+        let salt = self.salt.as_deref().unwrap_or_default();
+        let (prefix, suffix) = match &self.template {
+            Some(template) => (template.prefix(), template.suffix()),
+            None => (&[][..], &[][..]),
+        };
+        let parts = [salt, prefix, nonce, suffix];
+        match (algorithm, &self.hmac_key) {
+            (Some(algorithm), _) => algorithm.digest_iterated(&parts, self.iterations),
+            (None, Some(key)) => {
+                let mut mac = key.mac(&parts);
+                for _ in 1..self.iterations {
+                    mac = key.mac(&[&mac]);
+                }
+                DigestBytes::new(&mac)
+            }
+            (None, None) => self.algorithm.digest_iterated(&parts, self.iterations),
+        }
+    }
+
+    /// Whether `hash` satisfies this config's difficulty.
+    ///
+    /// `self_suffix` depends on the number as well, so it is checked separately by
+    /// [`HashFinderConfig::matches_self_suffix`].
+    pub fn matches(&self, hash: &str) -> bool {
+        if self.min_zero_count.is_some_and(|min| hash_zero_count(hash) < min) {
+            return false;
+        }
+        #[cfg(feature = "regex")]
+        if let Some(regex) = &self.regex {
+            return regex.is_match(hash);
+        }
+        if self.leading_zero_bits.is_some() || self.target.is_some() {
+            return hex_decode(hash).is_ok_and(|digest| self.matches_digest(&digest));
+        }
+        if let Some(wildcard) = &self.wildcard {
+            return match self.direction {
+                MatchDirection::Trailing => wildcard.is_suffix_of(hash),
+                MatchDirection::Leading => wildcard.is_prefix_of(hash),
+            };
+        }
+        if let Some(len) = self.palindrome {
+            return hash_ends_with_palindrome(hash, len);
+        }
+        if self.pattern_len().is_some() {
+            return self.prefix.as_ref().is_none_or(|p| p.is_prefix_of(hash))
+                && self.suffix.as_ref().is_none_or(|s| s.is_suffix_of(hash))
+                && self.suffixes.as_ref().is_none_or(|s| s.matching(hash).is_some())
+                && self.contains.as_ref().is_none_or(|c| c.is_infix_of(hash));
+        }
+        if self.zeros == 0 && (self.min_zero_count.is_some() || self.self_suffix) {
+            return true;
+        }
+        match self.direction {
+            MatchDirection::Trailing => {
+                hash_matches_difficulty(hash, self.zeros) == Ok(true)
+            }
+            MatchDirection::Leading => hash_starts_with_zeros(hash, self.zeros),
+        }
+    }
+
+    /// Like [`HashFinderConfig::matches`], but checks the digest bytes directly.
+    pub fn matches_digest(&self, digest: &[u8]) -> bool {
+        if self.min_zero_count.is_some_and(|min| digest_zero_count(digest) < min) {
+            return false;
+        }
+        #[cfg(feature = "regex")]
+        if let Some(regex) = &self.regex {
+            return regex.is_match(&crate::hex_encode(digest));
+        }
+        if let Some(target) = &self.target {
+            return <&[u8; 32]>::try_from(digest).is_ok_and(|d| digest_below_target(d, target));
+        }
+        if let Some(bits) = self.leading_zero_bits {
+            return digest_leading_zero_bits(digest) >= bits;
+        }
+        if let Some(wildcard) = &self.wildcard {
+            return match self.direction {
+                MatchDirection::Trailing => wildcard.is_suffix_of_digest(digest),
+                MatchDirection::Leading => wildcard.is_prefix_of_digest(digest),
+            };
+        }
+        if let Some(len) = self.palindrome {
+            return digest_ends_with_palindrome(digest, len);
+        }
+        if self.pattern_len().is_some() {
+            return self.prefix.as_ref().is_none_or(|p| p.is_prefix_of_digest(digest))
+                && self.suffix.as_ref().is_none_or(|s| s.is_suffix_of_digest(digest))
+                && self
+                    .suffixes
+                    .as_ref()
+                    .is_none_or(|s| s.matching_digest(digest).is_some())
+                && self.contains.as_ref().is_none_or(|c| c.is_infix_of_digest(digest));
+        }
+        if self.zeros == 0 && (self.min_zero_count.is_some() || self.self_suffix) {
+            return true;
+        }
+        self.digest_has_zeros(digest, self.zeros)
+    }
+
+    fn digest_has_zeros(&self, digest: &[u8], zeros: usize) -> bool {
+        match self.direction {
+            MatchDirection::Trailing => digest_ends_with_zeros(digest, zeros),
+            MatchDirection::Leading => digest_starts_with_zeros(digest, zeros),
+        }
+    }
+
+    pub(crate) fn matcher(&self) -> impl Fn(u64) -> Option<HashResult> + Sync + '_ {
+        self.matcher_where(|num, digest| {
+            self.matches_digest(digest)
+                && self.matches_self_suffix(num, digest)
+                && self.matches_also(num)
+        })
+    }
+
+    /// Like [`HashFinderConfig::matcher`], accepting the numbers `matched` accepts given
+    /// their digest instead of those meeting the config's criteria.
+    fn matcher_where<'a, M>(&'a self, matched: M) -> impl Fn(u64) -> Option<HashResult> + Sync + 'a
+    where
+        M: Fn(u64, &DigestBytes) -> bool + Sync + 'a,
+    {
+        move |num| {
+            // Only matches pay for hex encoding, unless `matched` needs it.
+            let digest = self.hash_digest(num);
+            let matched = matched(num, &digest);
+            let attempts = self.progress.as_ref().map(|progress| {
+                let attempts = progress.record_hash();
+                if matched {
+                    progress.record_match();
+                }
+                attempts
+            });
+            matched.then(|| HashResult {
+                number: num,
+                hash: digest.to_hex(),
+                suffix: self
+                    .suffixes
+                    .as_ref()
+                    .and_then(|s| s.matching_digest(&digest))
+                    .map(str::to_string),
+                attempts,
+                trailing_zeros: None,
+                shard: None,
+            })
+        }
+    }
+}
+
+/// Runs `op` on `pool`, or on the global pool when there is none.
+fn install_on<R, OP>(pool: Option<&rayon::ThreadPool>, op: OP) -> R
+where
+    R: Send,
+    OP: FnOnce() -> R + Send,
+{
+    match pool {
+        Some(pool) => pool.install(op),
+        None => op(),
+    }
+}
+
+/// Serializes optional bytes as lowercase hex.
+#[cfg(feature = "serde")]
+fn serialize_hex<T, S>(bytes: &Option<T>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    T: AsRef<[u8]>,
+    S: serde::Serializer,
+{
+    match bytes {
+        Some(bytes) => serializer.serialize_some(&crate::hex_encode(bytes.as_ref())),
+        None => serializer.serialize_none(),
+    }
+}
+
+/// A hash's place in [`HashFinderConfig::run_top_k_zeros`]: more trailing zeros rank
+/// higher, then lower numbers.
+type TopKRank = (usize, Reverse<u64>);
+
+/// Adds `rank` to the min-heap `top`, keeping only the best `k`.
+fn push_top_k(top: &mut BinaryHeap<Reverse<TopKRank>>, k: usize, rank: TopKRank) {
+    if top.len() < k {
+        top.push(Reverse(rank));
+    } else if let Some(mut worst) = top.peek_mut()
+        && worst.0 < rank
+    {
+        *worst = Reverse(rank);
+    }
+}
+
+/// Chance that at least `min` of `digits` uniformly random hex digits are `0`.
+fn zero_count_probability(digits: usize, min: usize) -> f64 {
+    let p = 1.0 / 16.0f64;
+    // Chance of exactly k zeros, stepped from k to k + 1 by the ratio of binomial terms.
+    let mut exactly = (1.0 - p).powi(digits as i32);
+    let mut tail = 0.0;
+    for k in 0..=digits {
+        if k >= min {
+            tail += exactly;
+        }
+        exactly *= (digits - k) as f64 / (k + 1) as f64 * p / (1.0 - p);
+    }
+    tail
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build_rejects_invalid_values() {
+        assert_eq!(
+            HashFinderConfig::new().max_results(1).build(),
+            Err(ConfigError::ZeroDifficulty)
+        );
+        assert_eq!(
+            HashFinderConfig::new().zeros(1).build(),
+            Err(ConfigError::ZeroResults)
+        );
+        assert_eq!(
+            HashFinderConfig::new()
+                .zeros(65)
+                .max_results(1)
+                .build(),
+            Err(ConfigError::DifficultyTooHigh {
+                zeros: 65,
+                algorithm: HashAlgorithm::Sha256
+            })
+        );
+        assert!(HashFinderConfig::new()
+            .zeros(65)
+            .max_results(1)
+            .algorithm(HashAlgorithm::Sha512)
+            .build()
+            .is_ok());
+        assert!(HashFinderConfig::new()
+            .zeros(41)
+            .max_results(1)
+            .algorithm(HashAlgorithm::Sha1)
+            .build()
+            .is_err());
+    }
+
+    #[test]
+    fn test_leading_direction() {
+        let results = HashFinderConfig::new()
+            .zeros(2)
+            .max_results(2)
+            .direction(MatchDirection::Leading)
+            .run().unwrap();
+        assert_eq!(results.len(), 2);
+        for result in &results {
+            assert!(result.hash.starts_with("00"));
+        }
+    }
+
+    #[test]
+    fn test_start_from_skips_earlier_numbers() {
+        let results = HashFinderConfig::new()
+            .zeros(2)
+            .max_results(3)
+            .start_from(1_000_000)
+            .run().unwrap();
+        assert_eq!(results.len(), 3);
+        assert!(results.iter().all(|r| r.number >= 1_000_000));
+    }
+
+    #[test]
+    fn test_search_ends_at_u64_max() {
+        // The only 1-zero matches in the last 201 numbers, u64::MAX itself not among them.
+        let expected: Vec<u64> =
+            [157, 153, 143, 131, 79, 64, 61, 45, 38].iter().map(|n| u64::MAX - n).collect();
+        let config = HashFinderConfig::new().zeros(1).max_results(100).start_from(u64::MAX - 200);
+        let mut numbers: Vec<u64> = config.run().unwrap().into_iter().map(|r| r.number).collect();
+        numbers.sort_unstable();
+        assert_eq!(numbers, expected);
+        assert_eq!(config.clone().start_from(u64::MAX - 37).run_first().unwrap(), None);
+        assert_eq!(config.clone().start_from(u64::MAX).run().unwrap(), []);
+
+        // The last number is searched too.
+        let last = config.zeros(0).min_zero_count(1).start_from(u64::MAX).run().unwrap();
+        assert_eq!(last.iter().map(|r| r.number).collect::<Vec<_>>(), [u64::MAX]);
+    }
+
+    #[test]
+    fn test_bounded_range() {
+        let config = HashFinderConfig::new()
+            .zeros(3)
+            .max_results(usize::MAX)
+            .start_from(1)
+            .end(20_000);
+        let mut numbers: Vec<u64> = config.run().unwrap().into_iter().map(|r| r.number).collect();
+        numbers.sort_unstable();
+        assert_eq!(numbers, [4163, 11848, 12843, 13467]);
+        assert_eq!(
+            config.clone().end(1).build(),
+            Err(ConfigError::EmptyRange { start: 1, end: 1 })
+        );
+        assert_eq!(config.start_from(4164).end(11848).run_first().unwrap(), None);
+    }
+
+    #[test]
+    fn test_count_matches() {
+        let config = HashFinderConfig::new().zeros(3).start_from(1).end(20_000);
+        assert_eq!(config.count_matches().unwrap(), 4);
+        assert_eq!(config.clone().start_from(4164).end(11848).count_matches().unwrap(), 0);
+        assert_eq!(config.zeros(2).threads(2).count_matches().unwrap(), 72);
+    }
+
+    #[test]
+    fn test_run_top_k_zeros() {
+        let config = HashFinderConfig::new().start_from(1).end(20_000);
+        let top: Vec<(u64, Option<usize>)> = config
+            .run_top_k_zeros(6)
+            .unwrap()
+            .into_iter()
+            .map(|r| (r.number, r.trailing_zeros))
+            .collect();
+        assert_eq!(
+            top,
+            [
+                (4163, Some(3)),
+                (11848, Some(3)),
+                (12843, Some(3)),
+                (13467, Some(3)),
+                (403, Some(2)),
+                (932, Some(2))
+            ]
+        );
+        assert_eq!(config.clone().threads(3).run_top_k_zeros(6).unwrap().len(), 6);
+        assert!(config.run_top_k_zeros(0).unwrap().is_empty());
+        assert!(matches!(
+            HashFinderConfig::new().run_top_k_zeros(1),
+            Err(HashFinderError::UnboundedRange)
+        ));
+    }
+
+    #[test]
+    fn test_nonce_encoding_changes_results() {
+        let config = HashFinderConfig::new().zeros(2).max_results(2);
+        assert_eq!(
+            config.hash(4163),
+            "95d4362bd3cd4315d0bbe38dfa5d7fb8f0aed5f1a31d98d510907279194e3000"
+        );
+        let hex = config.clone().nonce_encoding(NonceEncoding::HexString);
+        assert_eq!(
+            hex.hash(4163),
+            "c0aa4a0be7ba28399b09a68835a21755f442e25f8e0971b1d1ea3a6c749f0385"
+        );
+        let le = config.clone().nonce_encoding(NonceEncoding::LittleEndianBytes);
+        assert_eq!(
+            le.hash(4163),
+            "111beb7b9bc83e15acb451db76ae279cdb41c53a56d114fdf450d5eba5acf4ae"
+        );
+        let be = config.nonce_encoding(NonceEncoding::BigEndianBytes);
+        assert_eq!(
+            be.hash(4163),
+            "174405773bdfa08f50372aa3ff5719f5ed8845aaad7ae51f87943141ef279df2"
+        );
+
+        for result in le.run().unwrap() {
+            assert_eq!(result.hash, le.hash(result.number));
+            assert!(le.matches(&result.hash));
+        }
+    }
+
+    #[test]
+    fn test_salt_changes_hashes() {
+        let config = HashFinderConfig::new().zeros(3).max_results(2);
+        let salted = config.clone().salt("hello");
+        assert_ne!(config.hash(4163), salted.hash(4163));
+        assert_eq!(
+            salted.hash(4163),
+            "900c77b56b4b37995fd0ae10c54d0529418d976e5e6393008a30be67c7ba4c40"
+        );
+
+        let results = salted.run().unwrap();
+        assert_eq!(results.len(), 2);
+        for result in &results {
+            assert!(crate::verify_result_with_config(&salted, result));
+            assert!(!crate::verify_result_with_config(&config, result));
+        }
+    }
+
+    #[test]
+    fn test_threads() {
+        assert_eq!(
+            HashFinderConfig::new().zeros(1).max_results(1).threads(0).build(),
+            Err(ConfigError::ZeroThreads)
+        );
+
+        let config = HashFinderConfig::new()
+            .zeros(3)
+            .max_results(usize::MAX)
+            .end(20_000)
+            .threads(2);
+        let mut numbers: Vec<u64> = config.run().unwrap().into_iter().map(|r| r.number).collect();
+        numbers.sort_unstable();
+        assert_eq!(numbers, [4163, 11848, 12843, 13467]);
+        assert_eq!(config.clone().threads(1).run_first().unwrap().map(|r| r.number), Some(4163));
+        assert!(matches!(
+            config.threads(0).run_first(),
+            Err(HashFinderError::Config(ConfigError::ZeroThreads))
+        ));
+    }
+
+    #[cfg(feature = "checkpoint")]
+    #[test]
+    fn test_run_resumable_continues_from_checkpoint() {
+        let config = HashFinderConfig::new().zeros(3).max_results(3).end(20_000);
+        let mut checkpoint = SearchCheckpoint {
+            last_checked: 5000,
+            found: vec![HashResult::new(4163, config.hash(4163))],
+            params: None,
+        };
+
+        let results = config.run_resumable(&mut checkpoint, None).unwrap();
+        let numbers: Vec<u64> = results.iter().map(|r| r.number).collect();
+        assert_eq!(numbers, [4163, 11848, 12843]);
+        assert_eq!(checkpoint.found, results);
+
+        let mut cut_short = SearchCheckpoint::new();
+        let first = config.clone().max_results(1).run_resumable(&mut cut_short, None).unwrap();
+        assert_eq!(cut_short.last_checked, 0);
+        let resumed = config.clone().max_results(4).run_resumable(&mut cut_short, None).unwrap();
+        assert_eq!(resumed[0], first[0]);
+        let mut numbers: Vec<u64> = resumed.iter().map(|r| r.number).collect();
+        numbers.sort_unstable();
+        assert_eq!(numbers, [4163, 11848, 12843, 13467]);
+
+        let mut fresh = SearchCheckpoint::new();
+        let all = config.clone().max_results(10).run_resumable(&mut fresh, None).unwrap();
+        assert_eq!(all.len(), 4);
+        assert_eq!(fresh.last_checked, 19_999);
+
+        // 200 chunks on one dedicated pool.
+        let threaded = config.max_results(10).threads(2).chunk_size(100);
+        let mut fresh = SearchCheckpoint::new();
+        assert_eq!(threaded.run_resumable(&mut fresh, None).unwrap(), all);
+    }
+
+    #[cfg(feature = "checkpoint")]
+    #[test]
+    fn test_run_resumable_after_cancel_skips_nothing() {
+        let expected: Vec<u64> =
+            crate::find_hashes_in_range(1, 20_000, 2).unwrap().iter().map(|r| r.number).collect();
+        let config =
+            HashFinderConfig::new().zeros(2).max_results(usize::MAX).end(20_000).chunk_size(1000);
+        let path = std::env::temp_dir()
+            .join(format!("hash-finder-{}-cancelled.checkpoint", std::process::id()));
+
+        // Cancel part way through; wherever the search stops, the frontier is a chunk
+        // boundary and resuming finds exactly the matches of an uninterrupted scan.
+        let token = CancellationToken::new();
+        let progress = SearchProgress::new();
+        let watcher = {
+            let (token, progress) = (token.clone(), progress.clone());
+            std::thread::spawn(move || {
+                while progress.hashes() < 5000 {
+                    std::thread::yield_now();
+                }
+                token.cancel();
+            })
+        };
+        let mut checkpoint = SearchCheckpoint::new();
+        config
+            .clone()
+            .cancellation(token)
+            .progress(progress)
+            .run_resumable(&mut checkpoint, Some(&path))
+            .unwrap();
+        watcher.join().unwrap();
+        assert!(checkpoint.last_checked.is_multiple_of(1000), "{}", checkpoint.last_checked);
+        assert!(checkpoint.last_checked < 19_999 || checkpoint.found.len() == expected.len());
+
+        let mut saved = SearchCheckpoint::load(&path).unwrap();
+        assert_eq!(saved, checkpoint);
+        let resumed = config.run_resumable(&mut saved, Some(&path)).unwrap();
+        let mut numbers: Vec<u64> = resumed.iter().map(|r| r.number).collect();
+        numbers.sort_unstable();
+        assert_eq!(numbers, expected);
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[cfg(feature = "checkpoint")]
+    #[test]
+    fn test_run_resumable_rejects_other_params() {
+        let mut checkpoint = SearchCheckpoint::new();
+        let config = HashFinderConfig::new().zeros(3).max_results(1);
+        config.run_resumable(&mut checkpoint, None).unwrap();
+
+        let err = HashFinderConfig::new()
+            .zeros(4)
+            .max_results(1)
+            .run_resumable(&mut checkpoint, None)
+            .unwrap_err();
+        let HashFinderError::IoError(err) = err else {
+            panic!("expected an I/O error, got {:?}", err);
+        };
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput);
+        assert_eq!(checkpoint.found.len(), 1);
+    }
+
+    #[test]
+    fn test_hmac_search() {
+        let key = HmacKey::new(*b"Jefe");
+        let config = HashFinderConfig::new().zeros(2).max_results(3).hmac_key(key.clone());
+        assert_ne!(config.hash(4163), HashFinderConfig::new().hash(4163));
+
+        let results = config.run().unwrap();
+        assert_eq!(results.len(), 3);
+        for result in &results {
+            assert_eq!(result.hash, key.mac_hex(&[result.number.to_string().as_bytes()]));
+            assert!(crate::verify_result_with_config(&config, result));
+        }
+
+        assert_eq!(
+            config.clone().hmac_key(HmacKey::new([])).build(),
+            Err(ConfigError::EmptyHmacKey)
+        );
+        assert_eq!(
+            config.algorithm(HashAlgorithm::Sha512).build(),
+            Err(ConfigError::HmacRequiresSha256 {
+                algorithm: HashAlgorithm::Sha512
+            })
+        );
+    }
+
+    #[test]
+    fn test_iterations() {
+        assert_eq!(
+            HashFinderConfig::new().zeros(1).max_results(1).iterations(0).build(),
+            Err(ConfigError::ZeroIterations)
+        );
+
+        let single = HashFinderConfig::new().iterations(1);
+        assert_eq!(single.hash(4163), crate::compute_hash(4163));
+
+        // sha256(sha256(sha256("4163"))), hashing raw digest bytes between rounds.
+        let config = HashFinderConfig::new().zeros(2).max_results(3).iterations(3);
+        assert_eq!(
+            config.hash(4163),
+            "a9d62d1ae9e276079d714ab8d95abed8dd95761ad31d434cb077052680607a74"
+        );
+        for result in config.run().unwrap() {
+            assert_eq!(result.hash, config.hash(result.number));
+            assert!(result.hash.ends_with("00"));
+        }
+
+        let sha256d = HashFinderConfig::new().algorithm(HashAlgorithm::Sha256d);
+        assert_eq!(
+            sha256d.clone().iterations(2).hash(4163),
+            HashFinderConfig::new().iterations(4).hash(4163)
+        );
+    }
+
+    #[test]
+    fn test_run_with_stats() {
+        let config = HashFinderConfig::new().zeros(3).max_results(2).end(5000);
+        let (results, stats) = config.run_with_stats().unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].number, 4163);
+        assert_eq!(stats.candidates_checked, 4999);
+        assert_eq!(stats.results_found, 1);
+        assert!(stats.hash_rate > 0.0);
+
+        let progress = SearchProgress::new();
+        let (_, stats) = config.progress(progress.clone()).run_with_stats().unwrap();
+        let (_, again) = HashFinderConfig::new()
+            .zeros(3)
+            .max_results(2)
+            .end(5000)
+            .progress(progress.clone())
+            .run_with_stats().unwrap();
+        assert_eq!(stats.candidates_checked, 4999);
+        assert_eq!(again.candidates_checked, 4999);
+        assert_eq!(progress.hashes(), 9998);
+    }
+
+    #[test]
+    fn test_suffix() {
+        let suffix: HexPattern = "ab".parse().unwrap();
+        let config = HashFinderConfig::new().max_results(3).suffix(suffix.clone());
+        assert_eq!(config.difficulty(), 2);
+        let results = config.run().unwrap();
+        assert_eq!(results.len(), 3);
+        for result in &results {
+            assert_eq!(result.hash, crate::compute_hash(result.number));
+            assert!(result.hash.ends_with("ab"));
+            assert!(crate::verify_result_with_config(&config, result));
+        }
+        assert_eq!(config.clone().zeros(64).build().map(|c| c.difficulty()), Ok(2));
+
+        let long: HexPattern = "0".repeat(65).parse().unwrap();
+        assert_eq!(
+            config.clone().suffix(long).build(),
+            Err(ConfigError::PatternTooLong {
+                digits: 65,
+                algorithm: HashAlgorithm::Sha256
+            })
+        );
+        assert!(config.max_results(1).run_first().unwrap().unwrap().hash.ends_with("ab"));
+    }
+
+    #[test]
+    fn test_suffixes() {
+        let suffixes: SuffixSet = ["000", "aaa", "fff"]
+            .iter()
+            .map(|s| s.parse::<HexPattern>().unwrap())
+            .collect();
+        let config = HashFinderConfig::new()
+            .max_results(6)
+            .suffixes(suffixes.clone());
+        assert_eq!(config.difficulty(), 3);
+        assert_eq!(config.expected_attempts(), 1365);
+        let mut results = config.run().unwrap();
+        assert_eq!(results.len(), 6);
+        for result in &results {
+            let suffix = result.suffix.as_deref().unwrap();
+            assert!(result.hash.ends_with(suffix));
+            assert_eq!(suffixes.matching(&result.hash), Some(suffix));
+            assert!(crate::verify_result_with_config(&config, result));
+        }
+        // A forged attribution no longer verifies.
+        results[0].suffix = Some("abc".to_string());
+        assert!(!crate::verify_result_with_config(&config, &results[0]));
+
+        // sha256("4163") ends with 000, sha256("4164") with none of them.
+        assert!(config.matches(&config.hash(4163)));
+        assert!(!config.matches(&config.hash(4164)));
+
+        let single = HashFinderConfig::new().max_results(6).suffix("000".parse().unwrap());
+        assert!(single.run().unwrap().iter().all(|r| r.suffix.is_none()));
+
+        assert_eq!(
+            config.clone().suffixes(SuffixSet::default()).build(),
+            Err(ConfigError::EmptySuffixSet)
+        );
+        let long: HexPattern = "0".repeat(65).parse().unwrap();
+        let mut with_long = suffixes;
+        with_long.insert(long);
+        assert_eq!(
+            config.clone().suffixes(with_long).build(),
+            Err(ConfigError::PatternTooLong {
+                digits: 65,
+                algorithm: HashAlgorithm::Sha256
+            })
+        );
+        assert_eq!(
+            config.leading_zero_bits(4).build(),
+            Err(ConfigError::BitsWithPattern)
+        );
+    }
+
+    #[test]
+    fn test_contains() {
+        let needle: HexPattern = "abc".parse().unwrap();
+        let config = HashFinderConfig::new().max_results(3).contains(needle.clone());
+        assert_eq!(config.difficulty(), 3);
+        assert_eq!(config.expected_attempts(), 66);
+        for result in config.run().unwrap() {
+            assert!(result.hash.contains("abc"));
+            assert!(crate::verify_result_with_config(&config, &result));
+        }
+
+        // All patterns must hold together.
+        let both = config.clone().prefix("0".parse().unwrap());
+        for result in both.run().unwrap() {
+            assert!(result.hash.starts_with('0') && result.hash.contains("abc"));
+        }
+
+        let long: HexPattern = "a".repeat(65).parse().unwrap();
+        assert_eq!(
+            config.clone().contains(long).build(),
+            Err(ConfigError::PatternTooLong {
+                digits: 65,
+                algorithm: HashAlgorithm::Sha256
+            })
+        );
+        assert_eq!(
+            config.leading_zero_bits(4).build(),
+            Err(ConfigError::BitsWithPattern)
+        );
+    }
+
+    #[test]
+    fn test_target() {
+        let mut target = [0xff; 32];
+        target[0] = 0x00;
+        let config = HashFinderConfig::new().target(target).max_results(3);
+        assert_eq!(config.clone().build().map(|c| c.difficulty()), Ok(2));
+        for result in config.run().unwrap() {
+            assert!(result.hash.starts_with("00"));
+            assert!(crate::verify_result_with_config(&config, &result));
+        }
+        // sha256("286") = 0032..., sha256("1") = 6b86...
+        assert!(config.matches(&config.hash(286)));
+        assert!(!config.matches(&config.hash(1)));
+
+        assert_eq!(
+            config.clone().target([0; 32]).build(),
+            Err(ConfigError::ZeroTarget)
+        );
+        assert_eq!(
+            config.clone().algorithm(HashAlgorithm::Sha512).build(),
+            Err(ConfigError::TargetRequires256BitDigest {
+                algorithm: HashAlgorithm::Sha512
+            })
+        );
+        #[cfg(feature = "sha3")]
+        assert!(config.clone().algorithm(HashAlgorithm::Sha3_256).build().is_ok());
+        assert_eq!(
+            config.clone().leading_zero_bits(8).build(),
+            Err(ConfigError::TargetWithOtherCriteria)
+        );
+        assert_eq!(
+            config.prefix("00".parse().unwrap()).build(),
+            Err(ConfigError::TargetWithOtherCriteria)
+        );
+    }
+
+    #[test]
+    fn test_leading_zero_bits() {
+        let config = HashFinderConfig::new().leading_zero_bits(9).max_results(3);
+        assert_eq!(config.difficulty(), 3);
+        assert_eq!(config.expected_attempts(), 512);
+        for result in config.run().unwrap() {
+            assert!(config.matches(&result.hash));
+            assert!(crate::verify_result_with_config(&config, &result));
+        }
+        // sha256("671") starts with exactly 8 zero bits.
+        assert!(!config.matches(&config.hash(671)));
+        assert!(config.clone().leading_zero_bits(8).matches(&config.hash(671)));
+
+        assert_eq!(
+            config.clone().leading_zero_bits(0).build(),
+            Err(ConfigError::ZeroDifficulty)
+        );
+        assert_eq!(
+            config.clone().leading_zero_bits(257).build(),
+            Err(ConfigError::BitsTooHigh {
+                bits: 257,
+                algorithm: HashAlgorithm::Sha256
+            })
+        );
+        assert!(config.clone().leading_zero_bits(256).build().is_ok());
+        assert_eq!(
+            config.suffix("0".parse().unwrap()).build(),
+            Err(ConfigError::BitsWithPattern)
+        );
+        assert_eq!(HashFinderConfig::new().zeros(3).expected_attempts(), 4096);
+        assert_eq!(
+            HashFinderConfig::new().leading_zero_bits(10).expected_duration(256.0),
+            Duration::from_secs(4)
+        );
+    }
+
+    #[test]
+    fn test_wildcard() {
+        let wildcard: WildcardPattern = "0?0".parse().unwrap();
+        let config = HashFinderConfig::new()
+            .max_results(usize::MAX)
+            .end(20_000)
+            .wildcard(wildcard.clone());
+        assert_eq!(config.difficulty(), 2);
+        assert_eq!(config.expected_attempts(), 256);
+        let numbers = |config: &HashFinderConfig| -> Vec<u64> {
+            config.run_sorted().unwrap().iter().map(|r| r.number).collect()
+        };
+        let found = numbers(&config);
+        assert!(!found.is_empty());
+        for &num in &found {
+            let hash = config.hash(num);
+            assert!(hash.ends_with('0') && hash[61..62] == *"0", "{}", hash);
+            assert!(config.matches_digest(&config.hash_digest(num)));
+        }
+
+        #[cfg(feature = "regex")]
+        for (pattern, direction, regex) in [
+            ("0?0", MatchDirection::Trailing, "0[0-9a-f]0$"),
+            ("a??b", MatchDirection::Trailing, "a[0-9a-f]{2}b$"),
+            ("?f?", MatchDirection::Leading, "^[0-9a-f]f[0-9a-f]"),
+        ] {
+            let wildcard = config
+                .clone()
+                .wildcard(pattern.parse().unwrap())
+                .direction(direction);
+            let regex = HashFinderConfig {
+                wildcard: None,
+                ..wildcard.clone()
+            }
+            .regex(HashRegex::new(regex).unwrap());
+            assert_eq!(numbers(&wildcard), numbers(&regex), "{}", pattern);
+        }
+
+        // Without wildcards it is a plain suffix.
+        let plain = config.clone().wildcard("ab".parse().unwrap());
+        let suffix = HashFinderConfig {
+            wildcard: None,
+            ..plain.clone()
+        }
+        .suffix("ab".parse().unwrap());
+        assert_eq!(numbers(&plain), numbers(&suffix));
+        for num in 1..5000 {
+            let hash = plain.hash(num);
+            assert_eq!(plain.matches(&hash), hash.ends_with("ab"));
+        }
+
+        assert_eq!(
+            config.clone().suffix("0".parse().unwrap()).build(),
+            Err(ConfigError::WildcardWithOtherCriteria)
+        );
+        let long = "?".repeat(64) + "0";
+        assert!(matches!(
+            config.clone().wildcard(long.parse().unwrap()).build(),
+            Err(ConfigError::PatternTooLong { digits: 65, .. })
+        ));
+        assert!(config.zeros(0).build().is_ok());
+    }
+
+    #[test]
+    fn test_min_zero_count() {
+        let config = HashFinderConfig::new().max_results(3).min_zero_count(12);
+        assert!(config.clone().build().is_ok());
+        let numbers: Vec<u64> = config.run_sorted().unwrap().iter().map(|r| r.number).collect();
+        assert_eq!(numbers, [4051, 6016, 6804]);
+        for num in 1..5000 {
+            let hash = config.hash(num);
+            assert_eq!(config.matches(&hash), hash_zero_count(&hash) >= 12, "{}", hash);
+            assert_eq!(config.matches_digest(&config.hash_digest(num)), config.matches(&hash));
+        }
+        assert!((1800..1850).contains(&config.expected_attempts()));
+
+        // Both must hold with -N.
+        let both = config.clone().zeros(1).min_zero_count(10);
+        let numbers: Vec<u64> = both.run_sorted().unwrap().iter().map(|r| r.number).collect();
+        assert_eq!(numbers, [762, 793, 1806]);
+        for num in numbers {
+            let hash = both.hash(num);
+            assert!(hash.ends_with('0') && hash_zero_count(&hash) >= 10, "{}", hash);
+        }
+        assert!(!both.matches(&format!("{}1", "0".repeat(63))));
+        assert!(!both.matches(&format!("{}0", "1".repeat(63))));
+        let attempts = 16.0 / zero_count_probability(63, 9);
+        assert_eq!(both.expected_attempts(), attempts as u64);
+
+        assert_eq!(
+            config.clone().min_zero_count(0).build(),
+            Err(ConfigError::ZeroDifficulty)
+        );
+        assert_eq!(
+            config.clone().min_zero_count(65).build(),
+            Err(ConfigError::ZeroCountTooHigh {
+                count: 65,
+                algorithm: HashAlgorithm::Sha256
+            })
+        );
+        assert!(config.min_zero_count(65).run_first().is_err());
+        assert!((zero_count_probability(64, 0) - 1.0).abs() < 1e-12);
+        assert!((zero_count_probability(64, 20) - 1.1006e-9).abs() < 1e-12);
+        assert_eq!(zero_count_probability(4, 5), 0.0);
+    }
+
+    #[test]
+    fn test_self_suffix() {
+        // Unsalted SHA-256 has no solution below 2 * 10^7; this salt has two below 10^4.
+        let config = HashFinderConfig::new()
+            .self_suffix(true)
+            .salt("self")
+            .max_results(3)
+            .end(10_000);
+        assert!(config.clone().build().is_ok());
+        let results = config.run_sorted().unwrap();
+        let numbers: Vec<u64> = results.iter().map(|r| r.number).collect();
+        assert_eq!(numbers, [2, 4020]);
+        assert!(results[1].hash.ends_with("4020"));
+        for num in 1..10_000 {
+            let digest = config.hash_digest(num);
+            let expected = config.hash(num).ends_with(&num.to_string());
+            assert_eq!(config.matches_self_suffix(num, &digest), expected, "{}", num);
+        }
+        assert_eq!(config.expected_attempts(), 16);
+        assert_eq!(config.clone().start_from(1000).expected_attempts(), 65536);
+
+        // On top of other criteria, both must hold.
+        let both = config.clone().suffix("20".parse().unwrap());
+        let numbers: Vec<u64> = both.run_sorted().unwrap().iter().map(|r| r.number).collect();
+        assert_eq!(numbers, [4020]);
+        assert_eq!(both.expected_attempts(), 256 * 16);
+
+        let off = config.self_suffix(false);
+        assert!(off.matches_self_suffix(3, &off.hash_digest(3)));
+        assert_eq!(off.build(), Err(ConfigError::ZeroDifficulty));
+    }
+
+    #[test]
+    fn test_palindrome() {
+        let config = HashFinderConfig::new().max_results(5).palindrome(4);
+        assert!(config.clone().build().is_ok());
+        let numbers: Vec<u64> = config.run_sorted().unwrap().iter().map(|r| r.number).collect();
+        assert_eq!(numbers, [527, 584, 763, 790, 1076]);
+        for num in 1..2000 {
+            let hash = config.hash(num);
+            let tail: Vec<u8> = hash.bytes().rev().take(4).collect();
+            assert_eq!(config.matches(&hash), tail.iter().eq(tail.iter().rev()), "{}", hash);
+            assert_eq!(config.matches_digest(&config.hash_digest(num)), config.matches(&hash));
+        }
+        assert_eq!(config.difficulty(), 2);
+        assert_eq!(config.expected_attempts(), 256);
+
+        // A zero count still applies on top.
+        let both = config.clone().max_results(3).min_zero_count(8);
+        let numbers: Vec<u64> = both.run_sorted().unwrap().iter().map(|r| r.number).collect();
+        assert_eq!(numbers, [1110, 3667, 9320]);
+
+        let whole = HashFinderConfig::new().max_results(1).palindrome(64);
+        assert!(whole.clone().build().is_ok());
+        let palindrome = format!("{}{}", "0123456789abcdef".repeat(2), "fedcba9876543210".repeat(2));
+        assert!(whole.matches(&palindrome));
+        assert_eq!(whole.expected_attempts(), u64::MAX);
+
+        assert_eq!(config.clone().palindrome(0).build(), Err(ConfigError::ZeroDifficulty));
+        assert_eq!(
+            config.clone().palindrome(65).build(),
+            Err(ConfigError::PatternTooLong {
+                digits: 65,
+                algorithm: HashAlgorithm::Sha256
+            })
+        );
+        assert!(config.clone().palindrome(65).run_first().is_err());
+        assert_eq!(
+            config.clone().suffix("0".parse().unwrap()).build(),
+            Err(ConfigError::PalindromeWithOtherCriteria)
+        );
+        assert_eq!(
+            config.leading_zero_bits(4).build(),
+            Err(ConfigError::PalindromeWithOtherCriteria)
+        );
+    }
+
+    #[test]
+    fn test_prefix() {
+        let prefix: HexPattern = "00".parse().unwrap();
+        let config = HashFinderConfig::new().max_results(3).prefix(prefix.clone());
+        let results = config.run().unwrap();
+        assert_eq!(results.len(), 3);
+        for result in &results {
+            assert_eq!(result.hash, crate::compute_hash(result.number));
+            assert!(result.hash.starts_with("00"));
+            assert!(crate::verify_result_with_config(&config, result));
+        }
+
+        // Leading zeros and a `00` prefix are the same condition.
+        let leading = HashFinderConfig::new().zeros(2).direction(MatchDirection::Leading);
+        for num in 1..2000 {
+            assert_eq!(config.matches(&config.hash(num)), leading.matches(&leading.hash(num)));
+        }
+
+        let both = config.clone().suffix("0".parse().unwrap());
+        assert_eq!(both.difficulty(), 3);
+        for result in both.run().unwrap() {
+            assert!(result.hash.starts_with("00") && result.hash.ends_with('0'));
+        }
+
+        let long: HexPattern = "0".repeat(63).parse().unwrap();
+        assert_eq!(
+            both.suffix(long).build(),
+            Err(ConfigError::PatternTooLong {
+                digits: 65,
+                algorithm: HashAlgorithm::Sha256
+            })
+        );
+    }
+
+    #[test]
+    fn test_chunk_size() {
+        let config = HashFinderConfig::new().zeros(3).max_results(4).chunk_size(1000);
+        let mut numbers: Vec<u64> = config.run().unwrap().iter().map(|r| r.number).collect();
+        numbers.sort_unstable();
+        assert_eq!(numbers, [4163, 11848, 12843, 13467]);
+
+        let bounded = config.clone().start_from(4000).end(12_000).max_results(10);
+        let mut numbers: Vec<u64> = bounded.run().unwrap().iter().map(|r| r.number).collect();
+        numbers.sort_unstable();
+        assert_eq!(numbers, [4163, 11848]);
+
+        assert_eq!(config.clone().chunk_size(0).build(), Err(ConfigError::ZeroChunkSize));
+
+        let token = CancellationToken::new();
+        token.cancel();
+        assert!(config.cancellation(token).run().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_run_with_outcome() {
+        let started = Instant::now();
+        let (results, outcome) = HashFinderConfig::new()
+            .zeros(40)
+            .max_results(1)
+            .timeout(Duration::from_secs(1))
+            .run_with_outcome().unwrap();
+        assert!(results.is_empty());
+        assert_eq!(outcome, SearchOutcome::TimedOut);
+        assert!(started.elapsed() < Duration::from_secs(10));
+
+        let hopeless = HashFinderConfig::new()
+            .zeros(40)
+            .max_results(1)
+            .timeout(Duration::from_millis(200));
+        assert_eq!(hopeless.run_sorted_with_outcome().unwrap().1, SearchOutcome::TimedOut);
+        assert_eq!(hopeless.run_chained_with_outcome().unwrap().1, SearchOutcome::TimedOut);
+
+        let config = HashFinderConfig::new().zeros(3).max_results(10).end(20_000);
+        let generous = config.clone().timeout(Duration::from_secs(600));
+        let (results, outcome) = generous.run_with_outcome().unwrap();
+        assert_eq!((results.len(), outcome), (4, SearchOutcome::Completed));
+        let (results, outcome) = config.clone().max_results(2).run_with_outcome().unwrap();
+        assert_eq!((results.len(), outcome), (2, SearchOutcome::Completed));
+
+        let token = CancellationToken::new();
+        token.cancel();
+        let (results, outcome) = config.cancellation(token).run_with_outcome().unwrap();
+        assert_eq!((results.len(), outcome), (0, SearchOutcome::Cancelled));
+    }
+
+    #[test]
+    fn test_run_where() {
+        let config = HashFinderConfig::new().max_results(10).end(20_000);
+        let mut results = config.run_where(|_, hash| hash.ends_with("000")).unwrap();
+        results.sort_by_key(|r| r.number);
+        let mut expected = config.clone().zeros(3).run().unwrap();
+        expected.sort_by_key(|r| r.number);
+        assert_eq!(results, expected);
+
+        let salted = config.clone().salt(b"pepper".to_vec()).max_results(1);
+        let results = salted.run_where(|_, _| true).unwrap();
+        assert_eq!(results[0].hash, salted.hash_digest(results[0].number).to_hex());
+
+        assert!(matches!(
+            config.max_results(0).run_where(|_, _| true),
+            Err(HashFinderError::InvalidMaxResults)
+        ));
+    }
+
+    #[test]
+    fn test_filter_numbers() {
+        let config = HashFinderConfig::new().zeros(3).max_results(1);
+        let numbers = [13467, 1, 4163, 4163, 11848];
+        let numbers_of = |found: Vec<HashResult>| -> Vec<u64> {
+            found.iter().map(|r| r.number).collect()
+        };
+        let found = numbers_of(config.filter_numbers(&numbers).unwrap());
+        assert_eq!(found, [13467, 4163, 4163, 11848]);
+        assert!(config.filter_numbers(&[]).unwrap().is_empty());
+
+        let all: Vec<u64> = (1..20_000).rev().collect();
+        let found = numbers_of(config.filter_numbers(&all).unwrap());
+        assert_eq!(found, [13467, 12843, 11848, 4163]);
+    }
+
+    #[test]
+    fn test_filter_words() {
+        let words = ["apple", "4163", "", "word1690", "banana", "word1690"];
+        let config = HashFinderConfig::new().zeros(3).max_results(1);
+        let found: Vec<String> =
+            config.filter_words(&words).unwrap().into_iter().map(|r| r.word).collect();
+        assert_eq!(found, ["4163", "word1690", "word1690"]);
+        assert_eq!(config.hash_word("4163"), config.hash(4163));
+
+        let found = config.clone().zeros(4).filter_words(&words).unwrap();
+        assert_eq!(found.len(), 2);
+        assert_eq!(
+            found[0],
+            WordResult::new(
+                "word1690".to_string(),
+                "b19ae1a13f766130f6e57fe746c5dea21c0c950ff456c8ade5137807d2db0000".to_string()
+            )
+        );
+
+        // The salt and template wrap each word as they do a nonce.
+        let template: InputTemplate = "salt:{n}".parse().unwrap();
+        let salted = config.clone().zeros(2).salt(b"salt:".to_vec());
+        let templated = config.zeros(2).template(template);
+        let words: Vec<String> = (0..200).map(|i| format!("word{}", i)).collect();
+        assert_eq!(salted.filter_words(&words).unwrap(), templated.filter_words(&words).unwrap());
+        assert_eq!(salted.filter_words(&words).unwrap()[0].word, "word199");
+        assert_eq!(salted.hash_word("word199"), crate::compute_hash_str("salt:word199"));
+    }
+
+    #[test]
+    fn test_concurrent_searches_keep_their_results() {
+        let configs: Vec<HashFinderConfig> = ["a:", "b:", "c:", "d:"]
+            .iter()
+            .map(|salt| HashFinderConfig::new().zeros(2).max_results(50).salt(*salt))
+            .collect();
+        std::thread::scope(|scope| {
+            let searches: Vec<_> =
+                configs.iter().map(|config| scope.spawn(|| config.run().unwrap())).collect();
+            for (config, search) in configs.iter().zip(searches) {
+                let results = search.join().unwrap();
+                assert_eq!(results.len(), 50);
+                for result in results {
+                    assert_eq!(result.hash, config.hash(result.number));
+                    assert!(result.hash.ends_with("00"));
+                }
+            }
+        });
+    }
+
+    #[test]
+    fn test_shard() {
+        let config =
+            HashFinderConfig::new().zeros(2).max_results(usize::MAX).end(50_000).chunk_size(1000);
+        let mut whole: Vec<u64> = config.run().unwrap().into_iter().map(|r| r.number).collect();
+        whole.sort_unstable();
+
+        let mut union = Vec::new();
+        for index in 0..3 {
+            let shard = Shard::new(index, 3);
+            for result in config.clone().shard(index, 3).run().unwrap() {
+                assert_eq!(result.shard, Some(shard));
+                assert!(shard.owns_block(result.number / 1000));
+                union.push(result.number);
+            }
+        }
+        union.sort_unstable();
+        assert_eq!(union, whole);
+
+        // Shards still cover everything when the range starts mid-block, shuffled or not.
+        let offset = config.clone().start_from(1500);
+        let mut union: Vec<u64> = (0..4)
+            .flat_map(|index| offset.clone().shard(index, 4).randomize(index).run().unwrap())
+            .map(|r| r.number)
+            .collect();
+        union.sort_unstable();
+        assert_eq!(union, whole.iter().copied().filter(|&n| n >= 1500).collect::<Vec<_>>());
+
+        // The default blocks put all of 1..20000 in shard 0.
+        let small = HashFinderConfig::new().zeros(3).max_results(10).end(20_000);
+        assert_eq!(small.clone().shard(0, 2).run().unwrap().len(), 4);
+        assert_eq!(small.clone().shard(1, 2).run().unwrap(), []);
+        assert_eq!(
+            small.clone().shard(2, 2).build(),
+            Err(ConfigError::InvalidShard { index: 2, count: 2 })
+        );
+        assert_eq!(
+            small.shard(0, 0).build(),
+            Err(ConfigError::InvalidShard { index: 0, count: 0 })
+        );
+    }
+
+    #[test]
+    fn test_randomize() {
+        let config = HashFinderConfig::new().zeros(3).end(20_000).chunk_size(1000).randomize(7);
+        let mut numbers: Vec<u64> =
+            config.clone().max_results(10).run().unwrap().iter().map(|r| r.number).collect();
+        numbers.sort_unstable();
+        assert_eq!(numbers, [4163, 11848, 12843, 13467]);
+
+        // Each match sits in its own block, so the first block visited with one decides
+        // the result.
+        let first = |seed| config.clone().randomize(seed).max_results(1).run().unwrap()[0].number;
+        assert_eq!(first(7), first(7));
+        let firsts: std::collections::HashSet<u64> = (0..16).map(first).collect();
+        assert!(firsts.len() > 1, "{:?}", firsts);
+
+        let clipped = config.clone().start_from(4500).end(12_500).max_results(10);
+        let results = clipped.run().unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].number, 11848);
+        assert!(hash_matches_difficulty(&results[0].hash, 3).unwrap());
+
+        let unbounded = HashFinderConfig::new().zeros(2).max_results(3).randomize(1);
+        for result in unbounded.run().unwrap() {
+            assert_eq!(result.hash, unbounded.hash(result.number));
+            assert!(hash_matches_difficulty(&result.hash, 2).unwrap());
+        }
+    }
+
+    #[test]
+    fn test_pad_width() {
+        let config = HashFinderConfig::new().zeros(1).max_results(10).pad_width(2);
+        assert_eq!(config.hash(4), HashAlgorithm::Sha256.hash_hex(b"04"));
+        let mut numbers: Vec<u64> = config.run().unwrap().iter().map(|r| r.number).collect();
+        numbers.sort_unstable();
+        assert_eq!(numbers, [4, 23, 38, 76]);
+        assert_eq!(config.run_sorted().unwrap().len(), 4);
+        let first = config.clone().max_results(1).run_first().unwrap();
+        assert_eq!(first.map(|r| r.number < 100), Some(true));
+
+        assert!(config.fits_pad_width(99));
+        assert!(!config.fits_pad_width(100));
+        assert!(config.clone().end(100).build().is_ok());
+        assert_eq!(
+            config.clone().end(101).build(),
+            Err(ConfigError::NonceExceedsPadWidth { num: 100, width: 2 })
+        );
+        assert_eq!(
+            config.clone().start_from(100).build(),
+            Err(ConfigError::NonceExceedsPadWidth { num: 100, width: 2 })
+        );
+        assert_eq!(
+            config.clone().pad_width(21).build(),
+            Err(ConfigError::PadWidthOutOfRange { width: 21 })
+        );
+        assert_eq!(
+            config.nonce_encoding(NonceEncoding::HexString).build(),
+            Err(ConfigError::PadWidthRequiresDecimal {
+                encoding: NonceEncoding::HexString
+            })
+        );
+    }
+
+    #[test]
+    fn test_chained_with_salt() {
+        let config = HashFinderConfig::new().zeros(2).max_results(3).salt("genesis");
+        let chain = config.run_chained().unwrap();
+        assert_eq!(chain.len(), 3);
+        assert_eq!(chain[0].hash, config.hash(chain[0].number));
+        for pair in chain.windows(2) {
+            let input = format!("genesis{}{}", pair[0].hash, pair[1].number);
+            assert_eq!(pair[1].hash, HashAlgorithm::Sha256.hash_hex(input.as_bytes()));
+        }
+
+        let token = CancellationToken::new();
+        token.cancel();
+        assert!(config.cancellation(token).run_chained().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_also_constraints() {
+        let config = HashFinderConfig::new()
+            .zeros(2)
+            .max_results(3)
+            .also(AlgorithmConstraint::new(HashAlgorithm::Sha512, 1))
+            .also(AlgorithmConstraint::new(HashAlgorithm::Crc32, 1));
+        let results = config.run().unwrap();
+        assert_eq!(results.len(), 3);
+        for result in &results {
+            assert!(result.hash.ends_with("00"));
+            let input = result.number.to_string();
+            let also = config.also_hashes(result.number);
+            assert_eq!(also[0], HashAlgorithm::Sha512.hash_hex(input.as_bytes()));
+            assert!(also.iter().all(|hash| hash.ends_with('0')));
+            assert!(crate::verify_result_with_config(&config, result));
+        }
+
+        assert_eq!(
+            config.clone().also(AlgorithmConstraint::new(HashAlgorithm::Crc32, 9)).build(),
+            Err(ConfigError::DifficultyTooHigh {
+                zeros: 9,
+                algorithm: HashAlgorithm::Crc32
+            })
+        );
+        assert_eq!(
+            config.also(AlgorithmConstraint::new(HashAlgorithm::Sha512, 0)).build(),
+            Err(ConfigError::ZeroDifficulty)
+        );
+    }
+
+    #[test]
+    fn test_run_sorted() {
+        let config = HashFinderConfig::new().zeros(3).max_results(4);
+        let numbers: Vec<u64> = config.run_sorted().unwrap().iter().map(|r| r.number).collect();
+        assert_eq!(numbers, [4163, 11848, 12843, 13467]);
+
+        let bounded = config.start_from(12000).end(13000).max_results(10);
+        let numbers: Vec<u64> = bounded.run_sorted().unwrap().iter().map(|r| r.number).collect();
+        assert_eq!(numbers, [12843]);
+    }
+
+    #[test]
+    fn test_run_exact() {
+        let config = HashFinderConfig::new().zeros(2).end(20_000);
+        for max_results in [1, 7, 40] {
+            let results = config.clone().max_results(max_results).run_exact().unwrap();
+            assert_eq!(results.len(), max_results);
+        }
+        for threads in [1, 3] {
+            let config = config.clone().max_results(25).threads(threads);
+            assert_eq!(config.run_exact().unwrap().len(), 25);
+            assert_eq!(config.chunk_size(1000).run_exact().unwrap().len(), 25);
+        }
+
+        let bounded = config.clone().zeros(3).max_results(5);
+        assert!(matches!(
+            bounded.run_exact(),
+            Err(HashFinderError::RangeExhausted { found: 4, max_results: 5 })
+        ));
+
+        let token = CancellationToken::new();
+        token.cancel();
+        assert!(matches!(
+            config.clone().max_results(5).cancellation(token).run_exact(),
+            Err(HashFinderError::SearchCancelled)
+        ));
+        assert!(matches!(
+            config.max_results(0).run_exact(),
+            Err(HashFinderError::InvalidMaxResults)
+        ));
+    }
+
+    #[test]
+    fn test_measure_hashes_per_sec() {
+        let config = HashFinderConfig::new().algorithm(HashAlgorithm::XxHash64);
+        let rate = config.measure_hashes_per_sec(Duration::from_millis(100)).unwrap();
+        assert!(rate > 1000.0, "{}", rate);
+    }
+
+    #[test]
+    fn test_config_is_reusable() {
+        let config = HashFinderConfig::new().zeros(2).max_results(2).build().unwrap();
+        assert_eq!(config.run().unwrap().len(), 2);
+        assert_eq!(config.run().unwrap().len(), 2);
+    }
+}
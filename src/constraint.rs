@@ -0,0 +1,65 @@
+use crate::HashAlgorithm;
+use std::fmt;
+use std::str::FromStr;
+
+/// An extra `algorithm:zeros` condition a match must also satisfy, e.g. `sha512:2`.
+///
+/// The nonce is hashed with the same input as the main algorithm, so a match ends in
+/// zeros under both digests.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AlgorithmConstraint {
+    pub algorithm: HashAlgorithm,
+    pub zeros: usize,
+}
+
+impl AlgorithmConstraint {
+    pub fn new(algorithm: HashAlgorithm, zeros: usize) -> Self {
+        AlgorithmConstraint { algorithm, zeros }
+    }
+}
+
+/// Formats as `algorithm:zeros`, the syntax accepted by `FromStr`.
+impl fmt::Display for AlgorithmConstraint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.algorithm, self.zeros)
+    }
+}
+
+/// Serialized as its display form, e.g. `sha512:2`.
+#[cfg(feature = "serde")]
+impl serde::Serialize for AlgorithmConstraint {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl FromStr for AlgorithmConstraint {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (algorithm, zeros) = s
+            .split_once(':')
+            .ok_or_else(|| format!("expected `algorithm:zeros`, got '{}'", s))?;
+        let algorithm = algorithm.parse::<HashAlgorithm>()?;
+        let zeros = zeros
+            .parse::<usize>()
+            .map_err(|e| format!("invalid zeros '{}': {}", zeros, e))?;
+        Ok(AlgorithmConstraint::new(algorithm, zeros))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_constraint() {
+        let constraint: AlgorithmConstraint = "sha512:2".parse().unwrap();
+        assert_eq!(constraint, AlgorithmConstraint::new(HashAlgorithm::Sha512, 2));
+        assert_eq!(constraint.to_string(), "sha512:2");
+
+        assert!("sha512".parse::<AlgorithmConstraint>().is_err());
+        assert!("sha512:x".parse::<AlgorithmConstraint>().is_err());
+        assert!("nope:2".parse::<AlgorithmConstraint>().is_err());
+    }
+}
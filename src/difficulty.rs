@@ -0,0 +1,28 @@
+use core::fmt;
+
+/// Why [`hash_matches_difficulty`](crate::hash_matches_difficulty) could not give an answer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DifficultyError {
+    /// Zero zeros, which every hash would trivially have.
+    ZeroDifficulty,
+    /// More zeros than the hash has digits, which no hash could have.
+    TooManyZeros { zeros: usize, len: usize },
+    /// The hash has a non-hex `character` at byte `index`.
+    InvalidHex { index: usize, character: char },
+}
+
+impl fmt::Display for DifficultyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DifficultyError::ZeroDifficulty => write!(f, "difficulty must be at least 1 zero"),
+            DifficultyError::TooManyZeros { zeros, len } => {
+                write!(f, "{} zeros do not fit in a {}-digit hash", zeros, len)
+            }
+            DifficultyError::InvalidHex { index, character } => {
+                write!(f, "invalid hex digit {:?} at position {}", character, index)
+            }
+        }
+    }
+}
+
+impl core::error::Error for DifficultyError {}
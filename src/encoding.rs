@@ -0,0 +1,172 @@
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt;
+use core::str::FromStr;
+
+/// How a nonce is turned into bytes before hashing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum NonceEncoding {
+    /// ASCII decimal, e.g. `4163` -> `"4163"`.
+    #[default]
+    DecimalString,
+    /// ASCII lowercase hex without padding, e.g. `4163` -> `"1043"`.
+    HexString,
+    /// The 8-byte little-endian representation.
+    LittleEndianBytes,
+    /// The 8-byte big-endian representation.
+    BigEndianBytes,
+}
+
+/// The name [`compute_hash_with_encoding`](crate::compute_hash_with_encoding) uses for
+/// [`NonceEncoding`]; the two are interchangeable.
+pub type InputEncoding = NonceEncoding;
+
+/// Longest encoded nonce: `u64::MAX` in decimal.
+pub(crate) const MAX_ENCODED_LEN: usize = 20;
+
+impl NonceEncoding {
+    pub const ALL: &'static [NonceEncoding] = &[
+        NonceEncoding::DecimalString,
+        NonceEncoding::HexString,
+        NonceEncoding::LittleEndianBytes,
+        NonceEncoding::BigEndianBytes,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            NonceEncoding::DecimalString => "decimal",
+            NonceEncoding::HexString => "hex",
+            NonceEncoding::LittleEndianBytes => "le-bytes",
+            NonceEncoding::BigEndianBytes => "be-bytes",
+        }
+    }
+
+    pub fn encode(self, num: u64) -> Vec<u8> {
+        self.encode_into(num, &mut [0; MAX_ENCODED_LEN]).to_vec()
+    }
+
+    /// Encodes `num` into `buf` without allocating, returning the used tail or head.
+    pub(crate) fn encode_into(self, num: u64, buf: &mut [u8; MAX_ENCODED_LEN]) -> &[u8] {
+        match self {
+            NonceEncoding::DecimalString => write_digits(num, 10, buf),
+            NonceEncoding::HexString => write_digits(num, 16, buf),
+            NonceEncoding::LittleEndianBytes => {
+                buf[..8].copy_from_slice(&num.to_le_bytes());
+                &buf[..8]
+            }
+            NonceEncoding::BigEndianBytes => {
+                buf[..8].copy_from_slice(&num.to_be_bytes());
+                &buf[..8]
+            }
+        }
+    }
+
+    /// Like [`NonceEncoding::encode_into`], but left-pads decimal output with `'0'` to at
+    /// least `width` digits. Other encodings are returned unpadded.
+    #[cfg(feature = "std")]
+    pub(crate) fn encode_padded_into(
+        self,
+        num: u64,
+        width: usize,
+        buf: &mut [u8; MAX_ENCODED_LEN],
+    ) -> &[u8] {
+        let len = self.encode_into(num, buf).len();
+        match self {
+            NonceEncoding::DecimalString => {
+                let start = MAX_ENCODED_LEN - len.max(width.min(MAX_ENCODED_LEN));
+                buf[start..MAX_ENCODED_LEN - len].fill(b'0');
+                &buf[start..]
+            }
+            _ => self.encode_into(num, buf),
+        }
+    }
+}
+
+/// Writes `num` in `radix` (lowercase digits) at the end of `buf`.
+fn write_digits(mut num: u64, radix: u64, buf: &mut [u8; MAX_ENCODED_LEN]) -> &[u8] {
+    const DIGITS: &[u8; 16] = b"0123456789abcdef";
+    let mut i = buf.len();
+    loop {
+        i -= 1;
+        buf[i] = DIGITS[(num % radix) as usize];
+        num /= radix;
+        if num == 0 {
+            break;
+        }
+    }
+    &buf[i..]
+}
+
+impl fmt::Display for NonceEncoding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// Serialized as its display form, e.g. `decimal`.
+#[cfg(feature = "serde")]
+impl serde::Serialize for NonceEncoding {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl FromStr for NonceEncoding {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        NonceEncoding::ALL
+            .iter()
+            .copied()
+            .find(|encoding| encoding.name().eq_ignore_ascii_case(s))
+            .ok_or_else(|| {
+                let names: Vec<&str> = NonceEncoding::ALL.iter().map(|e| e.name()).collect();
+                format!("unknown nonce encoding '{}' (available: {})", s, names.join(", "))
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::string::ToString;
+
+    #[test]
+    fn test_encode() {
+        assert_eq!(NonceEncoding::DecimalString.encode(4163), b"4163");
+        assert_eq!(NonceEncoding::HexString.encode(4163), b"1043");
+        assert_eq!(
+            NonceEncoding::LittleEndianBytes.encode(4163),
+            [0x43, 0x10, 0, 0, 0, 0, 0, 0]
+        );
+        assert_eq!(
+            NonceEncoding::BigEndianBytes.encode(4163),
+            [0, 0, 0, 0, 0, 0, 0x10, 0x43]
+        );
+
+        for num in [0, 1, 9, 10, 4163, u64::MAX] {
+            assert_eq!(NonceEncoding::DecimalString.encode(num), num.to_string().as_bytes());
+            assert_eq!(NonceEncoding::HexString.encode(num), format!("{:x}", num).as_bytes());
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_encode_padded() {
+        let mut buf = [0; MAX_ENCODED_LEN];
+        let encoding = NonceEncoding::DecimalString;
+        assert_eq!(encoding.encode_padded_into(123, 7, &mut buf), b"0000123");
+        assert_eq!(encoding.encode_padded_into(123, 3, &mut buf), b"123");
+        assert_eq!(encoding.encode_padded_into(123, 0, &mut buf), b"123");
+        assert_eq!(encoding.encode_padded_into(1234, 3, &mut buf), b"1234");
+        assert_eq!(
+            encoding.encode_padded_into(0, MAX_ENCODED_LEN, &mut buf),
+            b"00000000000000000000"
+        );
+        assert_eq!(
+            NonceEncoding::BigEndianBytes.encode_padded_into(1, 12, &mut buf),
+            [0, 0, 0, 0, 0, 0, 0, 1]
+        );
+    }
+}
@@ -0,0 +1,123 @@
+use crate::ConfigError;
+use std::fmt;
+use std::io;
+
+/// Why a search function could not return results.
+#[derive(Debug)]
+pub enum HashFinderError {
+    /// `zeros` is 0 or longer than the hex digest of `max` digits.
+    InvalidZeroCount { zeros: usize, max: usize },
+    InvalidMaxResults,
+    InvalidRange { start: u64, end: u64 },
+    /// The search was cancelled before it could finish.
+    SearchCancelled,
+    /// The bounded range held only `found` of the `max_results` matches asked for.
+    RangeExhausted { found: usize, max_results: usize },
+    /// The search must scan a whole range, but neither `end` nor `pad_width` bounds it.
+    UnboundedRange,
+    /// A hex pattern or regex did not parse.
+    InvalidPattern(String),
+    /// A progress callback was asked for every 0 candidates.
+    InvalidProgressInterval,
+    /// A suffix collision was asked for 0 or more than `max` shared digits.
+    InvalidCollisionDigits { digits: usize, max: usize },
+    IoError(io::Error),
+    /// The dedicated pool for `threads` could not be built.
+    ThreadPool(rayon::ThreadPoolBuildError),
+    /// Any other setting rejected by [`HashFinderConfig::build`](crate::HashFinderConfig::build).
+    Config(ConfigError),
+}
+
+impl fmt::Display for HashFinderError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HashFinderError::InvalidZeroCount { zeros, max } => {
+                write!(f, "zeros must be between 1 and {} (got {})", max, zeros)
+            }
+            HashFinderError::InvalidMaxResults => write!(f, "max_results must be greater than 0"),
+            HashFinderError::InvalidRange { start, end } => {
+                write!(f, "search range {}..{} is empty", start, end)
+            }
+            HashFinderError::SearchCancelled => write!(f, "search was cancelled"),
+            HashFinderError::RangeExhausted { found, max_results } => write!(
+                f,
+                "search range exhausted with {} of {} results",
+                found, max_results
+            ),
+            HashFinderError::UnboundedRange => {
+                write!(f, "search range needs an end bound, from end or pad_width")
+            }
+            HashFinderError::InvalidPattern(e) => f.write_str(e),
+            HashFinderError::InvalidProgressInterval => {
+                write!(f, "progress interval must be greater than 0")
+            }
+            HashFinderError::InvalidCollisionDigits { digits, max } => {
+                write!(f, "k must be between 1 and {} (got {})", max, digits)
+            }
+            HashFinderError::IoError(e) => write!(f, "I/O error: {}", e),
+            HashFinderError::ThreadPool(e) => write!(f, "failed to build thread pool: {}", e),
+            HashFinderError::Config(e) => e.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for HashFinderError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            HashFinderError::IoError(e) => Some(e),
+            HashFinderError::ThreadPool(e) => Some(e),
+            HashFinderError::Config(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+/// Errors naming a zero count, results or range map to the matching variant; the rest,
+/// including a zero difficulty that may come from bits rather than zeros, are kept whole.
+impl From<ConfigError> for HashFinderError {
+    fn from(e: ConfigError) -> Self {
+        match e {
+            ConfigError::DifficultyTooHigh { zeros, algorithm } => {
+                HashFinderError::InvalidZeroCount {
+                    zeros,
+                    max: algorithm.hex_len(),
+                }
+            }
+            ConfigError::ZeroResults => HashFinderError::InvalidMaxResults,
+            ConfigError::EmptyRange { start, end } => HashFinderError::InvalidRange { start, end },
+            e => HashFinderError::Config(e),
+        }
+    }
+}
+
+impl From<io::Error> for HashFinderError {
+    fn from(e: io::Error) -> Self {
+        HashFinderError::IoError(e)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::HashAlgorithm;
+
+    #[test]
+    fn test_from_config_error() {
+        let e = HashFinderError::from(ConfigError::DifficultyTooHigh {
+            zeros: 41,
+            algorithm: HashAlgorithm::Sha1,
+        });
+        assert!(matches!(e, HashFinderError::InvalidZeroCount { zeros: 41, max: 40 }));
+        assert_eq!(e.to_string(), "zeros must be between 1 and 40 (got 41)");
+
+        let e = HashFinderError::from(ConfigError::EmptyRange { start: 5, end: 5 });
+        assert!(matches!(e, HashFinderError::InvalidRange { start: 5, end: 5 }));
+
+        let e = HashFinderError::from(ConfigError::ZeroResults);
+        assert!(matches!(e, HashFinderError::InvalidMaxResults));
+
+        let e = HashFinderError::from(ConfigError::ZeroThreads);
+        assert_eq!(e.to_string(), "threads must be greater than 0");
+        assert!(std::error::Error::source(&e).is_some());
+    }
+}
@@ -0,0 +1,78 @@
+use crate::{hash_matches_difficulty, hash_starts_with_zeros};
+
+#[cfg(feature = "regex")]
+use crate::HashRegex;
+
+/// A condition on the lowercase hex hash, for searches with
+/// [`find_hashes_with_pattern`](crate::find_hashes_with_pattern).
+///
+/// ```
+/// use rust_hash_finder::HashPattern;
+///
+/// assert!(HashPattern::TrailingZeros(3).matches("abc000"));
+/// assert!(HashPattern::Contains("c0ffee".to_string()).matches("12c0ffee34"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HashPattern {
+    TrailingZeros(usize),
+    LeadingZeros(usize),
+    Contains(String),
+    StartsWith(String),
+    EndsWith(String),
+    /// Compiled once, when the [`HashRegex`] is created, rather than on every match.
+    #[cfg(feature = "regex")]
+    Regex(HashRegex),
+}
+
+impl HashPattern {
+    pub fn matches(&self, hash: &str) -> bool {
+        match self {
+            HashPattern::TrailingZeros(zeros) => {
+                hash_matches_difficulty(hash, *zeros) == Ok(true)
+            }
+            HashPattern::LeadingZeros(zeros) => hash_starts_with_zeros(hash, *zeros),
+            HashPattern::Contains(needle) => hash.contains(needle.as_str()),
+            HashPattern::StartsWith(prefix) => hash.starts_with(prefix.as_str()),
+            HashPattern::EndsWith(suffix) => hash.ends_with(suffix.as_str()),
+            #[cfg(feature = "regex")]
+            HashPattern::Regex(regex) => regex.is_match(hash),
+        }
+    }
+}
+
+/// Whether `hash` satisfies `pattern`; same as [`HashPattern::matches`].
+pub fn hash_pattern_match(hash: &str, pattern: &HashPattern) -> bool {
+    pattern.matches(hash)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hash_pattern_match() {
+        let hash = "00ab12c0ffee3000";
+        for (pattern, expected) in [
+            (HashPattern::TrailingZeros(3), true),
+            (HashPattern::TrailingZeros(4), false),
+            (HashPattern::LeadingZeros(2), true),
+            (HashPattern::LeadingZeros(3), false),
+            (HashPattern::Contains("c0ffee".to_string()), true),
+            (HashPattern::Contains("beef".to_string()), false),
+            (HashPattern::StartsWith("00ab".to_string()), true),
+            (HashPattern::StartsWith("ab".to_string()), false),
+            (HashPattern::EndsWith("3000".to_string()), true),
+            (HashPattern::EndsWith("30".to_string()), false),
+        ] {
+            assert_eq!(hash_pattern_match(hash, &pattern), expected, "{:?}", pattern);
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "regex")]
+    fn test_regex_pattern() {
+        let pattern = HashPattern::Regex(HashRegex::new("^00.*000$").unwrap());
+        assert!(pattern.matches("00ab12c0ffee3000"));
+        assert!(!pattern.matches("00ab12c0ffee3001"));
+    }
+}
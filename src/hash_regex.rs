@@ -0,0 +1,77 @@
+use regex::Regex;
+use std::fmt;
+use std::str::FromStr;
+
+/// Regular expression matched against the lowercase hex hash, e.g. `^00|cafe.*cafe`.
+///
+/// Compiled once when parsed. Every candidate must be hex encoded before it can be
+/// matched, so searches with a regex run noticeably slower than zero, prefix or suffix
+/// searches, which compare digest bytes directly.
+#[derive(Debug, Clone)]
+pub struct HashRegex(Regex);
+
+impl HashRegex {
+    pub fn new(pattern: &str) -> Result<Self, String> {
+        Regex::new(pattern)
+            .map(HashRegex)
+            .map_err(|e| format!("invalid regex '{}': {}", pattern, e))
+    }
+
+    pub fn as_str(&self) -> &str {
+        self.0.as_str()
+    }
+
+    /// Whether the regex matches anywhere in the lowercase hex `hash`.
+    pub fn is_match(&self, hash: &str) -> bool {
+        self.0.is_match(hash)
+    }
+}
+
+/// Regexes are equal when compiled from the same pattern.
+impl PartialEq for HashRegex {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_str() == other.as_str()
+    }
+}
+
+impl Eq for HashRegex {}
+
+impl fmt::Display for HashRegex {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Serialized as its display form, the pattern.
+#[cfg(feature = "serde")]
+impl serde::Serialize for HashRegex {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl FromStr for HashRegex {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        HashRegex::new(s)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hash_regex() {
+        let regex: HashRegex = "00$|cafe.*cafe".parse().unwrap();
+        assert_eq!(regex.to_string(), "00$|cafe.*cafe");
+        assert!(regex.is_match("ab00"));
+        assert!(regex.is_match("cafe12cafe"));
+        assert!(!regex.is_match("cafe0"));
+        assert_eq!(regex, HashRegex::new("00$|cafe.*cafe").unwrap());
+
+        let err = "(".parse::<HashRegex>().unwrap_err();
+        assert!(err.starts_with("invalid regex '('"), "{}", err);
+    }
+}
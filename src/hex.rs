@@ -0,0 +1,31 @@
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+/// Lowercase hex encoding, matching the `{:x}` formatting of digests.
+pub fn hex_encode(bytes: &[u8]) -> String {
+    const HEX: &[u8; 16] = b"0123456789abcdef";
+    let mut out = String::with_capacity(bytes.len() * 2);
+    for &b in bytes {
+        out.push(HEX[(b >> 4) as usize] as char);
+        out.push(HEX[(b & 0x0f) as usize] as char);
+    }
+    out
+}
+
+/// Decodes hex digits (either case) into bytes; the inverse of [`hex_encode`].
+pub fn hex_decode(hex: &str) -> Result<Vec<u8>, String> {
+    if !hex.len().is_multiple_of(2) {
+        return Err(format!("hex string has an odd number of digits ({})", hex.len()));
+    }
+    hex.as_bytes()
+        .chunks(2)
+        .enumerate()
+        .map(|(i, pair)| {
+            core::str::from_utf8(pair)
+                .ok()
+                .and_then(|pair| u8::from_str_radix(pair, 16).ok())
+                .ok_or_else(|| format!("invalid hex digit at position {}", i * 2))
+        })
+        .collect()
+}
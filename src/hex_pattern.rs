@@ -0,0 +1,462 @@
+use crate::MAX_DIGEST_LEN;
+use std::collections::BTreeSet;
+use std::fmt;
+use std::str::FromStr;
+
+/// Hex digits a matching digest must start with, end with or contain, e.g. `deadbeef`.
+///
+/// Trailing zeros are the special case of a `000…` suffix. Parsing accepts either case and
+/// stores the digits lowercase, as hashes are printed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HexPattern {
+    digits: String,
+}
+
+impl HexPattern {
+    /// Number of hex digits, i.e. the difficulty of matching it.
+    pub fn len(&self) -> usize {
+        self.digits.len()
+    }
+
+    /// Always false; parsing rejects empty patterns.
+    pub fn is_empty(&self) -> bool {
+        self.digits.is_empty()
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.digits
+    }
+
+    /// Whether the lowercase hex `hash` starts with this pattern.
+    pub fn is_prefix_of(&self, hash: &str) -> bool {
+        hash.starts_with(&self.digits)
+    }
+
+    /// Whether the lowercase hex `hash` ends with this pattern.
+    pub fn is_suffix_of(&self, hash: &str) -> bool {
+        hash.ends_with(&self.digits)
+    }
+
+    /// Whether the lowercase hex `hash` contains this pattern anywhere.
+    pub fn is_infix_of(&self, hash: &str) -> bool {
+        hash.contains(self.digits.as_str())
+    }
+
+    /// Like [`HexPattern::is_prefix_of`], but compares nibbles of the raw digest directly.
+    pub fn is_prefix_of_digest(&self, digest: &[u8]) -> bool {
+        if self.len() > digest.len() * 2 {
+            return false;
+        }
+        self.digits.bytes().enumerate().all(|(i, digit)| {
+            let byte = digest[i / 2];
+            let nibble = if i % 2 == 0 { byte >> 4 } else { byte & 0x0f };
+            nibble_digit(nibble) == digit
+        })
+    }
+
+    /// Like [`HexPattern::is_suffix_of`], but compares nibbles of the raw digest directly.
+    pub fn is_suffix_of_digest(&self, digest: &[u8]) -> bool {
+        if self.len() > digest.len() * 2 {
+            return false;
+        }
+        self.digits.bytes().rev().enumerate().all(|(i, digit)| {
+            let byte = digest[digest.len() - 1 - i / 2];
+            let nibble = if i % 2 == 0 { byte & 0x0f } else { byte >> 4 };
+            nibble_digit(nibble) == digit
+        })
+    }
+
+    /// Like [`HexPattern::is_infix_of`], hex encoding the digest into a stack buffer.
+    pub fn is_infix_of_digest(&self, digest: &[u8]) -> bool {
+        let mut buf = [0u8; MAX_DIGEST_LEN * 2];
+        let hex = encode_hex(&digest[..digest.len().min(MAX_DIGEST_LEN)], &mut buf);
+        self.is_infix_of(hex)
+    }
+}
+
+/// Lowercase hex of `bytes`, which must fit in `buf`, without allocating.
+fn encode_hex<'a>(bytes: &[u8], buf: &'a mut [u8; MAX_DIGEST_LEN * 2]) -> &'a str {
+    for (i, byte) in bytes.iter().enumerate() {
+        buf[i * 2] = nibble_digit(byte >> 4);
+        buf[i * 2 + 1] = nibble_digit(byte & 0x0f);
+    }
+    std::str::from_utf8(&buf[..bytes.len() * 2]).expect("hex digits are ASCII")
+}
+
+fn nibble_digit(nibble: u8) -> u8 {
+    b"0123456789abcdef"[nibble as usize]
+}
+
+impl fmt::Display for HexPattern {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.digits)
+    }
+}
+
+/// Serialized as its display form, the hex digits.
+#[cfg(feature = "serde")]
+impl serde::Serialize for HexPattern {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl FromStr for HexPattern {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.is_empty() {
+            return Err("hex pattern must not be empty".to_string());
+        }
+        if let Some(c) = s.chars().find(|c| !c.is_ascii_hexdigit()) {
+            return Err(format!("invalid hex digit '{}' in '{}'", c, s));
+        }
+        Ok(HexPattern {
+            digits: s.to_ascii_lowercase(),
+        })
+    }
+}
+
+/// Hex digits with `?` standing for any single digit, e.g. `de?dbe?f`, matched at one end
+/// of the hash.
+///
+/// A cheap alternative to a regex for vanity searches: matching compares digit by digit,
+/// skipping the `?` positions. Parsing accepts either case, stores the digits lowercase,
+/// and rejects patterns without any fixed digit.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WildcardPattern {
+    digits: String,
+}
+
+impl WildcardPattern {
+    /// Number of positions, including wildcards.
+    pub fn len(&self) -> usize {
+        self.digits.len()
+    }
+
+    /// Always false; parsing rejects empty patterns.
+    pub fn is_empty(&self) -> bool {
+        self.digits.is_empty()
+    }
+
+    /// Number of fixed digits, i.e. the difficulty of matching it.
+    pub fn fixed_len(&self) -> usize {
+        self.digits.bytes().filter(|&digit| digit != b'?').count()
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.digits
+    }
+
+    /// Whether the lowercase hex `hash` starts with this pattern.
+    pub fn is_prefix_of(&self, hash: &str) -> bool {
+        hash.len() >= self.len() && digits_match(self.digits.bytes(), hash.bytes())
+    }
+
+    /// Whether the lowercase hex `hash` ends with this pattern.
+    pub fn is_suffix_of(&self, hash: &str) -> bool {
+        hash.len() >= self.len() && digits_match(self.digits.bytes().rev(), hash.bytes().rev())
+    }
+
+    /// Like [`WildcardPattern::is_prefix_of`], but compares nibbles of the raw digest
+    /// directly.
+    pub fn is_prefix_of_digest(&self, digest: &[u8]) -> bool {
+        if self.len() > digest.len() * 2 {
+            return false;
+        }
+        let nibbles = (0..self.len()).map(|i| {
+            let byte = digest[i / 2];
+            nibble_digit(if i % 2 == 0 { byte >> 4 } else { byte & 0x0f })
+        });
+        digits_match(self.digits.bytes(), nibbles)
+    }
+
+    /// Like [`WildcardPattern::is_suffix_of`], but compares nibbles of the raw digest
+    /// directly.
+    pub fn is_suffix_of_digest(&self, digest: &[u8]) -> bool {
+        if self.len() > digest.len() * 2 {
+            return false;
+        }
+        let nibbles = (0..self.len()).map(|i| {
+            let byte = digest[digest.len() - 1 - i / 2];
+            nibble_digit(if i % 2 == 0 { byte & 0x0f } else { byte >> 4 })
+        });
+        digits_match(self.digits.bytes().rev(), nibbles)
+    }
+}
+
+/// Whether every pattern digit equals the hash digit beside it, `?` matching any.
+fn digits_match(pattern: impl Iterator<Item = u8>, hash: impl Iterator<Item = u8>) -> bool {
+    pattern.zip(hash).all(|(digit, hex)| digit == b'?' || digit == hex)
+}
+
+impl fmt::Display for WildcardPattern {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.digits)
+    }
+}
+
+/// Serialized as its display form, e.g. `de?dbe?f`.
+#[cfg(feature = "serde")]
+impl serde::Serialize for WildcardPattern {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl FromStr for WildcardPattern {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.is_empty() {
+            return Err("wildcard pattern must not be empty".to_string());
+        }
+        if let Some(c) = s.chars().find(|&c| !c.is_ascii_hexdigit() && c != '?') {
+            return Err(format!(
+                "invalid character '{}' in '{}' (expected 0-9, a-f or ?)",
+                c, s
+            ));
+        }
+        if s.bytes().all(|b| b == b'?') {
+            return Err(format!("wildcard pattern '{}' has no fixed digits", s));
+        }
+        Ok(WildcardPattern {
+            digits: s.to_ascii_lowercase(),
+        })
+    }
+}
+
+/// Hex suffixes a matching hash may end with; any one of them is enough.
+///
+/// Suffixes are bucketed by length, so checking a hash costs one set lookup per distinct
+/// length rather than one comparison per suffix. When several match, e.g. `0` and `000`,
+/// the longest wins.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SuffixSet {
+    /// Distinct lengths, longest first, each with the suffixes of that length.
+    buckets: Vec<(usize, BTreeSet<String>)>,
+}
+
+impl SuffixSet {
+    pub fn new(suffixes: impl IntoIterator<Item = HexPattern>) -> Self {
+        let mut set = SuffixSet::default();
+        for suffix in suffixes {
+            set.insert(suffix);
+        }
+        set
+    }
+
+    pub fn insert(&mut self, suffix: HexPattern) {
+        let len = suffix.len();
+        match self.buckets.binary_search_by(|(l, _)| len.cmp(l)) {
+            Ok(i) => {
+                self.buckets[i].1.insert(suffix.digits);
+            }
+            Err(i) => self.buckets.insert(i, (len, BTreeSet::from([suffix.digits]))),
+        }
+    }
+
+    /// Number of distinct suffixes.
+    pub fn len(&self) -> usize {
+        self.buckets.iter().map(|(_, bucket)| bucket.len()).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.buckets.is_empty()
+    }
+
+    /// Length of the shortest suffix, or 0 when empty.
+    pub fn min_len(&self) -> usize {
+        self.buckets.last().map_or(0, |&(len, _)| len)
+    }
+
+    /// Length of the longest suffix, or 0 when empty.
+    pub fn max_len(&self) -> usize {
+        self.buckets.first().map_or(0, |&(len, _)| len)
+    }
+
+    /// Chance that a uniformly random hash ends with one of the suffixes, ignoring any
+    /// overlap between them.
+    pub fn match_probability(&self) -> f64 {
+        self.buckets
+            .iter()
+            .map(|(len, bucket)| bucket.len() as f64 / 16f64.powi(*len as i32))
+            .sum()
+    }
+
+    /// The suffixes, longest first.
+    pub fn iter(&self) -> impl Iterator<Item = &str> {
+        self.buckets
+            .iter()
+            .flat_map(|(_, bucket)| bucket.iter().map(String::as_str))
+    }
+
+    /// The longest suffix the lowercase hex `hash` ends with, if any.
+    pub fn matching(&self, hash: &str) -> Option<&str> {
+        self.buckets.iter().find_map(|(len, bucket)| {
+            let tail = hash.get(hash.len().checked_sub(*len)?..)?;
+            bucket.get(tail).map(String::as_str)
+        })
+    }
+
+    /// Like [`SuffixSet::matching`], but only hex encodes as much of the digest's tail as
+    /// the longest suffix needs.
+    pub fn matching_digest(&self, digest: &[u8]) -> Option<&str> {
+        let bytes = self.max_len().div_ceil(2).min(digest.len()).min(MAX_DIGEST_LEN);
+        let mut buf = [0u8; MAX_DIGEST_LEN * 2];
+        self.matching(encode_hex(&digest[digest.len() - bytes..], &mut buf))
+    }
+}
+
+impl FromIterator<HexPattern> for SuffixSet {
+    fn from_iter<I: IntoIterator<Item = HexPattern>>(iter: I) -> Self {
+        SuffixSet::new(iter)
+    }
+}
+
+/// Comma-separated, longest first.
+impl fmt::Display for SuffixSet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, suffix) in self.iter().enumerate() {
+            if i > 0 {
+                f.write_str(",")?;
+            }
+            f.write_str(suffix)?;
+        }
+        Ok(())
+    }
+}
+
+/// Serialized as its display form, the comma-separated suffixes.
+#[cfg(feature = "serde")]
+impl serde::Serialize for SuffixSet {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hex_decode;
+
+    #[test]
+    fn test_parse_pattern() {
+        let pattern: HexPattern = "DeadBeef".parse().unwrap();
+        assert_eq!(pattern.as_str(), "deadbeef");
+        assert_eq!(pattern.len(), 8);
+
+        assert!("".parse::<HexPattern>().is_err());
+        assert!("abg".parse::<HexPattern>().is_err());
+        assert!("0x12".parse::<HexPattern>().is_err());
+    }
+
+    #[test]
+    fn test_matches_digest() {
+        let hex = "0123456789abcdef";
+        let digest = hex_decode(hex).unwrap();
+        for (pattern, prefix, suffix) in [
+            ("0", true, false),
+            ("f", false, true),
+            ("01", true, false),
+            ("ef", false, true),
+            ("012", true, false),
+            ("def", false, true),
+            ("0123456789abcdef", true, true),
+            ("10", false, false),
+            ("fe", false, false),
+            ("00123456789abcdef", false, false),
+        ] {
+            let pattern: HexPattern = pattern.parse().unwrap();
+            assert_eq!(pattern.is_prefix_of_digest(&digest), prefix, "{}", pattern);
+            assert_eq!(pattern.is_prefix_of(hex), prefix, "{}", pattern);
+            assert_eq!(pattern.is_suffix_of_digest(&digest), suffix, "{}", pattern);
+            assert_eq!(pattern.is_suffix_of(hex), suffix, "{}", pattern);
+        }
+    }
+
+    #[test]
+    fn test_infix() {
+        let hex = "0123456789abcdef";
+        let digest = hex_decode(hex).unwrap();
+        for (pattern, infix) in [
+            ("0", true),
+            ("345", true),
+            ("9abc", true),
+            ("23456789abcdef", true),
+            ("0123456789abcdef", true),
+            ("35", false),
+            ("00123456789abcdef", false),
+        ] {
+            let pattern: HexPattern = pattern.parse().unwrap();
+            assert_eq!(pattern.is_infix_of_digest(&digest), infix, "{}", pattern);
+            assert_eq!(pattern.is_infix_of(hex), infix, "{}", pattern);
+        }
+    }
+
+    #[test]
+    fn test_wildcard() {
+        let pattern: WildcardPattern = "De?dBE?F".parse().unwrap();
+        assert_eq!(pattern.as_str(), "de?dbe?f");
+        assert_eq!((pattern.len(), pattern.fixed_len()), (8, 6));
+
+        assert!("".parse::<WildcardPattern>().is_err());
+        assert!("???".parse::<WildcardPattern>().is_err());
+        assert!("de*d".parse::<WildcardPattern>().is_err());
+        assert!("0x?".parse::<WildcardPattern>().is_err());
+
+        let hex = "0123456789abcdef";
+        let digest = hex_decode(hex).unwrap();
+        for (pattern, prefix, suffix) in [
+            ("0", true, false),
+            ("?1", true, false),
+            ("0?2", true, false),
+            ("1?2", false, false),
+            ("e?", false, true),
+            ("?ef", false, true),
+            ("d?f", false, true),
+            ("d?e", false, false),
+            ("0?????????????e?", true, true),
+            ("0???????????????f", false, false),
+        ] {
+            let pattern: WildcardPattern = pattern.parse().unwrap();
+            assert_eq!(pattern.is_prefix_of_digest(&digest), prefix, "{}", pattern);
+            assert_eq!(pattern.is_prefix_of(hex), prefix, "{}", pattern);
+            assert_eq!(pattern.is_suffix_of_digest(&digest), suffix, "{}", pattern);
+            assert_eq!(pattern.is_suffix_of(hex), suffix, "{}", pattern);
+        }
+    }
+
+    #[test]
+    fn test_suffix_set() {
+        let set: SuffixSet = ["000", "aaa", "0", "FFF", "aaa"]
+            .iter()
+            .map(|s| s.parse::<HexPattern>().unwrap())
+            .collect();
+        assert_eq!(set.len(), 4);
+        assert_eq!((set.min_len(), set.max_len()), (1, 3));
+        assert_eq!(set.to_string(), "000,aaa,fff,0");
+
+        for (hex, expected) in [
+            ("1234000", Some("000")),
+            ("12340", Some("0")),
+            ("12aaa", Some("aaa")),
+            ("fff", Some("fff")),
+            ("ff", None),
+            ("aa", None),
+            ("", None),
+        ] {
+            assert_eq!(set.matching(hex), expected, "{}", hex);
+            if hex.len() % 2 == 0 {
+                let digest = hex_decode(hex).unwrap();
+                assert_eq!(set.matching_digest(&digest), expected, "{}", hex);
+            }
+        }
+        let digest = hex_decode("123aaa").unwrap();
+        assert_eq!(set.matching_digest(&digest), Some("aaa"));
+
+        assert!(SuffixSet::default().is_empty());
+        assert_eq!(SuffixSet::default().matching_digest(&[0]), None);
+        assert!((set.match_probability() - (3.0 / 4096.0 + 1.0 / 16.0)).abs() < 1e-12);
+    }
+}
@@ -0,0 +1,96 @@
+use crate::{hex_decode, hex_encode};
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use std::fmt;
+
+/// Secret key for HMAC-SHA256 keyed searches.
+///
+/// Neither `Debug` nor `Serialize` reveals the key bytes, so configs holding a key are safe
+/// to log.
+#[derive(Clone, PartialEq, Eq)]
+pub struct HmacKey(Vec<u8>);
+
+impl HmacKey {
+    pub fn new(key: impl Into<Vec<u8>>) -> Self {
+        HmacKey(key.into())
+    }
+
+    /// Parses a key given as hex digits, ignoring surrounding whitespace.
+    pub fn from_hex(hex: &str) -> Result<Self, String> {
+        let key = hex_decode(hex.trim()).map_err(|e| format!("invalid HMAC key: {}", e))?;
+        if key.is_empty() {
+            return Err("HMAC key must not be empty".to_string());
+        }
+        Ok(HmacKey(key))
+    }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.0
+    }
+
+    /// HMAC-SHA256 of the concatenation of `parts`.
+    pub fn mac(&self, parts: &[&[u8]]) -> [u8; 32] {
+        let mut mac =
+            Hmac::<Sha256>::new_from_slice(&self.0).expect("HMAC accepts keys of any length");
+        for part in parts {
+            mac.update(part);
+        }
+        mac.finalize().into_bytes().into()
+    }
+
+    /// [`HmacKey::mac`] as lowercase hex.
+    pub fn mac_hex(&self, parts: &[&[u8]]) -> String {
+        hex_encode(&self.mac(parts))
+    }
+}
+
+impl fmt::Debug for HmacKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("HmacKey(<redacted>)")
+    }
+}
+
+/// Serialized as `"<redacted>"`, like `Debug`.
+#[cfg(feature = "serde")]
+impl serde::Serialize for HmacKey {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str("<redacted>")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // RFC 4231 test cases 1, 2 and 6.
+    #[test]
+    fn test_rfc4231_vectors() {
+        let key = HmacKey::new([0x0b; 20]);
+        assert_eq!(
+            key.mac_hex(&[b"Hi There"]),
+            "b0344c61d8db38535ca8afceaf0bf12b881dc200c9833da726e9376c2e32cff7"
+        );
+
+        let key = HmacKey::from_hex("4a656665").unwrap();
+        assert_eq!(key.as_bytes(), b"Jefe");
+        assert_eq!(
+            key.mac_hex(&[b"what do ya want ", b"for nothing?"]),
+            "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+        );
+
+        let key = HmacKey::new([0xaa; 131]);
+        assert_eq!(
+            key.mac_hex(&[b"Test Using Larger Than Block-Size Key - Hash Key First"]),
+            "60e431591ee0b67f0d8a26aacbf5b77f8e0bc6213728c5140546040f0ee37f54"
+        );
+    }
+
+    #[test]
+    fn test_key_is_redacted_and_validated() {
+        let key = HmacKey::from_hex(" 4A656665\n").unwrap();
+        assert_eq!(format!("{:?}", key), "HmacKey(<redacted>)");
+        assert!(HmacKey::from_hex("").is_err());
+        assert!(HmacKey::from_hex("abc").is_err());
+        assert!(HmacKey::from_hex("zz").is_err());
+    }
+}
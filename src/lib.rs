@@ -1,140 +1,1368 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+#[cfg(feature = "std")]
+mod algorithm;
+#[cfg(feature = "argon2")]
+mod argon2_params;
+#[cfg(feature = "tokio")]
+mod async_search;
+#[cfg(feature = "std")]
+mod block_order;
+#[cfg(feature = "std")]
+mod cancel;
+#[cfg(feature = "checkpoint")]
+mod checkpoint;
+#[cfg(feature = "std")]
+mod collision;
+#[cfg(feature = "std")]
+mod config;
+#[cfg(feature = "std")]
+mod constraint;
+mod difficulty;
+mod encoding;
+#[cfg(feature = "std")]
+mod error;
+#[cfg(feature = "std")]
+mod hash_pattern;
+#[cfg(feature = "regex")]
+mod hash_regex;
+mod hex;
+#[cfg(feature = "std")]
+mod hex_pattern;
+#[cfg(feature = "std")]
+mod hmac_key;
+#[cfg(feature = "std")]
+mod output;
+#[cfg(feature = "serde")]
+mod persistence;
+#[cfg(feature = "std")]
+mod predicate;
+#[cfg(feature = "std")]
+mod progress;
+#[cfg(feature = "std")]
+mod result;
+#[cfg(feature = "std")]
+mod search;
+#[cfg(feature = "std")]
+mod shard;
+#[cfg(feature = "std")]
+mod stream;
+#[cfg(feature = "std")]
+mod template;
+#[cfg(feature = "std")]
+mod verify;
+#[cfg(feature = "wasm")]
+mod wasm;
+
+use alloc::string::String;
+use core::time::Duration;
+#[cfg(feature = "std")]
+use std::ops::RangeInclusive;
+#[cfg(feature = "std")]
 use rayon::prelude::*;
-use sha2::{Digest, Sha256};
-use std::sync::atomic::{AtomicUsize, Ordering};
-use std::sync::Arc;
-use tracing::{debug, info, warn, instrument};
+#[cfg(feature = "std")]
+use std::collections::{HashMap, HashSet};
+#[cfg(feature = "std")]
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+#[cfg(feature = "std")]
+use std::sync::Mutex;
+#[cfg(feature = "std")]
+use std::time::Instant;
+#[cfg(feature = "std")]
+use tracing::{debug, instrument};
 
-#[cfg(feature = "crossbeam")]
-use crossbeam_channel::bounded;
+#[cfg(feature = "std")]
+pub use algorithm::{DigestBytes, HashAlgorithm, MAX_DIGEST_LEN};
+#[cfg(feature = "argon2")]
+pub use argon2_params::Argon2Params;
+#[cfg(feature = "tokio")]
+pub use async_search::{find_hashes_async, find_hashes_stream};
+#[cfg(feature = "std")]
+pub use cancel::CancellationToken;
+#[cfg(feature = "checkpoint")]
+pub use checkpoint::{CheckpointParams, SearchCheckpoint};
+pub use sha2::Digest;
+#[cfg(feature = "std")]
+pub use collision::{collision_expected_attempts, find_suffix_collision, MAX_COLLISION_DIGITS};
+#[cfg(feature = "std")]
+pub use config::{ConfigError, HashFinderConfig, MatchDirection, SearchOutcome};
+#[cfg(feature = "std")]
+pub use constraint::AlgorithmConstraint;
+pub use difficulty::DifficultyError;
+pub use encoding::{InputEncoding, NonceEncoding};
+#[cfg(feature = "std")]
+pub use error::HashFinderError;
+#[cfg(feature = "std")]
+pub use hash_pattern::{hash_pattern_match, HashPattern};
+#[cfg(feature = "regex")]
+pub use hash_regex::HashRegex;
+pub use hex::{hex_decode, hex_encode};
+#[cfg(feature = "std")]
+pub use hex_pattern::{HexPattern, SuffixSet, WildcardPattern};
+#[cfg(feature = "std")]
+pub use hmac_key::HmacKey;
+#[cfg(feature = "std")]
+pub use output::{
+    format_chain, format_results, format_results_with_also, format_results_with_encoding,
+    format_salted, format_words, write_raw_digests, HashEncoding, OutputFormat,
+};
+#[cfg(feature = "serde")]
+pub use persistence::{load_results, save_results};
+#[cfg(feature = "std")]
+pub use predicate::Predicate;
+#[cfg(feature = "std")]
+pub use progress::{
+    BenchmarkResult, ProgressEvent, ProgressReport, ProgressReporter, SearchProgress,
+    SearchStats,
+};
+#[cfg(feature = "std")]
+pub use result::{HashResult, WordResult};
+#[cfg(feature = "std")]
+pub use shard::{Shard, SHARD_BLOCK};
+#[cfg(feature = "std")]
+pub use stream::{HashFinder, HashStream};
+#[cfg(feature = "std")]
+pub use template::InputTemplate;
+#[cfg(feature = "std")]
+pub use verify::{
+    verify_chain, verify_hash, verify_hash_with_algo, verify_result_with_config, verify_results,
+};
+#[cfg(feature = "wasm")]
+pub use wasm::{compute_hash_wasm, find_hashes_wasm, hash_ends_with_zeros_wasm};
 
-#[instrument(skip_all, fields(num = %num))]
+#[cfg_attr(feature = "std", instrument(skip_all, fields(num = num)))]
 pub fn compute_hash(num: u64) -> String {
-    let mut hasher = Sha256::new();
-    hasher.update(num.to_string().as_bytes());
-    let result = hasher.finalize();
-    format!("{:x}", result)
+    compute_hash_with_encoding(num, InputEncoding::DecimalString)
+}
+
+/// SHA-256 of `num` encoded with `encoding`, as lowercase hex, e.g. for protocols that
+/// hash the nonce as 8 big-endian bytes rather than decimal text.
+pub fn compute_hash_with_encoding(num: u64, encoding: InputEncoding) -> String {
+    let mut buf = [0; encoding::MAX_ENCODED_LEN];
+    compute_hash_bytes(encoding.encode_into(num, &mut buf))
+}
+
+/// SHA-256 of arbitrary `input` bytes, as lowercase hex.
+pub fn compute_hash_bytes(input: &[u8]) -> String {
+    hex_encode(&sha2::Sha256::digest(input))
+}
+
+/// SHA-256 of the UTF-8 bytes of `input`, so `compute_hash_str("4163")` equals
+/// `compute_hash(4163)`.
+pub fn compute_hash_str(input: &str) -> String {
+    compute_hash_bytes(input.as_bytes())
+}
+
+/// Raw SHA-256 digest of the decimal representation of `num`, computed without any heap
+/// allocation.
+pub fn compute_hash_raw(num: u64) -> [u8; 32] {
+    let mut buf = [0; encoding::MAX_ENCODED_LEN];
+    sha2::Sha256::digest(NonceEncoding::DecimalString.encode_into(num, &mut buf)).into()
+}
+
+/// Raw SHA-256 digest of the decimal representation of `num`; same as
+/// [`compute_hash_raw`].
+pub fn compute_digest(num: u64) -> [u8; 32] {
+    compute_hash_raw(num)
+}
+
+/// SHA-256 of `salt || num.to_string()`.
+///
+/// The salt is hashed before the number, so any change to it produces a completely
+/// different hash sequence; an empty salt matches [`compute_hash`].
+#[cfg(feature = "std")]
+pub fn compute_hash_salted(num: u64, salt: &[u8]) -> String {
+    HashAlgorithm::Sha256.hash_parts_hex(&[salt, num.to_string().as_bytes()])
+}
+
+/// HMAC-SHA256 of the decimal representation of `num` under `key`.
+///
+/// Any key is accepted here, as HMAC defines; searches reject an empty key when the config
+/// is built.
+#[cfg(feature = "std")]
+pub fn compute_hmac(num: u64, key: &[u8]) -> String {
+    HmacKey::new(key).mac_hex(&[num.to_string().as_bytes()])
+}
+
+/// SHA-256 of `num` in decimal, left-padded with zeros to `width` digits, so
+/// `compute_hash_padded(123, 7)` hashes `"0000123"`.
+///
+/// Fails when `width` is 0 or above 20, or when `num` has more than `width` digits.
+#[cfg(feature = "std")]
+pub fn compute_hash_padded(num: u64, width: usize) -> Result<String, ConfigError> {
+    let config = HashFinderConfig::new().pad_width(width);
+    if width == 0 || width > encoding::MAX_ENCODED_LEN {
+        return Err(ConfigError::PadWidthOutOfRange { width });
+    }
+    if !config.fits_pad_width(num) {
+        return Err(ConfigError::NonceExceedsPadWidth { num, width });
+    }
+    Ok(config.hash(num))
+}
+
+#[cfg(feature = "std")]
+#[instrument(skip_all, fields(num = num, algo = %algo))]
+pub fn compute_hash_with_algo(num: u64, algo: HashAlgorithm) -> String {
+    algo.hash_hex(num.to_string().as_bytes())
+}
+
+/// `SHA256(SHA256(num))` as hashed by Bitcoin, where the second pass hashes the raw
+/// 32-byte digest rather than its hex form.
+///
+/// Shorthand for [`HashAlgorithm::Sha256d`]; use [`HashFinderConfig::iterations`] for
+/// more rounds.
+#[cfg(feature = "std")]
+pub fn compute_hash_double(num: u64) -> String {
+    compute_hash_with_algo(num, HashAlgorithm::Sha256d)
+}
+
+/// [`compute_hash`] of each of `nums` in parallel, paired with its number, in input order.
+///
+/// ```
+/// let hashes = rust_hash_finder::batch_compute_hashes(&[4163, 7]);
+/// assert_eq!(hashes[0].0, 4163);
+/// assert!(hashes[0].1.ends_with("000"));
+/// assert_eq!(hashes[1], (7, rust_hash_finder::compute_hash(7)));
+/// ```
+#[cfg(feature = "std")]
+pub fn batch_compute_hashes(nums: &[u64]) -> Vec<(u64, String)> {
+    nums.par_iter().map(|&num| (num, compute_hash(num))).collect()
+}
+
+/// BLAKE3 hash of the decimal representation of `num`.
+#[cfg(feature = "blake3")]
+pub fn compute_hash_blake3(num: u64) -> String {
+    compute_hash_with_algo(num, HashAlgorithm::Blake3)
+}
+
+/// Whether the hex `hash` ends with `zeros` zeros: the built-in predicate behind
+/// [`find_hashes`], which can be swapped for any other with [`find_hashes_where`].
+///
+/// Fails with [`DifficultyError::ZeroDifficulty`] when `zeros` is 0,
+/// [`DifficultyError::TooManyZeros`] when `zeros` exceeds the length of `hash`, so that no
+/// hash could match, and [`DifficultyError::InvalidHex`] when `hash` is not all hex
+/// digits. `zeros` equal to the length checks the whole hash.
+///
+/// ```
+/// use rust_hash_finder::{hash_matches_difficulty, DifficultyError};
+///
+/// assert_eq!(hash_matches_difficulty("abc000", 3), Ok(true));
+/// assert_eq!(hash_matches_difficulty("abc000", 4), Ok(false));
+/// assert_eq!(hash_matches_difficulty("abc000", 0), Err(DifficultyError::ZeroDifficulty));
+/// ```
+pub fn hash_matches_difficulty(hash: &str, zeros: usize) -> Result<bool, DifficultyError> {
+    if zeros == 0 {
+        return Err(DifficultyError::ZeroDifficulty);
+    }
+    if zeros > hash.len() {
+        return Err(DifficultyError::TooManyZeros {
+            zeros,
+            len: hash.len(),
+        });
+    }
+    if let Some((index, character)) = hash.char_indices().find(|(_, c)| !c.is_ascii_hexdigit()) {
+        return Err(DifficultyError::InvalidHex { index, character });
+    }
+    Ok(hash.as_bytes()[hash.len() - zeros..].iter().all(|&b| b == b'0'))
 }
 
+/// Whether the hex `hash` ends with `zeros` zeros, false for a `zeros` of 0 or longer than
+/// `hash`; only the trailing digits are looked at.
+#[deprecated(note = "use hash_matches_difficulty, which reports invalid input")]
 pub fn hash_ends_with_zeros(hash: &str, zeros: usize) -> bool {
     if zeros == 0 || zeros > hash.len() {
         return false;
     }
-    hash.ends_with(&"0".repeat(zeros))
-}
-
-#[cfg(feature = "atomics")]
-#[instrument(skip_all, fields(zeros = %zeros, max_results = %max_results))]
-pub fn find_hashes(zeros: usize, max_results: usize) -> Vec<(u64, String)> {
-    info!("Starting hash search with atomics implementation");
-    
-    let found_count = Arc::new(AtomicUsize::new(0));
-    let found_count_clone = Arc::clone(&found_count);
-    let results: Arc<std::sync::Mutex<Vec<(u64, String)>>> = 
-        Arc::new(std::sync::Mutex::new(Vec::new()));
-    let results_clone = Arc::clone(&results);
-    let suffix = "0".repeat(zeros);
-    
-    debug!("Searching for hashes ending with {} zeros", zeros);
-    
-    (1u64..)
-        .par_bridge()
-        .find_any(|&num| {
-            if found_count_clone.load(Ordering::Relaxed) >= max_results {
-                return true;
-            }
-            
-            let hash = compute_hash(num);
-            
-            if hash.ends_with(&suffix) {
-                let current = found_count_clone.fetch_add(1, Ordering::SeqCst);
-                
-                if current < max_results {
-                    debug!("Found hash: num={}, hash={}", num, hash);
-                    if let Ok(mut guard) = results_clone.lock() {
-                        guard.push((num, hash));
-                    }
-                }
-                
-                if current + 1 >= max_results {
-                    info!("Reached target of {} results", max_results);
-                    return true;
-                }
-            }
-            
-            false
-        });
-    
-    match Arc::try_unwrap(results) {
-        Ok(mutex) => {
-            let results = mutex.into_inner().unwrap_or_default();
-            info!("Search completed, found {} results", results.len());
-            results
-        }
-        Err(arc) => {
-            let results = arc.lock().unwrap().clone();
-            warn!("Had to clone results (Arc still has references)");
-            results
-        }
-    }
-}
-
-#[cfg(feature = "crossbeam")]
-#[instrument(skip_all, fields(zeros = %zeros, max_results = %max_results))]
-pub fn find_hashes(zeros: usize, max_results: usize) -> Vec<(u64, String)> {
-    info!("Starting hash search with crossbeam-channel implementation");
-    
-    let (tx, rx) = bounded::<(u64, String)>(100);
-    let found_count = Arc::new(AtomicUsize::new(0));
-    let found_count_clone = Arc::clone(&found_count);
-    let suffix = "0".repeat(zeros);
-    
-    debug!("Searching for hashes ending with {} zeros", zeros);
-    
-    let consumer = std::thread::spawn(move || {
-        let mut results = Vec::new();
-        for (num, hash) in rx {
-            debug!("Received hash: num={}, hash={}", num, hash);
-            results.push((num, hash));
-        }
-        results
-    });
-    
-    (1u64..)
-        .par_bridge()
-        .find_any(|&num| {
-            if found_count_clone.load(Ordering::Relaxed) >= max_results {
-                return true;
-            }
-            
-            let hash = compute_hash(num);
-            
-            if hash.ends_with(&suffix) {
-                let current = found_count_clone.fetch_add(1, Ordering::SeqCst);
-                
-                if current < max_results {
-                    debug!("Found hash: num={}, hash={}", num, hash);
-                    let _ = tx.send((num, hash));
+    hash.as_bytes()[hash.len() - zeros..].iter().all(|&b| b == b'0')
+}
+
+/// Number of `'0'` digits anywhere in the hex `hash`, trailing or not.
+///
+/// ```
+/// assert_eq!(rust_hash_finder::hash_zero_count("a0b00c000"), 6);
+/// ```
+pub fn hash_zero_count(hash: &str) -> usize {
+    hash.bytes().filter(|&b| b == b'0').count()
+}
+
+/// [`hash_zero_count`] of the hex form of `digest`, counted on the nibbles.
+pub fn digest_zero_count(digest: &[u8]) -> usize {
+    digest
+        .iter()
+        .map(|&b| usize::from(b >> 4 == 0) + usize::from(b & 0x0f == 0))
+        .sum()
+}
+
+/// Whether the last `len` digits of the hex `hash` read the same backwards, compared byte
+/// for byte; `hash.len()` checks the whole hash. False when `len` is 0 or longer than
+/// `hash`.
+///
+/// ```
+/// use rust_hash_finder::hash_ends_with_palindrome;
+///
+/// assert!(hash_ends_with_palindrome("12abba", 4));
+/// assert!(!hash_ends_with_palindrome("12abba", 5));
+/// ```
+pub fn hash_ends_with_palindrome(hash: &str, len: usize) -> bool {
+    if len == 0 || len > hash.len() {
+        return false;
+    }
+    let tail = &hash.as_bytes()[hash.len() - len..];
+    tail.iter().eq(tail.iter().rev())
+}
+
+/// Whether the hex `hash` ends with the decimal digits of `num` itself, e.g. a hash of 4163
+/// ending in `4163`.
+///
+/// ```
+/// use rust_hash_finder::hash_ends_with_number;
+///
+/// assert!(hash_ends_with_number("abc4163", 4163));
+/// assert!(!hash_ends_with_number("abc4163", 14163));
+/// ```
+pub fn hash_ends_with_number(hash: &str, num: u64) -> bool {
+    ends_with_decimal(hash.bytes().rev().map(|b| b.wrapping_sub(b'0')), num)
+}
+
+/// [`hash_ends_with_number`] of the hex form of `digest`, compared on the nibbles.
+pub fn digest_ends_with_number(digest: &[u8], num: u64) -> bool {
+    ends_with_decimal(digest.iter().rev().flat_map(|&b| [b & 0x0f, b >> 4]), num)
+}
+
+/// Whether `digits`, last first, start with the decimal digits of `num`, last first.
+fn ends_with_decimal(mut digits: impl Iterator<Item = u8>, mut num: u64) -> bool {
+    loop {
+        if digits.next() != Some((num % 10) as u8) {
+            return false;
+        }
+        num /= 10;
+        if num == 0 {
+            return true;
+        }
+    }
+}
+
+/// [`hash_ends_with_palindrome`] of the hex form of `digest`, compared on the nibbles.
+pub fn digest_ends_with_palindrome(digest: &[u8], len: usize) -> bool {
+    let digits = digest.len() * 2;
+    if len == 0 || len > digits {
+        return false;
+    }
+    let nibble = |i: usize| {
+        let byte = digest[i / 2];
+        if i.is_multiple_of(2) { byte >> 4 } else { byte & 0x0f }
+    };
+    let start = digits - len;
+    (0..len / 2).all(|i| nibble(start + i) == nibble(digits - 1 - i))
+}
+
+/// Expected number of hashes before one ends with `zeros` zeros, i.e. `16^zeros`, since
+/// each hex digit is zero with probability 1/16.
+///
+/// Holds for any algorithm with uniformly distributed output, and for leading zeros too.
+/// Saturates at `u64::MAX` from 16 zeros on.
+///
+/// ```
+/// assert_eq!(rust_hash_finder::estimate_attempts(3), 4096);
+/// ```
+pub fn estimate_attempts(zeros: usize) -> u64 {
+    u32::try_from(zeros)
+        .ok()
+        .and_then(|zeros| 16u64.checked_pow(zeros))
+        .unwrap_or(u64::MAX)
+}
+
+/// Expected time to find one match at `hashes_per_sec`, e.g. as measured by
+/// [`HashFinderConfig::measure_hashes_per_sec`].
+///
+/// Returns [`Duration::MAX`] when the throughput is not positive or the estimate does not
+/// fit in a `Duration`.
+pub fn estimate_duration(zeros: usize, hashes_per_sec: f64) -> Duration {
+    attempts_duration(estimate_attempts(zeros), hashes_per_sec)
+}
+
+/// Time to make `attempts` hashes at `hashes_per_sec`, as for [`estimate_duration`].
+pub(crate) fn attempts_duration(attempts: u64, hashes_per_sec: f64) -> Duration {
+    if hashes_per_sec <= 0.0 {
+        return Duration::MAX;
+    }
+    Duration::try_from_secs_f64(attempts as f64 / hashes_per_sec).unwrap_or(Duration::MAX)
+}
+
+/// Number of trailing zero digits in the hex form of `digest`, counted on the bytes.
+///
+/// Works for digests of any length; an odd count means the low nibble of the last
+/// nonzero byte is zero.
+///
+/// ```
+/// use rust_hash_finder::digest_trailing_zero_nibbles;
+///
+/// assert_eq!(digest_trailing_zero_nibbles(&[0xab, 0x30, 0x00]), 3);
+/// assert_eq!(digest_trailing_zero_nibbles(&[0x00, 0x00]), 4);
+/// assert_eq!(digest_trailing_zero_nibbles(&[]), 0);
+/// ```
+pub fn digest_trailing_zero_nibbles(digest: &[u8]) -> usize {
+    let zero_bytes = digest.iter().rev().take_while(|&&b| b == 0).count();
+    let rest = &digest[..digest.len() - zero_bytes];
+    zero_bytes * 2 + usize::from(rest.last().is_some_and(|&b| b & 0x0f == 0))
+}
+
+/// Whether the hex form of `digest` ends with `n` zeros, checked on the bytes.
+///
+/// `n / 2` whole bytes must be zero, plus the low nibble of the byte before them when `n`
+/// is odd. Any digest ends with 0 zeros; none ends with more zeros than it has digits.
+pub fn digest_ends_with_zero_nibbles(digest: &[u8], n: usize) -> bool {
+    if n > digest.len() * 2 {
+        return false;
+    }
+    let whole = digest.len() - n / 2;
+    digest[whole..].iter().all(|&b| b == 0)
+        && (n.is_multiple_of(2) || digest[whole - 1] & 0x0f == 0)
+}
+
+/// [`digest_ends_with_zero_nibbles`] as a difficulty check: like [`hash_matches_difficulty`],
+/// a difficulty of 0 never matches.
+pub fn digest_ends_with_zeros(digest: &[u8], zeros: usize) -> bool {
+    zeros != 0 && digest_ends_with_zero_nibbles(digest, zeros)
+}
+
+/// [`digest_ends_with_zeros`] for a raw SHA-256 digest, as returned by [`compute_hash_raw`].
+///
+/// Equivalent to `hash_matches_difficulty(&compute_hash(num), zeros) == Ok(true)` without
+/// building the hex string.
+pub fn hash_bytes_end_with_zeros(bytes: &[u8; 32], zeros: usize) -> bool {
+    digest_ends_with_zeros(bytes, zeros)
+}
+
+/// Whether the hex form of `digest` starts with `zeros` zeros, checked on the bytes.
+pub fn digest_starts_with_zeros(digest: &[u8], zeros: usize) -> bool {
+    if zeros == 0 || zeros > digest.len() * 2 {
+        return false;
+    }
+    let whole = zeros / 2;
+    digest[..whole].iter().all(|&b| b == 0)
+        && (zeros.is_multiple_of(2) || digest[whole] >> 4 == 0)
+}
+
+/// Number of leading zero bits in `digest`, Hashcash style; finer-grained than counting
+/// hex zeros, which each stand for 4 bits.
+///
+/// ```
+/// use rust_hash_finder::digest_leading_zero_bits;
+///
+/// assert_eq!(digest_leading_zero_bits(&[0x00, 0x7f]), 9);
+/// assert_eq!(digest_leading_zero_bits(&[0x00, 0x00]), 16);
+/// ```
+pub fn digest_leading_zero_bits(digest: &[u8]) -> u32 {
+    let mut bits = 0;
+    for &byte in digest {
+        bits += byte.leading_zeros();
+        if byte != 0 {
+            break;
+        }
+    }
+    bits
+}
+
+/// Whether `digest`, read as a 256-bit big-endian integer, is strictly below `target`, as
+/// in Bitcoin-style proof of work.
+///
+/// Compares the fixed-size arrays byte by byte, most significant first; no big integers
+/// are built.
+///
+/// ```
+/// use rust_hash_finder::digest_below_target;
+///
+/// let mut target = [0u8; 32];
+/// target[1] = 0x80;
+/// assert!(digest_below_target(&[0; 32], &target));
+/// assert!(!digest_below_target(&target, &target));
+/// ```
+pub fn digest_below_target(digest: &[u8; 32], target: &[u8; 32]) -> bool {
+    digest < target
+}
+
+/// Finds `max_results` numbers whose SHA-256 digest is numerically below `target`.
+///
+/// Fails when `target` is all zeros, since no digest can be below it.
+#[cfg(feature = "std")]
+pub fn find_hashes_below_target(
+    target: &[u8; 32],
+    max_results: usize,
+) -> Result<Vec<HashResult>, HashFinderError> {
+    HashFinderConfig::default()
+        .target(*target)
+        .max_results(max_results)
+        .run()
+}
+
+/// Like [`find_hashes`], but the search begins at `start` instead of 1; 0 is allowed and
+/// hashes `"0"`.
+///
+/// Useful for sharding: workers given disjoint starting points explore different numbers.
+#[cfg(feature = "std")]
+pub fn find_hashes_from(
+    zeros: usize,
+    max_results: usize,
+    start: u64,
+) -> Result<Vec<HashResult>, HashFinderError> {
+    HashFinderConfig::default()
+        .zeros(zeros)
+        .max_results(max_results)
+        .start_from(start)
+        .run()
+}
+
+/// Returns every number in `[start, end)` whose SHA-256 hash ends with `zeros` zeros,
+/// sorted ascending.
+///
+/// Unlike [`find_hashes`] this always terminates. Fails with
+/// [`HashFinderError::InvalidRange`] for an empty range and
+/// [`HashFinderError::InvalidZeroCount`] when `zeros` is 0 or above 64.
+#[cfg(feature = "std")]
+pub fn find_hashes_in_range(
+    start: u64,
+    end: u64,
+    zeros: usize,
+) -> Result<Vec<HashResult>, HashFinderError> {
+    let mut results = range_config(start, end, zeros)?.run()?;
+    results.sort_unstable_by_key(|r| r.number);
+    Ok(results)
+}
+
+/// Counts the numbers in `[start, end)` whose SHA-256 hash ends with `zeros` zeros, without
+/// collecting them; see [`HashFinderConfig::count_matches`].
+///
+/// Fails like [`find_hashes_in_range`].
+///
+/// ```
+/// assert_eq!(rust_hash_finder::count_matches_in_range(1, 20_000, 3).unwrap(), 4);
+/// ```
+#[cfg(feature = "std")]
+pub fn count_matches_in_range(start: u64, end: u64, zeros: usize) -> Result<u64, HashFinderError> {
+    range_config(start, end, zeros)?.count_matches()
+}
+
+/// The config for every SHA-256 hash in `[start, end)` ending with `zeros` zeros.
+#[cfg(feature = "std")]
+fn range_config(start: u64, end: u64, zeros: usize) -> Result<HashFinderConfig, HashFinderError> {
+    check_zeros(zeros)?;
+    Ok(HashFinderConfig::default()
+        .zeros(zeros)
+        .max_results(usize::MAX)
+        .start_from(start)
+        .end(end))
+}
+
+/// The `k` numbers in `[1, search_limit)` whose SHA-256 hashes have the most trailing
+/// zeros, most zeros first with ties going to the lower number; see
+/// [`HashFinderConfig::run_top_k_zeros`].
+///
+/// ```
+/// let top = rust_hash_finder::find_hashes_top_k_zeros(2, 20_000).unwrap();
+/// assert_eq!(top[0].number, 4163);
+/// assert_eq!(top[1].trailing_zeros, Some(3));
+/// ```
+#[cfg(feature = "std")]
+pub fn find_hashes_top_k_zeros(
+    k: usize,
+    search_limit: u64,
+) -> Result<Vec<HashResult>, HashFinderError> {
+    HashFinderConfig::default()
+        .start_from(1)
+        .end(search_limit)
+        .run_top_k_zeros(k)
+}
+
+/// Buckets in a [`trailing_zero_histogram`]: exactly 0 to 15 trailing zeros, then 16 or
+/// more.
+pub const HISTOGRAM_BUCKETS: usize = 17;
+
+/// Counts, for each number in `range`, how many SHA-256 hashes end with exactly 0, 1, 2, ...
+/// zeros; the last bucket holds 16 or more.
+///
+/// A uniform hash puts about `15/16` of the previous bucket's share in each bucket, and
+/// [`histogram_expected`] gives the exact expectation to compare against. Each thread
+/// fills its own buckets, merged at the end.
+///
+/// ```
+/// let buckets = rust_hash_finder::trailing_zero_histogram(1..=20_000);
+/// assert_eq!(buckets[..4], [18753, 1175, 68, 4]);
+/// ```
+#[cfg(feature = "std")]
+pub fn trailing_zero_histogram(range: RangeInclusive<u64>) -> [u64; HISTOGRAM_BUCKETS] {
+    range
+        .into_par_iter()
+        .fold(
+            || [0; HISTOGRAM_BUCKETS],
+            |mut buckets, num| {
+                let zeros = digest_trailing_zero_nibbles(&compute_hash_raw(num));
+                buckets[zeros.min(HISTOGRAM_BUCKETS - 1)] += 1;
+                buckets
+            },
+        )
+        .reduce(
+            || [0; HISTOGRAM_BUCKETS],
+            |mut buckets, other| {
+                for (bucket, count) in buckets.iter_mut().zip(other) {
+                    *bucket += count;
                 }
-                
-                if current + 1 >= max_results {
-                    info!("Reached target of {} results", max_results);
-                    return true;
+                buckets
+            },
+        )
+}
+
+/// Expected count in bucket `zeros` of a [`trailing_zero_histogram`] over `total` uniform
+/// hashes: `total * 15/16 * (1/16)^zeros`, or `total * (1/16)^16` for the last bucket.
+///
+/// ```
+/// assert_eq!(rust_hash_finder::histogram_expected(4096, 1), 240.0);
+/// ```
+#[cfg(feature = "std")]
+pub fn histogram_expected(total: u64, zeros: usize) -> f64 {
+    let tail = total as f64 / 16f64.powi(zeros.min(HISTOGRAM_BUCKETS - 1) as i32);
+    if zeros < HISTOGRAM_BUCKETS - 1 { tail * 15.0 / 16.0 } else { tail }
+}
+
+/// Numbers [`find_hashes_multi`] checks per parallel round before routing the matches.
+#[cfg(feature = "std")]
+const MULTI_CHUNK: u64 = 1 << 20;
+
+/// Finds, in a single scan, the first `max_per_level` numbers whose SHA-256 hash ends with
+/// `zeros` zeros for every `zeros` in `difficulties`.
+///
+/// Each candidate's trailing zeros are counted once and checked against every level, so a
+/// hash ending in 5 zeros also fills levels 1 to 4. Levels hold the smallest matching
+/// numbers in ascending order, the same on every run, and the scan lasts until the hardest
+/// level is full.
+///
+/// Fails with [`HashFinderError::InvalidZeroCount`] if a difficulty is 0 or above 64.
+///
+/// ```
+/// let levels = rust_hash_finder::find_hashes_multi(&[2, 3], 2).unwrap();
+/// assert_eq!(levels[&3][0].number, 4163);
+/// assert!(levels[&2].iter().all(|r| r.hash.ends_with("00")));
+/// ```
+#[cfg(feature = "std")]
+pub fn find_hashes_multi(
+    difficulties: &[usize],
+    max_per_level: usize,
+) -> Result<HashMap<usize, Vec<HashResult>>, HashFinderError> {
+    for &zeros in difficulties {
+        check_zeros(zeros)?;
+    }
+    let mut levels: HashMap<usize, Vec<HashResult>> =
+        difficulties.iter().map(|&zeros| (zeros, Vec::new())).collect();
+    let Some(&easiest) = difficulties.iter().min() else {
+        return Ok(levels);
+    };
+
+    let mut next = 1;
+    while next < u64::MAX && levels.values().any(|found| found.len() < max_per_level) {
+        let end = next.saturating_add(MULTI_CHUNK);
+        // Collected in order, so each level fills up with the smallest numbers.
+        let candidates: Vec<(u64, [u8; 32])> = (next..end)
+            .into_par_iter()
+            .filter_map(|num| {
+                let digest = compute_hash_raw(num);
+                digest_ends_with_zero_nibbles(&digest, easiest).then_some((num, digest))
+            })
+            .collect();
+        for (num, digest) in candidates {
+            let zeros = digest_trailing_zero_nibbles(&digest);
+            for (&level, found) in &mut levels {
+                if zeros >= level && found.len() < max_per_level {
+                    found.push(HashResult::new(num, hex_encode(&digest)));
                 }
             }
-            
-            false
-        });
-    
-    drop(tx);
-    let results = consumer.join().unwrap();
-    info!("Search completed, found {} results", results.len());
-    results
+        }
+        next = end;
+    }
+    Ok(levels)
+}
+
+/// Finds `per_salt` numbers whose salted SHA-256 hash ends with `zeros` zeros for each salt,
+/// e.g. to mint proof-of-work stamps for several messages in one run.
+///
+/// Each salt gets its own search, so results are independent. Equal salts share one entry.
+/// See [`HashFinderConfig::run_per_salt`] for other criteria.
+///
+/// Fails when `zeros` is 0 or above 64, or when `per_salt` is 0.
+///
+/// ```
+/// let salts = [b"msg-a:".to_vec(), b"msg-b:".to_vec()];
+/// let found = rust_hash_finder::find_hashes_multi_salt(&salts, 2, 3).unwrap();
+/// assert_eq!(found[&salts[0]].len(), 3);
+/// assert_ne!(found[&salts[0]], found[&salts[1]]);
+/// ```
+#[cfg(feature = "std")]
+pub fn find_hashes_multi_salt(
+    salts: &[Vec<u8>],
+    zeros: usize,
+    per_salt: usize,
+) -> Result<HashMap<Vec<u8>, Vec<HashResult>>, HashFinderError> {
+    let found = zeros_config(zeros, per_salt)?.run_per_salt(salts)?;
+    Ok(salts.iter().cloned().zip(found).collect())
+}
+
+/// Leading-zero counterpart of [`hash_matches_difficulty`], as used by Bitcoin-style proof of
+/// work; false for a `zeros` of 0 or longer than `hash`.
+pub fn hash_starts_with_zeros(hash: &str, zeros: usize) -> bool {
+    if zeros == 0 || zeros > hash.len() {
+        return false;
+    }
+    hash.as_bytes()[..zeros].iter().all(|&b| b == b'0')
+}
+
+/// Finds `max_results` numbers whose SHA-256 hash ends with `zeros` zeros.
+///
+/// Equivalent to
+/// `find_hashes_where(|_, hash| hash_matches_difficulty(hash, zeros) == Ok(true), max_results)`,
+/// but checks the zeros on the raw digest instead of hex encoding every candidate, which
+/// makes it several times faster.
+///
+/// Fails when `zeros` is 0 or above 64, or when `max_results` is 0.
+///
+/// ```
+/// let results = rust_hash_finder::find_hashes(3, 2).unwrap();
+/// assert!(results.iter().all(|r| r.hash.ends_with("000")));
+/// assert!(rust_hash_finder::find_hashes(0, 2).is_err());
+/// ```
+#[cfg(feature = "std")]
+pub fn find_hashes(zeros: usize, max_results: usize) -> Result<Vec<HashResult>, HashFinderError> {
+    zeros_config(zeros, max_results)?.run()
+}
+
+/// Like [`find_hashes`], but also records every number it accepts in a shared set and
+/// skips any number already there, so no number can appear twice in the results.
+///
+/// [`find_hashes`] already visits each candidate once and claims result slots with a
+/// compare-and-swap, so this is a belt-and-braces check for callers that must rely on
+/// uniqueness. Only matches touch the set, so its lock is rarely contended.
+///
+/// ```
+/// use std::collections::HashSet;
+///
+/// let results = rust_hash_finder::find_hashes_deduplicated(1, 50).unwrap();
+/// let numbers: HashSet<u64> = results.iter().map(|r| r.number).collect();
+/// assert_eq!(numbers.len(), 50);
+/// ```
+#[cfg(feature = "std")]
+pub fn find_hashes_deduplicated(
+    zeros: usize,
+    max_results: usize,
+) -> Result<Vec<HashResult>, HashFinderError> {
+    let config = zeros_config(zeros, max_results)?;
+    let matcher = config.matcher();
+    let seen = Mutex::new(HashSet::new());
+    Ok(search::search((config.start..=u64::MAX).par_bridge(), max_results, |num| {
+        let result = matcher(num)?;
+        seen.lock().unwrap().insert(num).then_some(result)
+    }))
+}
+
+/// Hashes numbers with SHA-256 for `seconds` on every core, exactly as [`find_hashes`]
+/// would, and reports the throughput.
+///
+/// Use [`BenchmarkResult::expected_duration`] to see how long a difficulty would take;
+/// [`HashFinderConfig::measure_hashes_per_sec`] measures any other configuration.
+#[cfg(feature = "std")]
+pub fn benchmark(seconds: u64) -> Result<BenchmarkResult, HashFinderError> {
+    let progress = SearchProgress::new();
+    let started = std::time::Instant::now();
+    HashFinderConfig::new()
+        .zeros(64)
+        .max_results(usize::MAX)
+        .timeout(Duration::from_secs(seconds))
+        .progress(progress.clone())
+        .run()?;
+    Ok(BenchmarkResult::new(started.elapsed(), progress.hashes()))
+}
+
+/// Like [`find_hashes`], but always returns exactly `max_results` results or an error.
+///
+/// ```
+/// let results = rust_hash_finder::find_hashes_exact(2, 5).unwrap();
+/// assert_eq!(results.len(), 5);
+/// ```
+#[cfg(feature = "std")]
+pub fn find_hashes_exact(
+    zeros: usize,
+    max_results: usize,
+) -> Result<Vec<HashResult>, HashFinderError> {
+    zeros_config(zeros, max_results)?.run_exact()
+}
+
+/// The validated config for `max_results` SHA-256 hashes ending with `zeros` zeros.
+#[cfg(feature = "std")]
+fn zeros_config(zeros: usize, max_results: usize) -> Result<HashFinderConfig, HashFinderError> {
+    check_zeros(zeros)?;
+    Ok(HashFinderConfig::default()
+        .zeros(zeros)
+        .max_results(max_results)
+        .build()?)
+}
+
+/// Fails unless `zeros` fits a SHA-256 hex digest.
+#[cfg(feature = "std")]
+pub(crate) fn check_zeros(zeros: usize) -> Result<(), HashFinderError> {
+    let max = HashAlgorithm::Sha256.hex_len();
+    if zeros == 0 || zeros > max {
+        return Err(HashFinderError::InvalidZeroCount { zeros, max });
+    }
+    Ok(())
+}
+
+/// Finds `max_results` numbers whose SHA-256 digest starts with at least `bits` zero bits.
+///
+/// Fails when `bits` is 0 or above 256, or when `max_results` is 0.
+#[cfg(feature = "std")]
+pub fn find_hashes_bits(bits: u32, max_results: usize) -> Result<Vec<HashResult>, HashFinderError> {
+    HashFinderConfig::default()
+        .leading_zero_bits(bits)
+        .max_results(max_results)
+        .run()
+}
+
+/// Finds `max_results` numbers whose SHA-256 hash ends with the hex digits `suffix`, e.g.
+/// `"deadbeef"`.
+///
+/// Fails when `suffix` is empty, contains non-hex characters or is longer than a SHA-256
+/// hex digest.
+///
+/// ```
+/// let results = rust_hash_finder::find_hashes_with_suffix("ab", 2).unwrap();
+/// assert!(results.iter().all(|r| r.hash.ends_with("ab")));
+/// ```
+#[cfg(feature = "std")]
+pub fn find_hashes_with_suffix(
+    suffix: &str,
+    max_results: usize,
+) -> Result<Vec<HashResult>, HashFinderError> {
+    let suffix = suffix.parse::<HexPattern>().map_err(HashFinderError::InvalidPattern)?;
+    HashFinderConfig::default()
+        .suffix(suffix)
+        .max_results(max_results)
+        .run()
+}
+
+/// Finds `max_results` numbers whose SHA-256 hash contains the hex digits `needle`
+/// anywhere, e.g. `"c0ffee"`.
+///
+/// Fails like [`find_hashes_with_suffix`]. Use [`HashFinderConfig::contains`] together
+/// with a prefix or suffix to require all of them.
+///
+/// ```
+/// let results = rust_hash_finder::find_hashes_containing("abc", 2).unwrap();
+/// assert!(results.iter().all(|r| r.hash.contains("abc")));
+/// ```
+#[cfg(feature = "std")]
+pub fn find_hashes_containing(
+    needle: &str,
+    max_results: usize,
+) -> Result<Vec<HashResult>, HashFinderError> {
+    let needle = needle.parse::<HexPattern>().map_err(HashFinderError::InvalidPattern)?;
+    HashFinderConfig::default()
+        .contains(needle)
+        .max_results(max_results)
+        .run()
+}
+
+/// Finds `max_results` numbers whose SHA-256 hash ends with any of `suffixes`, in a single
+/// pass; each result records the suffix it hit.
+///
+/// Fails like [`find_hashes_with_suffix`], or when `suffixes` is empty.
+///
+/// ```
+/// let suffixes = ["000".to_string(), "fff".to_string()];
+/// let results = rust_hash_finder::find_hashes_with_any_suffix(&suffixes, 2).unwrap();
+/// assert!(results.iter().all(|r| r.hash.ends_with(r.suffix.as_deref().unwrap())));
+/// ```
+#[cfg(feature = "std")]
+pub fn find_hashes_with_any_suffix(
+    suffixes: &[String],
+    max_results: usize,
+) -> Result<Vec<HashResult>, HashFinderError> {
+    let suffixes = suffixes
+        .iter()
+        .map(|suffix| suffix.parse::<HexPattern>())
+        .collect::<Result<SuffixSet, _>>()
+        .map_err(HashFinderError::InvalidPattern)?;
+    HashFinderConfig::default()
+        .suffixes(suffixes)
+        .max_results(max_results)
+        .run()
+}
+
+/// Finds `max_results` numbers whose SHA-256 hash starts with the hex digits `prefix`,
+/// e.g. `"cafe"` for vanity hashes.
+///
+/// Fails like [`find_hashes_with_suffix`]. Use [`HashFinderConfig::prefix`] together with
+/// [`HashFinderConfig::suffix`] to require both.
+///
+/// ```
+/// let results = rust_hash_finder::find_hashes_with_prefix("00", 2).unwrap();
+/// assert!(results.iter().all(|r| r.hash.starts_with("00")));
+/// ```
+#[cfg(feature = "std")]
+pub fn find_hashes_with_prefix(
+    prefix: &str,
+    max_results: usize,
+) -> Result<Vec<HashResult>, HashFinderError> {
+    let prefix = prefix.parse::<HexPattern>().map_err(HashFinderError::InvalidPattern)?;
+    HashFinderConfig::default()
+        .prefix(prefix)
+        .max_results(max_results)
+        .run()
+}
+
+/// Like [`find_hashes`], but also returns how long the search took, how many candidates
+/// were hashed and the hash rate achieved.
+///
+/// ```
+/// let (results, stats) = rust_hash_finder::find_hashes_with_stats(2, 3).unwrap();
+/// assert_eq!(stats.results_found, results.len());
+/// assert!(stats.candidates_checked >= 3);
+/// ```
+#[cfg(feature = "std")]
+pub fn find_hashes_with_stats(
+    zeros: usize,
+    max_results: usize,
+) -> Result<(Vec<HashResult>, SearchStats), HashFinderError> {
+    HashFinderConfig::default()
+        .zeros(zeros)
+        .max_results(max_results)
+        .run_with_stats()
+}
+
+/// Like [`find_hashes`], but searches the explicit ranges
+/// `[n * chunk_size, (n + 1) * chunk_size)` in turn, each with Rayon's indexed `par_iter`
+/// rather than `par_bridge` over an unbounded iterator.
+///
+/// Larger chunks keep every thread busy for longer; smaller ones stop sooner once enough
+/// matches are found.
+///
+/// Fails like [`find_hashes`], or when `chunk_size` is 0.
+#[cfg(feature = "std")]
+pub fn find_hashes_parallel_chunks(
+    zeros: usize,
+    max_results: usize,
+    chunk_size: u64,
+) -> Result<Vec<HashResult>, HashFinderError> {
+    HashFinderConfig::default()
+        .zeros(zeros)
+        .max_results(max_results)
+        .chunk_size(chunk_size)
+        .run()
+}
+
+/// Like [`find_hashes`], but returns the `max_results` smallest matching numbers in
+/// ascending order, so the output is the same on every run.
+#[cfg(feature = "std")]
+pub fn find_hashes_sorted(
+    zeros: usize,
+    max_results: usize,
+) -> Result<Vec<HashResult>, HashFinderError> {
+    HashFinderConfig::default()
+        .zeros(zeros)
+        .max_results(max_results)
+        .run_sorted()
+}
+
+/// Like [`find_hashes`], but stops early, returning what was found so far, once `token`
+/// is cancelled.
+#[cfg(feature = "std")]
+pub fn find_hashes_cancellable(
+    zeros: usize,
+    max_results: usize,
+    token: CancellationToken,
+) -> Result<Vec<HashResult>, HashFinderError> {
+    HashFinderConfig::default()
+        .zeros(zeros)
+        .max_results(max_results)
+        .cancellation(token)
+        .run()
+}
+
+/// Like [`find_hashes`], but gives up after `timeout` and returns whatever was found by
+/// then, which may be fewer than `max_results`.
+#[cfg(feature = "std")]
+pub fn find_hashes_with_timeout(
+    zeros: usize,
+    max_results: usize,
+    timeout: Duration,
+) -> Result<Vec<HashResult>, HashFinderError> {
+    HashFinderConfig::default()
+        .zeros(zeros)
+        .max_results(max_results)
+        .timeout(timeout)
+        .run()
+}
+
+/// Like [`find_hashes`], but resumes after `checkpoint` and includes the matches it holds.
+#[cfg(feature = "checkpoint")]
+pub fn find_hashes_resume(
+    zeros: usize,
+    max_results: usize,
+    checkpoint: Option<SearchCheckpoint>,
+) -> Result<Vec<HashResult>, HashFinderError> {
+    let mut checkpoint = checkpoint.unwrap_or_default();
+    HashFinderConfig::default()
+        .zeros(zeros)
+        .max_results(max_results)
+        .run_resumable(&mut checkpoint, None)
+}
+
+/// Like [`find_hashes`], but hashes with any RustCrypto [`Digest`] implementation.
+///
+/// The valid range for `zeros` follows from `D::output_size()`: fails with
+/// [`HashFinderError::InvalidZeroCount`] when `zeros` is 0 or longer than the hex digest,
+/// and with [`HashFinderError::InvalidMaxResults`] when `max_results` is 0.
+#[cfg(feature = "std")]
+#[instrument(skip_all, fields(zeros = zeros, max_results = max_results))]
+pub fn find_hashes_with_digest<D: Digest>(
+    zeros: usize,
+    max_results: usize,
+) -> Result<Vec<HashResult>, HashFinderError> {
+    let hex_len = <D as Digest>::output_size() * 2;
+    if zeros == 0 || zeros > hex_len {
+        return Err(HashFinderError::InvalidZeroCount { zeros, max: hex_len });
+    }
+    if max_results == 0 {
+        return Err(HashFinderError::InvalidMaxResults);
+    }
+
+    debug!("Searching for {}-char digests ending with {} zeros", hex_len, zeros);
+
+    Ok(search::search((1..=u64::MAX).par_bridge(), max_results, |num| {
+        let mut buf = [0; encoding::MAX_ENCODED_LEN];
+        let digest = D::digest(NonceEncoding::DecimalString.encode_into(num, &mut buf));
+        digest_ends_with_zeros(&digest, zeros).then(|| HashResult::new(num, hex_encode(&digest)))
+    }))
+}
+
+/// Candidates [`find_hashes_with_progress`] checks between calls of its callback.
+#[cfg(feature = "std")]
+pub const PROGRESS_EVERY: u64 = 1_000_000;
+
+/// Like [`find_hashes`], but calls `on_progress` after every [`PROGRESS_EVERY`] candidates
+/// checked, so a progress bar or meter can follow the search without a timer thread.
+///
+/// The callback runs on whichever search thread checked the last candidate of the batch,
+/// so calls can overlap and arrive slightly out of order; keep it cheap.
+///
+/// ```
+/// use rust_hash_finder::find_hashes_with_progress;
+///
+/// let results = find_hashes_with_progress(3, 2, |event| {
+///     eprintln!("{} checked, {} found", event.candidates_checked, event.results_found);
+/// });
+/// assert_eq!(results.unwrap().len(), 2);
+/// ```
+///
+/// Fails like [`find_hashes`].
+#[cfg(feature = "std")]
+pub fn find_hashes_with_progress<F>(
+    zeros: usize,
+    max_results: usize,
+    on_progress: F,
+) -> Result<Vec<HashResult>, HashFinderError>
+where
+    F: Fn(ProgressEvent) + Sync,
+{
+    find_hashes_with_progress_every(zeros, max_results, PROGRESS_EVERY, on_progress)
+}
+
+/// Like [`find_hashes_with_progress`], calling `on_progress` every `every` candidates.
+///
+/// Fails like [`find_hashes`], or with [`HashFinderError::InvalidProgressInterval`] when
+/// `every` is 0.
+#[cfg(feature = "std")]
+#[instrument(skip_all, fields(zeros = zeros, max_results = max_results, every = every))]
+pub fn find_hashes_with_progress_every<F>(
+    zeros: usize,
+    max_results: usize,
+    every: u64,
+    on_progress: F,
+) -> Result<Vec<HashResult>, HashFinderError>
+where
+    F: Fn(ProgressEvent) + Sync,
+{
+    if every == 0 {
+        return Err(HashFinderError::InvalidProgressInterval);
+    }
+    let config = zeros_config(zeros, max_results)?;
+
+    let started = Instant::now();
+    let checked = AtomicU64::new(0);
+    let found = AtomicUsize::new(0);
+    let matcher = config.matcher();
+    Ok(search::search((1..=u64::MAX).par_bridge(), max_results, |num| {
+        let result = matcher(num);
+        if result.is_some() {
+            found.fetch_add(1, Ordering::Relaxed);
+        }
+        let candidates_checked = checked.fetch_add(1, Ordering::Relaxed) + 1;
+        if candidates_checked.is_multiple_of(every) {
+            on_progress(ProgressEvent {
+                candidates_checked,
+                results_found: found.load(Ordering::Relaxed).min(max_results),
+                elapsed: started.elapsed(),
+            });
+        }
+        result
+    }))
+}
+
+/// Finds `max_results` numbers for which `predicate(number, hash)` holds, where `hash` is
+/// the number's SHA-256 as lowercase hex.
+///
+/// The general form of [`find_hashes`], which finds what
+/// `find_hashes_where(|_, hash| hash_matches_difficulty(hash, zeros) == Ok(true), max)` does
+/// without the hex encoding of every candidate that a string predicate needs.
+/// [`HashFinderConfig::run_where`] runs a predicate over any other config.
+///
+/// `predicate` is bounded by `Fn` itself rather than by a trait that [`HashPattern`] and
+/// [`Predicate`] could implement as well, since only an `Fn` bound lets the compiler infer
+/// a closure's argument types; with a trait, `|num, hash| ..` would need them spelled out.
+/// Patterns and predicates go through [`find_hashes_with_pattern`] and
+/// [`find_hashes_with_predicate`] instead, which are this function with their `matches`.
+///
+/// The predicate runs once per candidate on every search thread at once, so it should be
+/// cheap and must not rely on being called in order.
+///
+/// ```
+/// use rust_hash_finder::find_hashes_where;
+///
+/// let results = find_hashes_where(|num, hash| num.is_multiple_of(2) && hash.contains("beef"), 2);
+/// let results = results.unwrap();
+/// assert!(results.iter().all(|r| r.number.is_multiple_of(2) && r.hash.contains("beef")));
+/// ```
+///
+/// Fails with [`HashFinderError::InvalidMaxResults`] if `max_results` is 0.
+#[cfg(feature = "std")]
+#[instrument(skip_all, fields(max_results = max_results))]
+pub fn find_hashes_where<F>(
+    predicate: F,
+    max_results: usize,
+) -> Result<Vec<HashResult>, HashFinderError>
+where
+    F: Fn(u64, &str) -> bool + Sync,
+{
+    HashFinderConfig::default()
+        .max_results(max_results)
+        .run_where(predicate)
+}
+
+/// Like [`find_hashes_where`], taking one of the common conditions as a [`HashPattern`].
+///
+/// A separate entry point so that [`find_hashes_where`] keeps inferring closure argument
+/// types; see there.
+///
+/// ```
+/// use rust_hash_finder::{find_hashes_with_pattern, HashPattern};
+///
+/// let pattern = HashPattern::Contains("beef".to_string());
+/// let results = find_hashes_with_pattern(&pattern, 2).unwrap();
+/// assert!(results.iter().all(|r| pattern.matches(&r.hash)));
+/// ```
+///
+/// Fails with [`HashFinderError::InvalidMaxResults`] if `max_results` is 0.
+#[cfg(feature = "std")]
+pub fn find_hashes_with_pattern(
+    pattern: &HashPattern,
+    max_results: usize,
+) -> Result<Vec<HashResult>, HashFinderError> {
+    find_hashes_where(|_, hash| pattern.matches(hash), max_results)
+}
+
+/// Like [`find_hashes_where`], taking a composed [`Predicate`], for the same reason as
+/// [`find_hashes_with_pattern`].
+///
+/// ```
+/// use rust_hash_finder::{find_hashes_with_predicate, Predicate};
+///
+/// let predicate = Predicate::suffix("00").and(!Predicate::prefix("0"));
+/// let results = find_hashes_with_predicate(&predicate, 2).unwrap();
+/// assert!(results.iter().all(|r| r.hash.ends_with("00") && !r.hash.starts_with('0')));
+/// ```
+///
+/// Fails with [`HashFinderError::InvalidMaxResults`] if `max_results` is 0.
+#[cfg(feature = "std")]
+pub fn find_hashes_with_predicate(
+    predicate: &Predicate,
+    max_results: usize,
+) -> Result<Vec<HashResult>, HashFinderError> {
+    find_hashes_where(|_, hash| predicate.matches(hash), max_results)
+}
+
+/// Finds `max_results` numbers whose lowercase hex SHA-256 hash matches `regex`.
+///
+/// Every candidate is hex encoded before matching, so this is slower than the built-in
+/// zero, prefix and suffix searches; prefer those when they can express the condition.
+///
+/// ```
+/// let results = rust_hash_finder::find_hashes_matching("^ab|cd$", 2).unwrap();
+/// assert!(results.iter().all(|r| r.hash.starts_with("ab") || r.hash.ends_with("cd")));
+/// ```
+#[cfg(feature = "regex")]
+pub fn find_hashes_matching(
+    regex: &str,
+    max_results: usize,
+) -> Result<Vec<HashResult>, HashFinderError> {
+    HashFinderConfig::default()
+        .regex(HashRegex::new(regex).map_err(HashFinderError::InvalidPattern)?)
+        .max_results(max_results)
+        .run()
+}
+
+/// Finds numbers whose SHA-256 hash starts, rather than ends, with `zeros` zeros.
+#[cfg(feature = "std")]
+pub fn find_hashes_prefix(
+    zeros: usize,
+    max_results: usize,
+) -> Result<Vec<HashResult>, HashFinderError> {
+    HashFinderConfig::default()
+        .zeros(zeros)
+        .max_results(max_results)
+        .direction(MatchDirection::Leading)
+        .run()
+}
+
+/// Finds a single number whose SHA-256 hash ends with `zeros` zeros.
+///
+/// Stops at the first hit, so it is cheaper than `find_hashes(zeros, 1)`. Fails with
+/// [`HashFinderError::InvalidZeroCount`] when `zeros` is 0 or above 64.
+#[cfg(feature = "std")]
+pub fn find_first_hash(zeros: usize) -> Result<Option<HashResult>, HashFinderError> {
+    check_zeros(zeros)?;
+    HashFinderConfig::default().zeros(zeros).run_first()
+}
+
+#[cfg(feature = "std")]
+pub fn find_hashes_with_algo(
+    zeros: usize,
+    max_results: usize,
+    algo: HashAlgorithm,
+) -> Result<Vec<HashResult>, HashFinderError> {
+    HashFinderConfig::default()
+        .zeros(zeros)
+        .max_results(max_results)
+        .algorithm(algo)
+        .run()
+}
+
+/// Like [`find_hashes`], but matches on HMAC-SHA256 of each nonce under `key`.
+#[cfg(feature = "std")]
+pub fn find_hashes_with_hmac(
+    zeros: usize,
+    max_results: usize,
+    key: &[u8],
+) -> Result<Vec<HashResult>, HashFinderError> {
+    HashFinderConfig::default()
+        .zeros(zeros)
+        .max_results(max_results)
+        .hmac_key(HmacKey::new(key))
+        .run()
+}
+
+/// Like [`find_hashes`], but hashes `salt || nonce` instead of the bare nonce.
+#[cfg(feature = "std")]
+pub fn find_hashes_with_salt(
+    zeros: usize,
+    max_results: usize,
+    salt: &[u8],
+) -> Result<Vec<HashResult>, HashFinderError> {
+    HashFinderConfig::default()
+        .zeros(zeros)
+        .max_results(max_results)
+        .salt(salt)
+        .run()
+}
+
+/// Like [`find_hashes`], but hashes each nonce substituted into `template`.
+#[cfg(feature = "std")]
+pub fn find_hashes_with_template(
+    zeros: usize,
+    max_results: usize,
+    template: &InputTemplate,
+) -> Result<Vec<HashResult>, HashFinderError> {
+    HashFinderConfig::default()
+        .zeros(zeros)
+        .max_results(max_results)
+        .template(template.clone())
+        .run()
+}
+
+/// Like [`find_hashes`], but applies SHA-256 `iterations` times, re-hashing the raw digest
+/// of each round. `iterations` of 1 is the same as [`find_hashes`].
+#[cfg(feature = "std")]
+pub fn find_hashes_iterated(
+    zeros: usize,
+    max_results: usize,
+    iterations: u32,
+) -> Result<Vec<HashResult>, HashFinderError> {
+    HashFinderConfig::default()
+        .zeros(zeros)
+        .max_results(max_results)
+        .iterations(iterations)
+        .run()
+}
+
+/// Like [`find_hashes`], but hashes each number left-padded with zeros to `width` digits.
+///
+/// The search stops at the first number wider than `width`, so it can return fewer than
+/// `max_results` matches.
+#[cfg(feature = "std")]
+pub fn find_hashes_padded(
+    zeros: usize,
+    max_results: usize,
+    width: usize,
+) -> Result<Vec<HashResult>, HashFinderError> {
+    HashFinderConfig::default()
+        .zeros(zeros)
+        .max_results(max_results)
+        .pad_width(width)
+        .run()
+}
+
+/// Finds a chain of `links` SHA-256 matches ending with `zeros` zeros, where each link
+/// after the first hashes the previous link's hex hash followed by its nonce.
+///
+/// See [`HashFinderConfig::run_chained`].
+#[cfg(feature = "std")]
+pub fn find_hash_chain(zeros: usize, links: usize) -> Result<Vec<HashResult>, HashFinderError> {
+    HashFinderConfig::default()
+        .zeros(zeros)
+        .max_results(links)
+        .run_chained()
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "std"))]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_parse_algorithm() {
+        for &algo in HashAlgorithm::ALL {
+            assert_eq!(algo.name().parse::<HashAlgorithm>(), Ok(algo));
+            assert_eq!(algo.name().to_uppercase().parse::<HashAlgorithm>(), Ok(algo));
+        }
+        let err = "sha9".parse::<HashAlgorithm>().unwrap_err();
+        assert!(err.starts_with("unknown algorithm 'sha9' (available: sha256, "), "{}", err);
+    }
+
+    #[test]
+    #[cfg(not(feature = "blake3"))]
+    fn test_parse_algorithm_not_compiled_in() {
+        let err = "blake3".parse::<HashAlgorithm>().unwrap_err();
+        assert!(err.contains("rebuild with `--features blake3`"), "{}", err);
+        assert!(err.contains("available: sha256"), "{}", err);
+        assert!(!err.contains("unknown"), "{}", err);
+    }
+
     #[test]
     fn test_compute_hash_known_values() {
         let hash1 = compute_hash(1);
@@ -145,22 +1373,1156 @@ mod tests {
     }
 
     #[test]
+    fn test_compute_digest_matches_hex() {
+        for num in 0..1000 {
+            assert_eq!(hex_encode(&compute_digest(num)), compute_hash(num));
+        }
+        assert_eq!(hex_encode(&[0x00, 0x0f, 0xa0, 0xff]), "000fa0ff");
+        assert_eq!(hex_encode(&[]), "");
+    }
+
+    #[test]
+    #[allow(deprecated)]
     fn test_hash_ends_with_zeros() {
         assert!(hash_ends_with_zeros("abc000", 3));
         assert!(!hash_ends_with_zeros("abc001", 3));
         assert!(!hash_ends_with_zeros("", 1));
+        assert!(!hash_ends_with_zeros("abc000", 0));
+        assert!(hash_ends_with_zeros("xyz000", 3));
     }
 
     #[test]
-    fn test_find_hashes_count() {
-        let results = find_hashes(3, 2);
+    fn test_hash_matches_difficulty() {
+        assert_eq!(hash_matches_difficulty("abc000", 3), Ok(true));
+        assert_eq!(hash_matches_difficulty("abc001", 3), Ok(false));
+        assert_eq!(hash_matches_difficulty("ABC000", 3), Ok(true));
+
+        // zeros = 0 is an error rather than trivially true or false.
+        assert_eq!(hash_matches_difficulty("abc000", 0), Err(DifficultyError::ZeroDifficulty));
+        assert_eq!(hash_matches_difficulty("", 0), Err(DifficultyError::ZeroDifficulty));
+
+        // zeros = len checks the whole hash.
+        assert_eq!(hash_matches_difficulty("000", 3), Ok(true));
+        assert_eq!(hash_matches_difficulty("a00", 3), Ok(false));
+        assert_eq!(hash_matches_difficulty(&"0".repeat(64), 64), Ok(true));
+        assert_eq!(hash_matches_difficulty(&compute_hash(4163), 64), Ok(false));
+
+        // zeros > len can never match.
+        assert_eq!(
+            hash_matches_difficulty("000", 4),
+            Err(DifficultyError::TooManyZeros { zeros: 4, len: 3 })
+        );
+        assert_eq!(
+            hash_matches_difficulty("", 1),
+            Err(DifficultyError::TooManyZeros { zeros: 1, len: 0 })
+        );
+
+        // Non-hex input is rejected wherever it is, even if the tail is all zeros.
+        assert_eq!(
+            hash_matches_difficulty("xyz000", 3),
+            Err(DifficultyError::InvalidHex { index: 0, character: 'x' })
+        );
+        assert_eq!(
+            hash_matches_difficulty("abc00 ", 1),
+            Err(DifficultyError::InvalidHex { index: 5, character: ' ' })
+        );
+        assert_eq!(
+            hash_matches_difficulty("ab\u{e9}000", 3),
+            Err(DifficultyError::InvalidHex { index: 2, character: '\u{e9}' })
+        );
+
+        for num in 0..2000 {
+            let hash = compute_hash(num);
+            for zeros in 1..=4 {
+                #[allow(deprecated)]
+                let old = hash_ends_with_zeros(&hash, zeros);
+                assert_eq!(hash_matches_difficulty(&hash, zeros), Ok(old));
+            }
+        }
+        assert_eq!(
+            DifficultyError::TooManyZeros { zeros: 65, len: 64 }.to_string(),
+            "65 zeros do not fit in a 64-digit hash"
+        );
+    }
+
+    #[test]
+    fn test_compute_hash_sha256d_known_value() {
+        let hash = compute_hash_with_algo(1, HashAlgorithm::Sha256d);
+        assert_eq!(
+            hash,
+            "9c2e4d8fe97d881430de4e754b4205b9c27ce96715231cffc4337340cb110280"
+        );
+        assert_ne!(hash, compute_hash(1));
+    }
+
+    #[test]
+    fn test_compute_hash_bytes_and_str() {
+        assert_eq!(
+            compute_hash_str("hello"),
+            "2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824"
+        );
+        assert_eq!(compute_hash_bytes(b"hello"), compute_hash_str("hello"));
+        assert_eq!(compute_hash_str("4163"), compute_hash(4163));
+        assert_eq!(compute_hash_bytes(&[0xff, 0x00]).len(), 64);
+        assert_eq!(
+            compute_hash_bytes(&[]),
+            "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+        );
+    }
+
+    #[test]
+    fn test_compute_hash_double() {
+        assert_eq!(
+            compute_hash_double(12345),
+            "6860d0f5d9c4b0db633527188db9209c5bd0355bfeb530c900be4d87c859e0ef"
+        );
+        assert_eq!(
+            compute_hash_double(12345),
+            HashFinderConfig::new().iterations(2).hash(12345)
+        );
+    }
+
+    #[test]
+    fn test_find_hashes_sha256d() {
+        for result in find_hashes_with_algo(2, 2, HashAlgorithm::Sha256d).unwrap() {
+            assert_eq!(
+                result.hash,
+                compute_hash_with_algo(result.number, HashAlgorithm::Sha256d)
+            );
+            assert_eq!(hash_matches_difficulty(&result.hash, 2), Ok(true));
+        }
+    }
+
+    #[test]
+    fn test_find_hashes_with_timeout() {
+        let start = std::time::Instant::now();
+        let results = find_hashes_with_timeout(20, 1, Duration::from_millis(200)).unwrap();
+        assert!(results.is_empty());
+        assert!(start.elapsed() < Duration::from_secs(10));
+
+        let start = std::time::Instant::now();
+        assert_eq!(find_hashes_with_timeout(2, 3, Duration::from_secs(600)).unwrap().len(), 3);
+        assert!(start.elapsed() < Duration::from_secs(60));
+    }
+
+    #[test]
+    fn test_find_hashes_with_progress() {
+        let events = std::sync::Mutex::new(Vec::new());
+        let results =
+            find_hashes_with_progress_every(3, 2, 1000, |event| events.lock().unwrap().push(event));
+        let results = results.unwrap();
         assert_eq!(results.len(), 2);
+        assert!(results.iter().all(|r| hash_matches_difficulty(&r.hash, 3) == Ok(true)));
+
+        let mut events = events.into_inner().unwrap();
+        // The second match is 11848, so thousands of candidates are checked first.
+        assert!(events.len() >= 4, "{}", events.len());
+        events.sort_unstable_by_key(|event| event.candidates_checked);
+        for (i, event) in events.iter().enumerate() {
+            assert_eq!(event.candidates_checked, 1000 * (i as u64 + 1));
+            assert!(event.results_found <= 2);
+        }
+
+        // Too few candidates for a single batch of the default size.
+        let called = AtomicUsize::new(0);
+        let results = find_hashes_with_progress(2, 1, |_| {
+            called.fetch_add(1, Ordering::Relaxed);
+        }).unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(called.into_inner(), 0);
+    }
+
+    #[test]
+    fn test_find_hashes_multi_salt() {
+        let salts = [b"msg-a:".to_vec(), b"msg-b:".to_vec()];
+        let found = find_hashes_multi_salt(&salts, 2, 3).unwrap();
+        assert_eq!(found.len(), 2);
+        for salt in &salts {
+            let salted = HashFinderConfig::new().salt(salt.clone());
+            assert_eq!(found[salt].len(), 3);
+            for result in &found[salt] {
+                assert_eq!(result.hash, salted.hash(result.number));
+                assert!(result.hash.ends_with("00"));
+            }
+        }
+        // A match for one salt is practically never a match for the other.
+        let other = HashFinderConfig::new().salt(salts[1].clone());
+        assert!(found[&salts[0]].iter().any(|r| !other.hash(r.number).ends_with("00")));
+        assert!(find_hashes_multi_salt(&[], 2, 3).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_find_hashes_multi() {
+        let levels = find_hashes_multi(&[4, 2, 3], 3).unwrap();
+        let numbers = |zeros: usize| -> Vec<u64> {
+            levels[&zeros].iter().map(|r| r.number).collect()
+        };
+        assert_eq!(numbers(2), [403, 932, 1270]);
+        assert_eq!(numbers(3), [4163, 11848, 12843]);
+        assert_eq!(numbers(4), [31214, 88183, 112370]);
+        for (&zeros, results) in &levels {
+            assert!(results.iter().all(|r| r.hash == compute_hash(r.number)));
+            assert!(results.iter().all(|r| hash_matches_difficulty(&r.hash, zeros) == Ok(true)));
+        }
+
+        assert!(find_hashes_multi(&[], 3).unwrap().is_empty());
+        assert!(find_hashes_multi(&[3], 0).unwrap()[&3].is_empty());
+    }
+
+    #[test]
+    fn test_trailing_zero_histogram() {
+        let buckets = trailing_zero_histogram(1..=20_000);
+        assert_eq!(buckets, [18753, 1175, 68, 4, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]);
+        assert_eq!(buckets.iter().sum::<u64>(), 20_000);
+        assert_eq!(buckets[3] as usize, find_hashes_in_range(1, 20_001, 3).unwrap().len());
+        assert_eq!(trailing_zero_histogram(4163..=4163)[3], 1);
+
+        let expected: f64 = (0..HISTOGRAM_BUCKETS).map(|z| histogram_expected(20_000, z)).sum();
+        assert!((expected - 20_000.0).abs() < 1e-6, "{}", expected);
+    }
+
+    #[test]
+    fn test_compute_hash_with_encoding() {
+        let cases = [
+            (
+                InputEncoding::DecimalString,
+                "95d4362bd3cd4315d0bbe38dfa5d7fb8f0aed5f1a31d98d510907279194e3000",
+            ),
+            (
+                InputEncoding::HexString,
+                "c0aa4a0be7ba28399b09a68835a21755f442e25f8e0971b1d1ea3a6c749f0385",
+            ),
+            (
+                InputEncoding::LittleEndianBytes,
+                "111beb7b9bc83e15acb451db76ae279cdb41c53a56d114fdf450d5eba5acf4ae",
+            ),
+            (
+                InputEncoding::BigEndianBytes,
+                "174405773bdfa08f50372aa3ff5719f5ed8845aaad7ae51f87943141ef279df2",
+            ),
+        ];
+        for (encoding, expected) in cases {
+            assert_eq!(compute_hash_with_encoding(4163, encoding), expected, "{}", encoding);
+        }
+        assert_eq!(compute_hash(4163), compute_hash_with_encoding(4163, InputEncoding::default()));
+        assert_eq!(
+            compute_hash_with_encoding(0, InputEncoding::BigEndianBytes),
+            "af5570f5a1810b7af78caf4bc70a660f0df51e42baf91d4de5b2328de0e83dfc"
+        );
+    }
+
+    #[test]
+    fn test_batch_compute_hashes() {
+        assert!(batch_compute_hashes(&[]).is_empty());
+        let nums: Vec<u64> = (0..5000).rev().chain([4163, 4163, u64::MAX]).collect();
+        let hashes = batch_compute_hashes(&nums);
+        assert_eq!(hashes.len(), nums.len());
+        for (&num, (hashed, hash)) in nums.iter().zip(&hashes) {
+            assert_eq!(*hashed, num);
+            assert_eq!(*hash, compute_hash(num));
+        }
+    }
+
+    #[test]
+    fn test_find_hashes_deduplicated_stress() {
+        for _ in 0..1000 {
+            let results = find_hashes_deduplicated(1, 50).unwrap();
+            assert_eq!(results.len(), 50);
+            let numbers: HashSet<u64> = results.iter().map(|r| r.number).collect();
+            assert_eq!(numbers.len(), 50);
+            assert!(results.iter().all(|r| r.hash.ends_with('0')));
+        }
+        assert!(find_hashes_deduplicated(0, 50).is_err());
+        assert!(find_hashes_deduplicated(1, 0).is_err());
+    }
+
+    #[test]
+    fn test_compute_hash_raw() {
+        let raw = compute_hash_raw(4163);
+        assert_eq!(hex_encode(&raw), compute_hash(4163));
+        assert_eq!(raw[31], 0);
+        assert_eq!(raw[30] & 0x0f, 0);
+        assert_eq!(compute_hash_raw(u64::MAX), compute_digest(u64::MAX));
+    }
+
+    #[test]
+    fn test_digest_zero_checks_match_hex_checks() {
+        for num in 0..5000 {
+            let digest = compute_hash_raw(num);
+            let hash = hex_encode(&digest);
+            for zeros in 0..=4 {
+                assert_eq!(
+                    digest_ends_with_zeros(&digest, zeros),
+                    hash_matches_difficulty(&hash, zeros) == Ok(true)
+                );
+                assert_eq!(
+                    digest_starts_with_zeros(&digest, zeros),
+                    hash_starts_with_zeros(&hash, zeros)
+                );
+            }
+        }
+        assert!(digest_ends_with_zeros(&[0, 0], 4));
+        assert!(!digest_ends_with_zeros(&[0, 0], 5));
+        assert!(digest_starts_with_zeros(&[0x0f], 1));
+        assert!(!digest_starts_with_zeros(&[0x0f], 2));
+    }
+
+    #[test]
+    fn test_digest_zero_nibbles_match_hex() {
+        for num in 0..5000u64 {
+            // Digests of 0 to 32 bytes, with up to 6 trailing nibbles cleared so that long
+            // and odd runs of zeros both come up.
+            let mut digest = compute_hash_raw(num)[..(num % 33) as usize].to_vec();
+            let len = digest.len();
+            for i in 0..((num / 33 % 7) as usize).min(len * 2) {
+                digest[len - 1 - i / 2] &= if i.is_multiple_of(2) { 0xf0 } else { 0x0f };
+            }
+            let hash = hex_encode(&digest);
+            let expected = hash.len() - hash.trim_end_matches('0').len();
+            assert_eq!(digest_trailing_zero_nibbles(&digest), expected, "{}", hash);
+            for n in 0..=hash.len() + 1 {
+                assert_eq!(
+                    digest_ends_with_zero_nibbles(&digest, n),
+                    hash.ends_with(&"0".repeat(n)),
+                    "{} {}",
+                    hash,
+                    n
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_hash_bytes_end_with_zeros() {
+        assert!(hash_bytes_end_with_zeros(&compute_hash_raw(4163), 3));
+        assert!(!hash_bytes_end_with_zeros(&compute_hash_raw(4163), 4));
+        for num in 0..2000 {
+            let digest = compute_hash_raw(num);
+            for zeros in [0, 1, 2, 3, 64, 65] {
+                assert_eq!(
+                    hash_bytes_end_with_zeros(&digest, zeros),
+                    hash_matches_difficulty(&compute_hash(num), zeros) == Ok(true)
+                );
+            }
+        }
+        assert!(hash_bytes_end_with_zeros(&[0; 32], 64));
+    }
+
+    #[test]
+    fn test_hex_decode_round_trip() {
+        let digest = compute_digest(4163);
+        assert_eq!(hex_decode(&hex_encode(&digest)).unwrap(), digest);
+        assert_eq!(hex_decode("00FFa0").unwrap(), [0x00, 0xff, 0xa0]);
+        assert!(hex_decode("abc").is_err());
+        assert!(hex_decode("0g").is_err());
+    }
+
+    #[test]
+    fn test_find_hashes_with_hmac() {
+        for result in find_hashes_with_hmac(2, 2, b"secret").unwrap() {
+            assert_eq!(result.hash, compute_hmac(result.number, b"secret"));
+            assert_eq!(hash_matches_difficulty(&result.hash, 2), Ok(true));
+        }
+    }
+
+    #[test]
+    fn test_compute_hmac() {
+        assert_eq!(
+            compute_hmac(4163, b"key"),
+            "5a428478ba092e8a13fb55bbd5d8243ab10d96a80b85583fc857e12208be3f3a"
+        );
+        assert_eq!(
+            compute_hmac(4163, b"key"),
+            HashFinderConfig::new().hmac_key(HmacKey::new(*b"key")).hash(4163)
+        );
+    }
+
+    #[test]
+    fn test_zero_count() {
+        for (hash, count) in [
+            ("", 0),
+            ("abc", 0),
+            ("0", 1),
+            ("0000", 4),
+            ("a0b0", 2),
+            ("00ab00", 4),
+            ("880c2fc00805b3104264d490baf0090788b267e456c6d9f50aeca7e330b427b0", 12),
+        ] {
+            assert_eq!(hash_zero_count(hash), count, "{}", hash);
+            if hash.len().is_multiple_of(2) {
+                assert_eq!(digest_zero_count(&hex_decode(hash).unwrap()), count, "{}", hash);
+            }
+        }
+        assert_eq!(hash_zero_count(&compute_hash(4051)), 12);
+        assert_eq!(digest_zero_count(&compute_hash_raw(4051)), 12);
+    }
+
+    #[test]
+    fn test_benchmark() {
+        let result = benchmark(1).unwrap();
+        assert!(result.elapsed >= Duration::from_secs(1));
+        assert!(result.hashes > 0);
+        let rate = result.hashes as f64 / result.elapsed.as_secs_f64();
+        assert!((result.hashes_per_sec - rate).abs() < 1e-6 * rate);
+        assert!(result.expected_duration(2) < result.expected_duration(3));
+        assert!(result.to_string().contains(&format!("Hashes computed: {}\n", result.hashes)));
+
+        let idle = BenchmarkResult::new(Duration::ZERO, 0);
+        assert_eq!(idle.hashes_per_sec, 0.0);
+        assert_eq!(idle.expected_duration(1), Duration::MAX);
+    }
+
+    #[test]
+    fn test_ends_with_number() {
+        for (hash, num, expected) in [
+            ("", 0, false),
+            ("0", 0, true),
+            ("a0", 0, true),
+            ("a0", 10, false),
+            ("10", 10, true),
+            ("4163", 4163, true),
+            ("f4163", 4163, true),
+            ("4163", 14163, false),
+            ("41630", 4163, false),
+            ("a163", 4163, false),
+            ("ffffffffffffffff18446744073709551615", u64::MAX, true),
+            ("ffffffffffffffff18446744073709551614", u64::MAX, false),
+        ] {
+            assert_eq!(hash_ends_with_number(hash, num), expected, "{} {}", hash, num);
+            if hash.len().is_multiple_of(2) {
+                let digest = hex_decode(hash).unwrap();
+                assert_eq!(digest_ends_with_number(&digest, num), expected, "{} {}", hash, num);
+            }
+        }
+    }
+
+    #[test]
+    fn test_palindrome() {
+        for (hash, len, expected) in [
+            ("", 0, false),
+            ("a", 0, false),
+            ("a", 1, true),
+            ("a", 2, false),
+            ("ab", 2, false),
+            ("aa", 2, true),
+            ("12abba", 4, true),
+            ("12abba", 5, false),
+            ("12abba", 6, false),
+            ("2abcba", 5, true),
+            ("2abcba", 6, false),
+            ("abcdedcba0", 9, false),
+            ("0abcdedcba", 9, true),
+            ("0abcdedcba", 10, false),
+            ("a0b1c2d33d2c1b0a", 16, true),
+            ("a0b1c2d34d2c1b0a", 16, false),
+        ] {
+            assert_eq!(hash_ends_with_palindrome(hash, len), expected, "{} {}", hash, len);
+            if hash.len().is_multiple_of(2) {
+                let digest = hex_decode(hash).unwrap();
+                assert_eq!(digest_ends_with_palindrome(&digest, len), expected, "{} {}", hash, len);
+            }
+        }
+
+        // A whole palindrome need not end with a shorter one.
+        let whole = "0123456789abcdeffedcba9876543210";
+        assert!(hash_ends_with_palindrome(whole, whole.len()));
+        assert!(digest_ends_with_palindrome(&hex_decode(whole).unwrap(), whole.len()));
+        assert!(!hash_ends_with_palindrome(whole, whole.len() - 2));
+    }
+
+    #[test]
+    fn test_find_hashes_exact() {
+        for (zeros, max_results) in [(1, 1), (1, 100), (2, 17), (3, 4)] {
+            let results = find_hashes_exact(zeros, max_results).unwrap();
+            assert_eq!(results.len(), max_results);
+            assert!(results.iter().all(|r| verify_hash(r.number, &r.hash)));
+            assert!(results.iter().all(|r| hash_matches_difficulty(&r.hash, zeros) == Ok(true)));
+        }
+        assert!(matches!(
+            find_hashes_exact(65, 1),
+            Err(HashFinderError::InvalidZeroCount { zeros: 65, max: 64 })
+        ));
+        assert!(matches!(find_hashes_exact(2, 0), Err(HashFinderError::InvalidMaxResults)));
+    }
+
+    #[test]
+    fn test_find_hashes_cancellable() {
+        let token = CancellationToken::new();
+        let canceller = token.clone();
+        let handle = std::thread::spawn(move || {
+            std::thread::sleep(Duration::from_millis(200));
+            canceller.cancel();
+        });
+
+        let start = std::time::Instant::now();
+        assert!(find_hashes_cancellable(20, 1, token.clone()).unwrap().is_empty());
+        assert!(start.elapsed() < Duration::from_secs(10));
+        assert!(token.is_cancelled());
+        handle.join().unwrap();
+
+        assert_eq!(find_hashes_cancellable(2, 3, CancellationToken::new()).unwrap().len(), 3);
+    }
+
+    #[test]
+    fn test_compute_hash_salted() {
+        assert_eq!(compute_hash_salted(4163, b""), compute_hash(4163));
+        assert_eq!(
+            compute_hash_salted(4163, b"hello"),
+            "900c77b56b4b37995fd0ae10c54d0529418d976e5e6393008a30be67c7ba4c40"
+        );
+        assert_ne!(compute_hash_salted(4163, b"hello"), compute_hash_salted(4163, b"hellp"));
+        assert_eq!(
+            compute_hash_salted(4163, b"hello"),
+            HashFinderConfig::new().salt("hello").hash(4163)
+        );
+    }
+
+    #[test]
+    fn test_find_hashes_with_salt() {
+        let config = HashFinderConfig::new().salt("hello");
+        for result in find_hashes_with_salt(2, 2, b"hello").unwrap() {
+            assert_eq!(result.hash, config.hash(result.number));
+            assert_ne!(result.hash, compute_hash(result.number));
+            assert_eq!(hash_matches_difficulty(&result.hash, 2), Ok(true));
+        }
+    }
+
+    #[test]
+    fn test_compute_hash_padded() {
+        let padded = compute_hash_padded(123, 7).unwrap();
+        assert_ne!(padded, compute_hash(123));
+        assert_eq!(
+            padded,
+            "f64e6b9f89106658822b83a8d3300260db1bcbee872f1af306581ff5599657e4"
+        );
+        assert_eq!(compute_hash_padded(123, 3).unwrap(), compute_hash(123));
+        assert_eq!(
+            compute_hash_padded(1234, 3),
+            Err(ConfigError::NonceExceedsPadWidth { num: 1234, width: 3 })
+        );
+        assert_eq!(
+            compute_hash_padded(1, 0),
+            Err(ConfigError::PadWidthOutOfRange { width: 0 })
+        );
+    }
+
+    #[test]
+    fn test_find_hashes_padded() {
+        let results = find_hashes_padded(2, 10, 3).unwrap();
+        let mut numbers: Vec<u64> = results.iter().map(|r| r.number).collect();
+        numbers.sort_unstable();
+        assert_eq!(numbers, [403, 932]);
+        for result in &results {
+            assert_eq!(result.hash, compute_hash_padded(result.number, 3).unwrap());
+        }
+    }
+
+    #[test]
+    fn test_find_hashes_iterated() {
+        for result in find_hashes_iterated(3, 2, 1).unwrap() {
+            assert_eq!(result.hash, compute_hash(result.number));
+        }
+
+        let config = HashFinderConfig::new().iterations(1000);
+        assert_eq!(
+            config.hash(1),
+            "4a596e9ceaf492bfa47bbb8d5f166e13cebcad2dab961d22e34db37d3026193d"
+        );
+        for result in find_hashes_iterated(2, 2, 5).unwrap() {
+            assert_eq!(result.hash, config.clone().iterations(5).hash(result.number));
+            assert_eq!(hash_matches_difficulty(&result.hash, 2), Ok(true));
+        }
+    }
+
+    #[test]
+    fn test_estimates() {
+        assert_eq!(estimate_attempts(0), 1);
+        assert_eq!(estimate_attempts(1), 16);
+        assert_eq!(estimate_attempts(7), 268_435_456);
+        assert_eq!(estimate_attempts(15), 1 << 60);
+        assert_eq!(estimate_attempts(16), u64::MAX);
+        assert_eq!(estimate_attempts(usize::MAX), u64::MAX);
+
+        assert_eq!(estimate_duration(4, 65536.0), Duration::from_secs(1));
+        assert_eq!(estimate_duration(2, 512.0), Duration::from_millis(500));
+        assert_eq!(estimate_duration(4, 0.0), Duration::MAX);
+        assert_eq!(estimate_duration(64, 1.0), Duration::MAX);
+    }
+
+    #[test]
+    fn test_find_hashes_sorted() {
+        let first = find_hashes_sorted(3, 5).unwrap();
+        let numbers: Vec<u64> = first.iter().map(|r| r.number).collect();
+        assert!(numbers.windows(2).all(|w| w[0] < w[1]));
+        assert_eq!(numbers, [4163, 11848, 12843, 13467, 20215]);
+        for _ in 0..3 {
+            assert_eq!(find_hashes_sorted(3, 5).unwrap(), first);
+        }
+        assert!(verify_results(&first, 3));
+    }
+
+    #[test]
+    fn test_find_hash_chain() {
+        let chain = find_hash_chain(2, 4).unwrap();
+        assert_eq!(chain.len(), 4);
+        assert_eq!(chain[0].hash, compute_hash(chain[0].number));
+        for pair in chain.windows(2) {
+            let input = format!("{}{}", pair[0].hash, pair[1].number);
+            assert_eq!(pair[1].hash, HashAlgorithm::Sha256.hash_hex(input.as_bytes()));
+        }
+        assert!(chain.iter().all(|link| hash_matches_difficulty(&link.hash, 2) == Ok(true)));
+    }
+
+    #[test]
+    fn test_compute_hash_sha512_known_value() {
+        let hash = compute_hash_with_algo(1, HashAlgorithm::Sha512);
+        assert_eq!(
+            hash,
+            "4dff4ea340f0a823f15d3f4f01ab62eae0e5da579ccb851f8db9dfe84c58b2b3\
+             7b89903a740e1ee172da793a6e79d560e5f7f9bd058a12a280433ed6fa46510a"
+        );
+        assert_eq!(compute_hash(1), compute_hash_with_algo(1, HashAlgorithm::Sha256));
+    }
+
+    #[test]
+    fn test_hash_ends_with_zeros_sha512_length() {
+        let hash = compute_hash_with_algo(155, HashAlgorithm::Sha512);
+        assert_eq!(hash.len(), HashAlgorithm::Sha512.hex_len());
+        assert_eq!(hash_matches_difficulty(&hash, 2), Ok(true));
+        assert!(hash_matches_difficulty(&hash, 129).is_err());
+    }
+
+    #[test]
+    fn test_find_hashes_sha512() {
+        let results = find_hashes_with_algo(2, 1, HashAlgorithm::Sha512).unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].hash.len(), 128);
+        assert!(results[0].hash.ends_with("00"));
+    }
+
+    #[test]
+    fn test_compute_hash_blake2b_known_value() {
+        assert_eq!(
+            compute_hash_with_algo(1, HashAlgorithm::Blake2b),
+            "1ced8f5be2db23a6513eba4d819c73806424748a7bc6fa0d792cc1c7d1775a97\
+             78e894aa91413f6eb79ad5ae2f871eafcc78797e4c82af6d1cbfb1a294a10d10"
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "sha3")]
+    fn test_compute_hash_sha3_and_keccak_known_values() {
+        let sha3 = compute_hash_with_algo(1, HashAlgorithm::Sha3_256);
+        let keccak = compute_hash_with_algo(1, HashAlgorithm::Keccak256);
+        assert_eq!(
+            sha3,
+            "67b176705b46206614219f47a05aee7ae6a3edbe850bbbe214c536b989aea4d2"
+        );
+        assert_eq!(
+            keccak,
+            "c89efdaa54c0f20c7adf612882df0950f5a951637e0307cdcb4c672f298b8bc6"
+        );
+        assert_ne!(sha3, keccak);
+    }
+
+    #[test]
+    fn test_compute_hash_ripemd160_known_value() {
+        let hash = compute_hash_with_algo(1, HashAlgorithm::Ripemd160);
+        assert_eq!(hash, "c47907abd2a80492ca9388b05c0e382518ff3960");
+        assert_eq!(hash.len(), HashAlgorithm::Ripemd160.hex_len());
+    }
+
+    #[test]
+    fn test_compute_hash_xxhash_known_values() {
+        assert_eq!(compute_hash_with_algo(1, HashAlgorithm::XxHash64), "b7b41276360564d4");
+        assert_eq!(
+            compute_hash_with_algo(4163, HashAlgorithm::XxHash64),
+            "7a98b408756fe99e"
+        );
+        assert_eq!(HashAlgorithm::XxHash64.hex_len(), 16);
+    }
+
+    #[test]
+    fn test_compute_hash_crc32_known_value() {
+        assert_eq!(HashAlgorithm::Crc32.hash_hex(b"123"), "884863d2");
+        assert_eq!(compute_hash_with_algo(4163, HashAlgorithm::Crc32), "4d68cf2e");
+        assert_eq!(HashAlgorithm::Crc32.hex_len(), 8);
+    }
+
+    #[test]
+    fn test_find_hashes_crc32() {
+        let results = find_hashes_with_algo(1, 10, HashAlgorithm::Crc32).unwrap();
+        assert_eq!(results.len(), 10);
+        for result in &results {
+            assert_eq!(result.hash.len(), 8);
+            assert_eq!(result.hash, compute_hash_with_algo(result.number, HashAlgorithm::Crc32));
+            assert!(result.hash.ends_with('0'));
+        }
+    }
+
+    #[test]
+    fn test_find_hashes_blake2b() {
+        let results = find_hashes_with_algo(2, 2, HashAlgorithm::Blake2b).unwrap();
+        assert_eq!(results.len(), 2);
+        for HashResult { number: num, hash, .. } in &results {
+            assert_eq!(hash.len(), HashAlgorithm::Blake2b.hex_len());
+            assert_eq!(*hash, compute_hash_with_algo(*num, HashAlgorithm::Blake2b));
+            assert_eq!(hash_matches_difficulty(hash, 2), Ok(true));
+        }
+    }
+
+    #[cfg(feature = "blake3")]
+    #[test]
+    fn test_compute_hash_blake3_known_value() {
+        assert_eq!(
+            compute_hash_with_algo(1, HashAlgorithm::Blake3),
+            "d63bd9a826af91c1fea371965a64e11ee20f13e46b5f52c59901136605b3a487"
+        );
+    }
+
+    #[cfg(feature = "blake3")]
+    #[test]
+    fn test_compute_hash_blake3_wrapper() {
+        assert_eq!(
+            compute_hash_blake3(42),
+            compute_hash_with_algo(42, HashAlgorithm::Blake3)
+        );
+        assert_ne!(compute_hash_blake3(42), compute_hash(42));
+    }
+
+    #[cfg(feature = "blake3")]
+    #[test]
+    fn test_find_hashes_blake3() {
+        let results = find_hashes_with_algo(2, 2, HashAlgorithm::Blake3).unwrap();
+        assert_eq!(results.len(), 2);
+        for HashResult { number: num, hash, .. } in &results {
+            let expected = blake3::hash(num.to_string().as_bytes());
+            assert_eq!(*hash, expected.to_hex().to_string());
+            assert_eq!(hash_matches_difficulty(hash, 2), Ok(true));
+        }
+    }
+
+    #[test]
+    fn test_compute_hash_sha1_known_values() {
+        assert_eq!(
+            compute_hash_with_algo(1, HashAlgorithm::Sha1),
+            "356a192b7913b04c54574d18c28d46e6395428ab"
+        );
+        let hash = compute_hash_with_algo(1018, HashAlgorithm::Sha1);
+        assert_eq!(hash, "cea8be18f8249fdbaaa535b000505661dd160000");
+        assert_eq!(hash_matches_difficulty(&hash, 4), Ok(true));
+        assert!(hash_matches_difficulty(&hash, 41).is_err());
+    }
+
+    #[test]
+    fn test_hash_starts_with_zeros() {
+        assert!(hash_starts_with_zeros("000abc", 3));
+        assert!(!hash_starts_with_zeros("100abc", 3));
+        assert!(!hash_starts_with_zeros("000", 4));
+        assert!(!hash_starts_with_zeros("000", 0));
+        assert!(hash_starts_with_zeros(&compute_hash(886), 3));
+    }
+
+    #[test]
+    fn test_digest_leading_zero_bits() {
+        assert_eq!(digest_leading_zero_bits(&[0x0f, 0xff]), 4);
+        assert_eq!(digest_leading_zero_bits(&[0x10, 0x00]), 3);
+        assert_eq!(digest_leading_zero_bits(&[0x00, 0xff]), 8);
+        assert_eq!(digest_leading_zero_bits(&[0x00, 0x7f]), 9);
+        assert_eq!(digest_leading_zero_bits(&[0x00, 0x0f]), 12);
+        assert_eq!(digest_leading_zero_bits(&[0x00, 0x00]), 16);
+        assert_eq!(digest_leading_zero_bits(&[0x80]), 0);
+        assert_eq!(digest_leading_zero_bits(&[]), 0);
+        // sha256("886") = 000f...
+        assert_eq!(digest_leading_zero_bits(&compute_hash_raw(886)), 12);
+    }
+
+    #[test]
+    fn test_find_hashes_bits() {
+        for (bits, first) in [(4, 39), (8, 286), (9, 286), (12, 886)] {
+            let results = find_hashes_bits(bits, 3).unwrap();
+            assert_eq!(results.len(), 3);
+            for result in &results {
+                assert_eq!(result.hash, compute_hash(result.number));
+                assert!(digest_leading_zero_bits(&compute_hash_raw(result.number)) >= bits);
+            }
+            let config = HashFinderConfig::new().leading_zero_bits(bits).max_results(1);
+            assert_eq!(config.run_sorted().unwrap()[0].number, first, "{} bits", bits);
+        }
+    }
+
+    #[test]
+    fn test_digest_below_target() {
+        let ones = [0xff; 32];
+        let zeros = [0x00; 32];
+        for num in 1..100 {
+            let digest = compute_hash_raw(num);
+            assert!(digest_below_target(&digest, &ones));
+            assert!(!digest_below_target(&digest, &zeros));
+        }
+        assert!(!digest_below_target(&ones, &ones));
+        assert!(!digest_below_target(&zeros, &zeros));
+
+        // Equal-length lowercase hex sorts like the numbers it encodes.
+        let target = hex_decode(&format!("1c{}", "a5".repeat(31))).unwrap();
+        let target: [u8; 32] = target.try_into().unwrap();
+        let target_hex = hex_encode(&target);
+        for num in 1..200 {
+            let below = compute_hash(num) < target_hex;
+            assert_eq!(digest_below_target(&compute_hash_raw(num), &target), below, "{}", num);
+        }
+
+        let config = HashFinderConfig::new().target(target).end(200).max_results(100);
+        let mut numbers: Vec<u64> = config.run().unwrap().iter().map(|r| r.number).collect();
+        numbers.sort_unstable();
+        assert_eq!(
+            numbers,
+            [9, 39, 49, 50, 51, 55, 65, 101, 105, 109, 122, 125, 135, 146, 149, 152, 156, 178]
+        );
+    }
+
+    #[test]
+    fn test_find_hashes_below_target() {
+        let mut target = [0xff; 32];
+        target[..2].fill(0);
+        let results = find_hashes_below_target(&target, 1).unwrap();
+        assert_eq!(results.len(), 1);
+        assert!(digest_below_target(&compute_hash_raw(results[0].number), &target));
+        assert!(results[0].hash.starts_with("0000"));
+
+        let config = HashFinderConfig::new().target(target).max_results(1);
+        assert_eq!(config.run_sorted().unwrap()[0].number, 88484);
+        assert_eq!(config.difficulty(), 4);
+        assert_eq!(config.expected_attempts(), 65536);
+
+        assert!(matches!(
+            find_hashes_below_target(&[0; 32], 1),
+            Err(HashFinderError::Config(ConfigError::ZeroTarget))
+        ));
+        assert_eq!(find_hashes_below_target(&[0xff; 32], 3).unwrap().len(), 3);
+    }
+
+    #[test]
+    fn test_find_hashes_where() {
+        let same_ends = |hash: &str| hash.as_bytes()[0] == hash.as_bytes()[63];
+        let results = find_hashes_where(|_, hash| same_ends(hash), 5).unwrap();
+        assert_eq!(results.len(), 5);
+        for result in &results {
+            assert_eq!(result.hash, compute_hash(result.number));
+            assert!(same_ends(&result.hash));
+        }
+
+        // The built-in predicate gives the same matches as find_hashes.
+        let results =
+            find_hashes_where(|_, hash| hash_matches_difficulty(hash, 3) == Ok(true), 2).unwrap();
+        assert_eq!(results.len(), 2);
+        assert!(verify_results(&results, 3));
+    }
+
+    #[test]
+    fn test_find_hashes_agrees_with_its_where_form() {
+        // find_hashes and find_hashes_where run these two searches over an unbounded range,
+        // where which matches come back first depends on the threads.
+        let config = HashFinderConfig::new().zeros(3).end(30_000).max_results(usize::MAX);
+        let numbers = |results: Vec<HashResult>| -> Vec<u64> {
+            let mut numbers: Vec<u64> = results.iter().map(|r| r.number).collect();
+            numbers.sort_unstable();
+            numbers
+        };
+        let by_digest = numbers(config.run().unwrap());
+        let trailing_zeros = |_, hash: &str| hash_matches_difficulty(hash, 3) == Ok(true);
+        let by_hex = numbers(config.run_where(trailing_zeros).unwrap());
+        assert_eq!(by_digest, [4163, 11848, 12843, 13467, 20215, 28892]);
+        assert_eq!(by_hex, by_digest);
+
+        let found = find_hashes(3, 4).unwrap();
+        let found_where = find_hashes_where(trailing_zeros, 4).unwrap();
+        assert_eq!(found.len(), found_where.len());
+        assert!(verify_results(&found, 3) && verify_results(&found_where, 3));
+    }
+
+    #[test]
+    fn test_find_hashes_where_nonce() {
+        let even = |num: u64, hash: &str| {
+            num.is_multiple_of(2) && hash_matches_difficulty(hash, 2) == Ok(true)
+        };
+        let results = find_hashes_where(even, 4).unwrap();
+        assert_eq!(results.len(), 4);
+        for result in &results {
+            assert!(result.number.is_multiple_of(2));
+            assert!(verify_results(std::slice::from_ref(result), 2));
+        }
+
+        // The smallest even numbers whose hashes end with 00.
+        let mut numbers: Vec<u64> = find_hashes_where(|num, hash| num <= 2012 && even(num, hash), 3)
+            .unwrap()
+            .iter()
+            .map(|r| r.number)
+            .collect();
+        numbers.sort_unstable();
+        assert_eq!(numbers, [932, 1270, 2012]);
+    }
+
+    #[test]
+    fn test_find_hashes_with_pattern() {
+        let pattern = HashPattern::TrailingZeros(3);
+        let results = find_hashes_with_pattern(&pattern, 2).unwrap();
+        assert_eq!(results.len(), 2);
+        assert!(verify_results(&results, 3));
+
+        let pattern = HashPattern::StartsWith("ab".to_string());
+        for result in find_hashes_with_pattern(&pattern, 3).unwrap() {
+            assert!(result.hash.starts_with("ab"));
+            assert!(verify_hash(result.number, &result.hash));
+        }
+    }
+
+    #[test]
+    fn test_find_hashes_with_predicate() {
+        let predicate = Predicate::suffix("00").or(Predicate::prefix("000"));
+        let results = find_hashes_with_predicate(&predicate, 4).unwrap();
+        assert_eq!(results.len(), 4);
+        for result in &results {
+            assert!(result.hash.ends_with("00") || result.hash.starts_with("000"));
+            assert!(verify_hash(result.number, &result.hash));
+        }
+    }
+
+    #[test]
+    fn test_find_hashes_where_rejects_zero_results() {
+        assert!(matches!(
+            find_hashes_where(|_, _| true, 0),
+            Err(HashFinderError::InvalidMaxResults)
+        ));
+    }
+
+    #[test]
+    #[cfg(feature = "regex")]
+    fn test_find_hashes_matching() {
+        // An anchored regex behaves exactly like -N 2.
+        let config = HashFinderConfig::new().zeros(2).max_results(1);
+        let regex = config.clone().regex(HashRegex::new("00$").unwrap());
+        for num in 1..2000 {
+            assert_eq!(regex.matches(&config.hash(num)), config.matches(&config.hash(num)));
+        }
+        assert_eq!(regex.clone().run_sorted().unwrap(), config.run_sorted().unwrap());
+
+        let results = find_hashes_matching("00$", 3).unwrap();
+        assert_eq!(results.len(), 3);
+        assert!(verify_results(&results, 2));
+
+        let err = find_hashes_matching("[0-", 1).unwrap_err();
+        assert!(matches!(err, HashFinderError::InvalidPattern(_)));
+        assert!(err.to_string().starts_with("invalid regex '[0-'"), "{}", err);
+        assert!(find_hashes_matching("0$", 0).is_err());
+        assert_eq!(
+            regex.suffix("0".parse().unwrap()).build(),
+            Err(ConfigError::RegexWithOtherCriteria)
+        );
+    }
+
+    #[test]
+    fn test_find_hashes_prefix() {
+        let results = find_hashes_prefix(3, 2).unwrap();
+        assert_eq!(results.len(), 2);
+        for HashResult { number: num, hash, .. } in &results {
+            assert_eq!(*hash, compute_hash(*num));
+            assert!(hash.starts_with("000"));
+        }
+    }
+
+    #[test]
+    fn test_find_hashes_count() {
+        let results = find_hashes(3, 2).unwrap();
+        assert_eq!(results.len(), 2);
+    }
+
+    #[test]
+    fn test_find_hashes_rejects_invalid_input() {
+        assert!(matches!(
+            find_hashes(0, 1),
+            Err(HashFinderError::InvalidZeroCount { zeros: 0, max: 64 })
+        ));
+        assert!(matches!(
+            find_hashes(65, 1),
+            Err(HashFinderError::InvalidZeroCount { zeros: 65, max: 64 })
+        ));
+        assert!(matches!(find_hashes(3, 0), Err(HashFinderError::InvalidMaxResults)));
+        assert_eq!(
+            find_hashes(0, 1).unwrap_err().to_string(),
+            "zeros must be between 1 and 64 (got 0)"
+        );
+    }
+
+    #[test]
+    fn test_compute_hash_md5_known_values() {
+        assert_eq!(
+            compute_hash_with_algo(1, HashAlgorithm::Md5),
+            "c4ca4238a0b923820dcc509a6f75849b"
+        );
+        let hash = compute_hash_with_algo(1164, HashAlgorithm::Md5);
+        assert_eq!(hash, "00e26af6ac3b1c1c49d7c3d79c60d000");
+        assert_eq!(hash_matches_difficulty(&hash, 3), Ok(true));
+        assert!(hash_matches_difficulty(&hash, 33).is_err());
+    }
+
+    #[test]
+    fn test_find_hashes_md5() {
+        let results = find_hashes_with_algo(2, 3, HashAlgorithm::Md5).unwrap();
+        assert_eq!(results.len(), 3);
+        for HashResult { number: num, hash, .. } in &results {
+            assert_eq!(hash.len(), 32);
+            assert_eq!(*hash, compute_hash_with_algo(*num, HashAlgorithm::Md5));
+            assert_eq!(hash_matches_difficulty(hash, 2), Ok(true));
+        }
+    }
+
+    #[test]
+    #[ignore = "timing comparison; run with --ignored --release"]
+    fn test_md5_search_faster_than_sha256() {
+        let time = |algo| {
+            let start = std::time::Instant::now();
+            for _ in 0..20 {
+                assert_eq!(find_hashes_with_algo(2, 20, algo).unwrap().len(), 20);
+            }
+            start.elapsed()
+        };
+        let sha256 = time(HashAlgorithm::Sha256);
+        let md5 = time(HashAlgorithm::Md5);
+        assert!(md5 < sha256, "md5 took {:?}, sha256 took {:?}", md5, sha256);
+    }
+
+    #[test]
+    fn test_find_hashes_with_suffix() {
+        let results = find_hashes_with_suffix("ab", 1).unwrap();
+        assert_eq!(results.len(), 1);
+        assert!(results[0].hash.ends_with("ab"));
+        assert!(verify_hash(results[0].number, &results[0].hash));
+
+        let upper = find_hashes_with_suffix("AB", 1).unwrap();
+        assert!(upper[0].hash.ends_with("ab"));
+
+        assert!(find_hashes_with_suffix("", 1).is_err());
+        assert!(find_hashes_with_suffix("xyz", 1).is_err());
+        assert!(find_hashes_with_suffix(&"a".repeat(65), 1).is_err());
+        assert!(find_hashes_with_suffix("ab", 0).is_err());
+    }
+
+    #[test]
+    fn test_find_hashes_containing() {
+        let results = find_hashes_containing("abc", 2).unwrap();
+        assert_eq!(results.len(), 2);
+        for result in &results {
+            assert!(result.hash.contains("abc"));
+            assert!(verify_hash(result.number, &result.hash));
+        }
+
+        assert!(find_hashes_containing("xyz", 1).is_err());
+        assert!(find_hashes_containing("", 1).is_err());
+        assert!(find_hashes_containing(&"a".repeat(65), 1).is_err());
+    }
+
+    #[test]
+    fn test_find_hashes_with_any_suffix() {
+        let suffixes = ["000".to_string(), "AAA".to_string(), "fff".to_string()];
+        let results = find_hashes_with_any_suffix(&suffixes, 5).unwrap();
+        assert_eq!(results.len(), 5);
+        for result in &results {
+            let suffix = result.suffix.as_deref().unwrap();
+            assert!(["000", "aaa", "fff"].contains(&suffix));
+            assert!(result.hash.ends_with(suffix));
+            assert!(verify_hash(result.number, &result.hash));
+        }
+
+        assert!(find_hashes_with_any_suffix(&[], 1).is_err());
+        assert!(find_hashes_with_any_suffix(&["00".to_string(), "xy".to_string()], 1).is_err());
+    }
+
+    #[test]
+    fn test_find_hashes_parallel_chunks() {
+        // Single-number chunks are searched strictly in order.
+        let numbers: Vec<u64> = find_hashes_parallel_chunks(3, 3, 1).unwrap()
+            .iter()
+            .map(|r| r.number)
+            .collect();
+        assert_eq!(numbers, [4163, 11848, 12843]);
+
+        let results = find_hashes_parallel_chunks(3, 5, 1 << 20).unwrap();
+        assert_eq!(results.len(), 5);
+        for result in &results {
+            assert_eq!(result.hash, compute_hash(result.number));
+            assert!(result.number < 1 << 20);
+            assert_eq!(hash_matches_difficulty(&result.hash, 3), Ok(true));
+        }
+    }
+
+    #[test]
+    fn test_find_hashes_with_prefix() {
+        let results = find_hashes_with_prefix("Ab", 1).unwrap();
+        assert_eq!(results.len(), 1);
+        assert!(results[0].hash.starts_with("ab"));
+        assert!(verify_hash(results[0].number, &results[0].hash));
+
+        assert!(find_hashes_with_prefix("", 1).is_err());
+        assert!(find_hashes_with_prefix("-1", 1).is_err());
+        assert!(find_hashes_with_prefix(&"a".repeat(65), 1).is_err());
+    }
+
+    #[test]
+    fn test_find_hashes_with_stats() {
+        let (results, stats) = find_hashes_with_stats(3, 2).unwrap();
+        assert_eq!(results.len(), 2);
+        assert_eq!(stats.results_found, 2);
+        for result in &results {
+            assert_eq!(result.hash, compute_hash(result.number));
+            assert!(result.attempts.unwrap() <= stats.candidates_checked);
+        }
+        assert!(stats.candidates_checked >= 2);
+        assert!(stats.hash_rate > 0.0);
+    }
+
+    #[test]
+    fn test_find_hashes_with_digest() {
+        let results = find_hashes_with_digest::<sha2::Sha256>(2, 2).unwrap();
+        assert_eq!(results.len(), 2);
+        for HashResult { number: num, hash, .. } in &results {
+            assert_eq!(*hash, compute_hash(*num));
+            assert_eq!(hash_matches_difficulty(hash, 2), Ok(true));
+        }
+
+        let results = find_hashes_with_digest::<sha2::Sha512>(2, 2).unwrap();
+        assert_eq!(results.len(), 2);
+        for HashResult { number: num, hash, .. } in &results {
+            assert_eq!(*hash, compute_hash_with_algo(*num, HashAlgorithm::Sha512));
+            assert_eq!(hash_matches_difficulty(hash, 2), Ok(true));
+        }
+    }
+
+    #[test]
+    fn test_find_hashes_with_digest_rejects_long_difficulty() {
+        assert!(matches!(
+            find_hashes_with_digest::<sha2::Sha256>(65, 1),
+            Err(HashFinderError::InvalidZeroCount { zeros: 65, max: 64 })
+        ));
+        assert!(matches!(
+            find_hashes_with_digest::<sha2::Sha256>(2, 0),
+            Err(HashFinderError::InvalidMaxResults)
+        ));
+    }
+
+    #[test]
+    fn test_find_first_hash() {
+        let HashResult { number: num, hash, .. } = find_first_hash(3).unwrap().unwrap();
+        assert_eq!(hash, compute_hash(num));
+        assert_eq!(hash_matches_difficulty(&hash, 3), Ok(true));
+        assert!(matches!(find_first_hash(0), Err(HashFinderError::InvalidZeroCount { .. })));
+        assert!(matches!(find_first_hash(65), Err(HashFinderError::InvalidZeroCount { .. })));
     }
 
     #[test]
     fn test_find_hashes_validity() {
-        let results = find_hashes(3, 1);
+        let results = find_hashes(3, 1).unwrap();
         assert_eq!(results.len(), 1);
-        assert!(results[0].1.ends_with("000"));
+        assert!(results[0].hash.ends_with("000"));
     }
 }
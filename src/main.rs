@@ -1,48 +1,1444 @@
-use clap::Parser;
-use rust_hash_finder::find_hashes;
-use tracing::{info};
+use clap::{Parser, Subcommand};
+use rust_hash_finder::{
+    benchmark, collision_expected_attempts, compute_hash, estimate_attempts, find_suffix_collision,
+    format_chain, format_results_with_also, format_results_with_encoding, format_salted,
+    format_words, hex_decode, hex_encode, histogram_expected, trailing_zero_histogram,
+    verify_result_with_config, write_raw_digests,
+    AlgorithmConstraint, HashAlgorithm, HashEncoding, HashFinderConfig, HashFinderError,
+    HashResult, HexPattern, HmacKey, InputTemplate, MatchDirection, NonceEncoding, OutputFormat,
+    ProgressReporter, SearchOutcome, SearchProgress, SearchStats, WildcardPattern,
+    HISTOGRAM_BUCKETS, MAX_COLLISION_DIGITS, SHARD_BLOCK,
+};
+use tracing::{info, warn};
+
+#[cfg(feature = "argon2")]
+use rust_hash_finder::Argon2Params;
+#[cfg(feature = "checkpoint")]
+use rust_hash_finder::SearchCheckpoint;
+#[cfg(feature = "regex")]
+use rust_hash_finder::HashRegex;
+#[cfg(feature = "serde")]
+use rust_hash_finder::save_results;
 use tracing_subscriber::EnvFilter;
+use std::path::PathBuf;
 use std::process::ExitCode;
+use std::time::{Duration, Instant};
+
+/// How long `--estimate` hashes to measure throughput.
+const ESTIMATE_SAMPLE: Duration = Duration::from_secs(1);
+
+/// Difficulties `benchmark` prints an expected time for.
+const BENCHMARK_ZEROS: std::ops::RangeInclusive<usize> = 1..=8;
+
+/// Longest suffix `collide` searches without --force; longer ones take hundreds of MiB.
+const COLLIDE_DIGITS_UNFORCED: usize = 10;
+
+/// Rough memory per hash `collide` remembers, for its memory estimate.
+const COLLIDE_BYTES_PER_HASH: u64 = 32;
+
+/// Spelled as an alias so clap treats `--salt-hex` as a single value, not a list.
+type SaltBytes = Vec<u8>;
+
+/// Options --salt-file would silently override or ignore.
+const SALT_FILE_CONFLICTS: [&str; 9] =
+    ["salt", "salt_hex", "chained", "sort", "also", "raw_file", "count_only", "top_k", "dry_run"];
+
+/// Options --stdin would silently override or ignore.
+const STDIN_CONFLICTS: [&str; 11] = [
+    "end", "chained", "sort", "randomize", "salt_file", "raw_file", "count_only", "top_k",
+    "estimate", "dry_run", "chunk_size",
+];
+
+/// Options --wordlist would silently override or ignore, which include the ones that only
+/// make sense for numbers.
+const WORDLIST_CONFLICTS: [&str; 14] = [
+    "end", "chained", "sort", "randomize", "salt_file", "raw_file", "count_only", "top_k",
+    "estimate", "dry_run", "chunk_size", "stdin", "self_suffix", "pad_width",
+];
+
+/// Options that search in ways --shard-count cannot split.
+const SHARD_CONFLICTS: [&str; 7] =
+    ["chained", "sort", "salt_file", "stdin", "wordlist", "count_only", "top_k"];
+
+/// Exit status when --timeout stops a search short of -F results, as timeout(1) uses.
+const TIMED_OUT_EXIT: u8 = 124;
+
+/// Lines --stdin reads and hashes as one parallel batch.
+const STDIN_BATCH: usize = 1 << 16;
+
+/// Match criteria that replace -N.
+const CRITERIA: [&str; 8] =
+    ["prefix", "suffix", "contains", "wildcard", "palindrome", "bits", "pattern", "target"];
+
+/// Options that can be given without -N: the criteria, --min-zero-count and --self-suffix,
+/// which also combine with it, and --top-k, which ranks hashes instead.
+const ALONE: [&str; 11] = [
+    "prefix",
+    "suffix",
+    "contains",
+    "wildcard",
+    "palindrome",
+    "bits",
+    "pattern",
+    "target",
+    "min_zero_count",
+    "self_suffix",
+    "top_k",
+];
+
+/// Options --top-k cannot be combined with, since it ranks hashes rather than matching
+/// them.
+const NOT_WITH_TOP_K: [&str; 14] = [
+    "zeros",
+    "prefix",
+    "suffix",
+    "contains",
+    "wildcard",
+    "palindrome",
+    "bits",
+    "pattern",
+    "target",
+    "min_zero_count",
+    "self_suffix",
+    "count_only",
+    "estimate",
+    "chained",
+];
 
 #[derive(Parser, Debug)]
-#[command(author, version, about, long_about = None)]
+#[command(author, version, about, long_about = None, subcommand_negates_reqs = true)]
 struct Args {
-    #[arg(short = 'N', long)]
-    zeros: usize,
-    
-    #[arg(short = 'F', long)]
-    results: usize,
-    
-    #[arg(short, long)]
+    #[command(subcommand)]
+    command: Option<Command>,
+
+    #[arg(
+        short = 'N',
+        long,
+        required_unless_present_any = ALONE,
+        conflicts_with_all = CRITERIA
+    )]
+    zeros: Option<usize>,
+
+    /// Also require at least K '0' digits anywhere in the hash; alone, it is the only
+    /// criterion
+    #[arg(long, value_name = "K")]
+    min_zero_count: Option<usize>,
+
+    /// Also require the hash to end with the number's own decimal digits, e.g. 4163 for the
+    /// hash of 4163; alone, it is the only criterion
+    #[arg(long)]
+    self_suffix: bool,
+
+    /// Find digests starting with B zero bits instead of -N hex zeros, e.g. 9
+    #[arg(long, value_name = "B", conflicts_with_all = ["prefix", "suffix", "contains"])]
+    bits: Option<u32>,
+
+    /// Find hashes starting with these hex digits instead of -N zeros, e.g. cafe
+    #[arg(long, value_name = "HEX")]
+    prefix: Option<HexPattern>,
+
+    /// Find hashes ending with these hex digits instead of -N zeros, e.g. deadbeef; with
+    /// --prefix, both must match. Repeat or comma-separate to accept any of several, e.g.
+    /// 000,aaa,fff; each result then shows the suffix it hit
+    #[arg(long, value_name = "HEX", value_delimiter = ',')]
+    suffix: Vec<HexPattern>,
+
+    /// Find hashes containing these hex digits anywhere instead of -N zeros, e.g. c0ffee;
+    /// with --prefix or --suffix, all must match
+    #[arg(long, value_name = "HEX")]
+    contains: Option<HexPattern>,
+
+    /// Find hashes ending with these hex digits instead, where ? matches any digit, e.g.
+    /// de?dbe?f; as fast as --suffix
+    #[arg(
+        long,
+        value_name = "PATTERN",
+        conflicts_with_all = ["bits", "prefix", "suffix", "contains", "pattern", "target"]
+    )]
+    wildcard: Option<WildcardPattern>,
+
+    /// Anchor --wildcard to the start of the hash instead of the end
+    #[arg(long, requires = "wildcard")]
+    wildcard_at_start: bool,
+
+    /// Find hashes whose last K hex digits read the same backwards instead, e.g. 4; without
+    /// K the whole digest, which is astronomically rare and needs --force
+    #[arg(
+        long,
+        value_name = "K",
+        num_args = 0..=1,
+        conflicts_with_all = [
+            "bits", "prefix", "suffix", "contains", "wildcard", "pattern", "target"
+        ]
+    )]
+    palindrome: Option<Option<usize>>,
+
+    /// Search for a whole-digest --palindrome anyway
+    #[arg(long, requires = "palindrome")]
+    force: bool,
+
+    /// Find hashes whose lowercase hex matches this regex instead, e.g. '^00|cafe.*cafe';
+    /// much slower than -N, --prefix or --suffix since every hash is hex encoded
+    #[arg(
+        long,
+        value_name = "REGEX",
+        conflicts_with_all = ["bits", "prefix", "suffix", "contains", "estimate"]
+    )]
+    pattern: Option<String>,
+
+    /// Find digests numerically below this 256-bit target instead, given as 64 hex digits,
+    /// as in real proof of work; needs a 256-bit algorithm such as sha256
+    #[arg(
+        long,
+        value_name = "HEX",
+        value_parser = parse_target,
+        conflicts_with_all = ["bits", "prefix", "suffix", "contains", "pattern"]
+    )]
+    target: Option<[u8; 32]>,
+
+    #[arg(
+        short = 'F',
+        long,
+        required_unless_present_any = ["estimate", "count_only", "top_k", "stdin", "wordlist"]
+    )]
+    results: Option<usize>,
+
+    /// Print the expected attempts and time for the difficulty, from a short throughput
+    /// measurement, and exit without searching
+    #[arg(long)]
+    estimate: bool,
+
+    /// First number to hash; later runs can pick up past earlier results. 0 is allowed and
+    /// hashes "0"
+    #[arg(long, default_value_t = 1)]
+    start: u64,
+
+    /// Stop before this number instead of searching on until -F results are found; finding
+    /// fewer is reported on stderr
+    #[arg(long)]
+    end: Option<u64>,
+
+    /// Print how many numbers in --start..--end match, as `count, range, zeros`, instead
+    /// of the matches themselves
+    #[arg(long, requires = "end", conflicts_with_all = ["estimate", "chained"])]
+    count_only: bool,
+
+    /// Print the K numbers in --start..--end whose hashes have the most trailing zeros,
+    /// most first, instead of searching for a difficulty
+    #[arg(
+        long,
+        value_name = "K",
+        requires = "end",
+        conflicts_with_all = NOT_WITH_TOP_K
+    )]
+    top_k: Option<usize>,
+
+    /// Validate the arguments, print the resolved config as JSON with the hash of --start,
+    /// and exit without searching
+    #[arg(long, conflicts_with_all = ["estimate", "count_only", "top_k"])]
+    dry_run: bool,
+
+    /// Digest to search; xxhash and crc32 are non-cryptographic and meant for benchmarks
+    /// and demos
+    #[arg(short = 'A', long, global = true, default_value_t = HashAlgorithm::Sha256)]
+    algorithm: HashAlgorithm,
+
+    /// Memory per Argon2 hash, e.g. 64MiB, 512KiB or 1GiB
+    #[cfg(feature = "argon2")]
+    #[arg(
+        long,
+        global = true,
+        value_name = "SIZE",
+        default_value = "64MiB",
+        value_parser = parse_mem_kib
+    )]
+    argon2_mem: u32,
+
+    /// Argon2 passes over memory per hash
+    #[cfg(feature = "argon2")]
+    #[arg(
+        long,
+        global = true,
+        value_name = "N",
+        default_value_t = 3,
+        value_parser = clap::value_parser!(u32).range(1..)
+    )]
+    argon2_iters: u32,
+
+    /// How nonces are turned into bytes: decimal, hex, le-bytes, be-bytes
+    #[arg(long, global = true, default_value_t = NonceEncoding::DecimalString)]
+    nonce_encoding: NonceEncoding,
+
+    /// Left-pad decimal nonces with zeros to W digits (at most 20), e.g. 0000123
+    #[arg(long, global = true, value_name = "W")]
+    pad_width: Option<usize>,
+
+    /// Search HMAC-SHA256 output under this key, given as hex or as @FILE holding hex
+    #[arg(long, global = true, value_name = "HEX|@FILE", value_parser = parse_hmac_key)]
+    hmac_key: Option<HmacKey>,
+
+    /// Times to apply the hash, re-hashing the raw digest each round
+    #[arg(long, global = true, value_name = "K", default_value_t = 1)]
+    iterations: u32,
+
+    /// Input to hash with the nonce at `{n}`, e.g. `block-{n}-v2`
+    #[arg(long, global = true)]
+    template: Option<InputTemplate>,
+
+    /// Stop after this long, e.g. 30s, 5m or 1h30m (plain numbers are seconds), print
+    /// whatever was found and exit with status 124 if it was fewer than -F
+    #[arg(long, value_name = "DURATION", value_parser = parse_duration)]
+    timeout: Option<Duration>,
+
+    /// Worker threads to use (defaults to one per CPU)
+    #[arg(short = 'T', long)]
+    threads: Option<usize>,
+
+    /// String hashed in front of every nonce, e.g. myapp: to hash myapp:42 for nonce 42
+    ///
+    /// Also spelled --input-prefix, since --prefix constrains the hash rather than its input.
+    #[arg(long, global = true, visible_alias = "input-prefix")]
+    salt: Option<String>,
+
+    /// Salt given as hex, for salts that are not valid text
+    #[arg(
+        long,
+        global = true,
+        value_name = "HEX",
+        conflicts_with = "salt",
+        value_parser = hex_decode
+    )]
+    salt_hex: Option<SaltBytes>,
+
+    /// Find -F results for each salt in this file, one per line, each result tagged with
+    /// the line its salt is on; blank lines are skipped
+    #[arg(
+        long,
+        value_name = "PATH",
+        conflicts_with_all = SALT_FILE_CONFLICTS
+    )]
+    salt_file: Option<PathBuf>,
+
+    /// Hash the numbers on stdin, one per line, and print those that match in input order;
+    /// with -F, stop after that many
+    #[arg(long, conflicts_with_all = STDIN_CONFLICTS)]
+    stdin: bool,
+
+    /// Hash each line of this file in place of the nonce, after any --salt and inside any
+    /// --template, and print the words that match as `word, "hash"`; with -F, stop after
+    /// that many
+    #[arg(long, value_name = "PATH", conflicts_with_all = WORDLIST_CONFLICTS)]
+    wordlist: Option<PathBuf>,
+
+    /// Fail on a --stdin line that is not a number instead of warning and skipping it
+    #[arg(long, requires = "stdin")]
+    strict: bool,
+
+    /// Also require ALGORITHM's digest to end with ZEROS zeros, e.g. sha512:2; repeatable
+    #[arg(long, value_name = "ALGORITHM:ZEROS")]
+    also: Vec<AlgorithmConstraint>,
+
+    /// Find a chain of F links, each hashing the previous link's hash followed by its nonce
+    #[arg(long)]
+    chained: bool,
+
+    /// Print the F smallest matching numbers in ascending order, the same on every run
+    #[arg(long, conflicts_with = "chained")]
+    sort: bool,
+
+    /// Search explicit ranges of N numbers in turn instead of one unbounded parallel stream
+    #[arg(
+        long,
+        value_name = "N",
+        conflicts_with_all = ["chained", "sort"],
+        value_parser = clap::value_parser!(u64).range(1..)
+    )]
+    chunk_size: Option<u64>,
+
+    /// Visit blocks of 2^20 numbers, or --chunk-size, in a shuffled order instead of
+    /// counting up from --start, so different runs find different results
+    #[arg(long, conflicts_with_all = ["chained", "sort"])]
+    randomize: bool,
+
+    /// Seed for --randomize, to repeat an earlier run's block order; logged when not given
+    #[arg(long, requires = "randomize")]
+    seed: Option<u64>,
+
+    /// Search only shard I of --shard-count: the blocks of 2^20 numbers, or --chunk-size,
+    /// whose index modulo the shard count is I; each result is tagged `shard=I/N`
+    #[arg(long, value_name = "I", requires = "shard_count")]
+    shard_index: Option<u64>,
+
+    /// Split the search into N shards for separate machines, each run with the same options
+    /// and its own --shard-index in 0..N
+    #[arg(
+        long,
+        value_name = "N",
+        requires = "shard_index",
+        conflicts_with_all = SHARD_CONFLICTS,
+        value_parser = clap::value_parser!(u64).range(1..)
+    )]
+    shard_count: Option<u64>,
+
+    /// Save search progress to this file so it can be resumed; with --chunk-size, progress
+    /// is recorded every N numbers instead of every 2^24
+    #[cfg(feature = "checkpoint")]
+    #[arg(
+        long,
+        value_name = "PATH",
+        conflicts_with_all = [
+            "chained", "sort", "randomize", "salt_file", "stdin", "wordlist", "shard_count"
+        ]
+    )]
+    checkpoint_file: Option<PathBuf>,
+
+    /// Least time between saves of --checkpoint-file
+    #[cfg(feature = "checkpoint")]
+    #[arg(long, value_name = "SECONDS", default_value_t = 10)]
+    checkpoint_interval: u64,
+
+    /// Continue from the progress saved in --checkpoint-file; refused if it was saved with a
+    /// different algorithm, -N, salt or nonce encoding
+    #[cfg(feature = "checkpoint")]
+    #[arg(long, requires = "checkpoint_file")]
+    resume: bool,
+
+    /// Print elapsed time, hashes computed, hashes/sec and results found to stderr
+    /// periodically
+    #[arg(long)]
+    progress: bool,
+
+    /// Seconds between --progress lines
+    #[arg(
+        long,
+        value_name = "SECONDS",
+        default_value_t = 5,
+        requires = "progress",
+        value_parser = clap::value_parser!(u64).range(1..)
+    )]
+    progress_interval: u64,
+
+    /// Print elapsed time, candidates checked, hash rate and results found to stderr once
+    /// the search finishes
+    #[arg(long)]
+    stats: bool,
+
+    /// Output format: text, json, csv
+    #[arg(long, default_value_t = OutputFormat::Text)]
+    format: OutputFormat,
+
+    /// How hashes are printed: lowerhex, upperhex, base64, raw-bytes-to-file
+    #[arg(long, default_value_t = HashEncoding::LowerHex)]
+    hash_encoding: HashEncoding,
+
+    /// File receiving the raw digests with --hash-encoding raw-bytes-to-file
+    #[arg(long, value_name = "PATH")]
+    raw_file: Option<PathBuf>,
+
+    /// Write results to this file instead of stdout, replacing its contents; with --format
+    /// json it is a JSON array `load_results` reads back
+    #[arg(long, value_name = "PATH")]
+    output_file: Option<PathBuf>,
+
+    /// Append to --output-file instead of truncating it
+    #[arg(long, requires = "output_file")]
+    append: bool,
+
+    #[arg(short, long, global = true)]
     verbose: bool,
+
+    /// Log format: text, json (one object per line, for log aggregators); logs go to stderr
+    #[arg(long, global = true, default_value_t = LogFormat::Text)]
+    log_format: LogFormat,
+}
+
+/// How log events are written by `--log-format`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+enum LogFormat {
+    /// Human-readable lines, e.g. `2026-10-16T12:00:00.000000Z  INFO run{zeros=3 ...}: ...`.
+    #[default]
+    Text,
+    /// One JSON object per line on stderr, like the text logs:
+    ///
+    /// ```text
+    /// {
+    ///   "timestamp": "2026-10-16T12:00:00.000000Z",
+    ///   "level": "DEBUG",
+    ///   "fields": {"message": "Found hash", "num": 4163, "hash": "95d4...3000"},
+    ///   "span": {"zeros": 3, "max_results": 2, "start": 1, "algo": "sha256", "name": "run"},
+    ///   "spans": [{"zeros": 3, "max_results": 2, "start": 1, "algo": "sha256", "name": "run"}]
+    /// }
+    /// ```
+    ///
+    /// `fields` holds the message and the event's own fields, `span` the innermost
+    /// `#[instrument]` span and `spans` every enclosing one, outermost first. Both are
+    /// omitted outside any span. Counts such as `zeros`, `max_results`, `start` and `num`
+    /// are JSON numbers; algorithm and encoding names are strings.
+    Json,
+}
+
+impl LogFormat {
+    const ALL: [LogFormat; 2] = [LogFormat::Text, LogFormat::Json];
+
+    fn name(self) -> &'static str {
+        match self {
+            LogFormat::Text => "text",
+            LogFormat::Json => "json",
+        }
+    }
+}
+
+impl std::fmt::Display for LogFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl std::str::FromStr for LogFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        LogFormat::ALL
+            .into_iter()
+            .find(|format| format.name().eq_ignore_ascii_case(s))
+            .ok_or_else(|| format!("unknown log format '{}' (available: text, json)", s))
+    }
+}
+
+impl Args {
+    /// Applies `--argon2-mem` and `--argon2-iters` to an `--algorithm argon2` selection.
+    #[cfg(feature = "argon2")]
+    fn with_argon2_params(mut self) -> Result<Self, String> {
+        if let HashAlgorithm::Argon2id(_) = self.algorithm {
+            let params = Argon2Params::new(self.argon2_mem, self.argon2_iters)?;
+            self.algorithm = HashAlgorithm::Argon2id(params);
+        }
+        Ok(self)
+    }
+
+    /// Whether every hash must be counted, for `--stats` or for results to carry attempt
+    /// counts.
+    fn counts_attempts(&self) -> bool {
+        #[cfg(feature = "argon2")]
+        let memory_hard = matches!(self.algorithm, HashAlgorithm::Argon2id(_));
+        #[cfg(not(feature = "argon2"))]
+        let memory_hard = false;
+        self.progress || self.stats || memory_hard
+    }
+
+    /// The file to write with `save_results` rather than the formatter: a fresh
+    /// `--output-file` with `--format json`, for plain results in lowercase hex.
+    #[cfg(feature = "serde")]
+    fn json_output_file(&self) -> Option<&PathBuf> {
+        let plain = !self.chained && self.also.is_empty() && !self.append;
+        let json = self.format == OutputFormat::Json;
+        let hex = self.hash_encoding == HashEncoding::LowerHex;
+        self.output_file.as_ref().filter(|_| plain && json && hex)
+    }
+
+    fn salt_bytes(&self) -> Option<&[u8]> {
+        match (&self.salt, &self.salt_hex) {
+            (Some(salt), _) => Some(salt.as_bytes()),
+            (None, Some(salt)) => Some(salt),
+            (None, None) => None,
+        }
+    }
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Verify `number, "hash"` lines produced by a previous run
+    Verify {
+        #[arg(short = 'N', long, required_unless_present = "self_suffix")]
+        zeros: Option<usize>,
+
+        /// Check that each hash ends with its own number, as searched with --self-suffix
+        #[arg(long)]
+        self_suffix: bool,
+
+        /// File to read results from (defaults to stdin)
+        file: Option<PathBuf>,
+    },
+    /// Measure SHA-256 throughput on every core and estimate the time per result for 1 to 8
+    /// trailing zeros
+    Benchmark {
+        /// How long to hash for
+        #[arg(long, value_name = "SECONDS", default_value_t = 5)]
+        bench_seconds: u64,
+    },
+    /// Find two numbers whose SHA-256 hashes share their last K hex digits, remembering
+    /// every hash checked until they do
+    Collide {
+        /// Hex digits the two hashes must share
+        #[arg(
+            short = 'K',
+            long,
+            value_parser = clap::value_parser!(u8).range(1..=MAX_COLLISION_DIGITS as i64)
+        )]
+        digits: u8,
+
+        /// Search even when K needs hundreds of MiB or more
+        #[arg(long)]
+        force: bool,
+    },
+    /// Count how many hashes of 1..=M end with exactly 0, 1, 2, ... zeros, next to the
+    /// counts a uniform hash would give
+    Histogram {
+        /// Last number to hash
+        #[arg(short = 'M', long, value_parser = clap::value_parser!(u64).range(1..))]
+        max: u64,
+
+        /// Print `{"total": M, "buckets": [...]}` instead of a table
+        #[arg(long)]
+        json: bool,
+    },
+}
+
+/// Reads an `--argon2-mem` size in KiB, MiB or GiB; a bare number is taken as KiB.
+#[cfg(feature = "argon2")]
+fn parse_mem_kib(value: &str) -> Result<u32, String> {
+    let (digits, scale) = [("GiB", 1024 * 1024), ("MiB", 1024), ("KiB", 1)]
+        .into_iter()
+        .find_map(|(suffix, scale)| value.strip_suffix(suffix).map(|d| (d, scale)))
+        .unwrap_or((value, 1));
+    digits
+        .trim()
+        .parse::<u32>()
+        .ok()
+        .and_then(|n| n.checked_mul(scale))
+        .filter(|&kib| kib >= 8)
+        .ok_or_else(|| {
+            format!("invalid memory size '{}' (expected e.g. 64MiB, at least 8KiB)", value)
+        })
+}
+
+/// Applies `--pattern`, which needs the `regex` feature.
+#[cfg(feature = "regex")]
+fn with_regex(config: HashFinderConfig, pattern: &str) -> Result<HashFinderConfig, String> {
+    Ok(config.regex(HashRegex::new(pattern)?))
+}
+
+#[cfg(not(feature = "regex"))]
+fn with_regex(_config: HashFinderConfig, _pattern: &str) -> Result<HashFinderConfig, String> {
+    Err("--pattern was not compiled in; rebuild with `--features regex`".to_string())
+}
+
+/// Reads a `--target` value: exactly 64 hex digits.
+fn parse_target(value: &str) -> Result<[u8; 32], String> {
+    let bytes = hex_decode(value).map_err(|e| format!("invalid target: {}", e))?;
+    <[u8; 32]>::try_from(bytes)
+        .map_err(|_| format!("target must be 64 hex digits (got {})", value.len()))
+}
+
+/// Reads a `--timeout` value: whole seconds, or amounts in `h`, `m`, `s` or `ms` run
+/// together, e.g. `90s`, `5m` or `1h30m`.
+fn parse_duration(value: &str) -> Result<Duration, String> {
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Ok(Duration::from_secs(seconds));
+    }
+
+    let invalid = || format!("invalid duration '{}': expected e.g. 90s, 5m or 1h30m", value);
+    if value.is_empty() {
+        return Err(invalid());
+    }
+    let mut total = Duration::ZERO;
+    let mut rest = value;
+    while !rest.is_empty() {
+        let digits = rest.find(|c: char| !c.is_ascii_digit()).ok_or_else(invalid)?;
+        let amount: u64 = rest[..digits].parse().map_err(|_| invalid())?;
+        rest = &rest[digits..];
+        let unit_len = rest.find(|c: char| c.is_ascii_digit()).unwrap_or(rest.len());
+        let part = match &rest[..unit_len] {
+            "ms" => Some(Duration::from_millis(amount)),
+            "s" => Some(Duration::from_secs(amount)),
+            "m" => amount.checked_mul(60).map(Duration::from_secs),
+            "h" => amount.checked_mul(3600).map(Duration::from_secs),
+            _ => None,
+        };
+        total = part.and_then(|part| total.checked_add(part)).ok_or_else(invalid)?;
+        rest = &rest[unit_len..];
+    }
+    Ok(total)
+}
+
+/// Reads an `--hmac-key` value: hex digits, or `@path` to a file containing them.
+fn parse_hmac_key(value: &str) -> Result<HmacKey, String> {
+    match value.strip_prefix('@') {
+        Some(path) => {
+            let hex = std::fs::read_to_string(path)
+                .map_err(|e| format!("failed to read HMAC key file {}: {}", path, e))?;
+            HmacKey::from_hex(&hex)
+        }
+        None => HmacKey::from_hex(value),
+    }
 }
 
 fn main() -> ExitCode {
     let args = Args::parse();
-    
-    if args.zeros == 0 || args.results == 0 {
-        eprintln!("Error: Both N and F must be greater than 0");
-        return ExitCode::FAILURE;
-    }
-    
+    #[cfg(feature = "argon2")]
+    let args = match args.with_argon2_params() {
+        Ok(args) => args,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+
     let default_level = if args.verbose { "debug" } else { "info" };
     let env_filter = EnvFilter::try_from_default_env()
         .unwrap_or_else(|_| EnvFilter::new(format!("rust_hash_finder={}", default_level)));
-    
-    tracing_subscriber::fmt()
+
+    // Logs go to stderr so stdout carries only results, for pipes and `--format json`.
+    let subscriber = tracing_subscriber::fmt()
         .with_env_filter(env_filter)
-        .with_target(false)
-        .init();
-    
+        .with_writer(std::io::stderr)
+        .with_target(false);
+    match args.log_format {
+        LogFormat::Text => subscriber.init(),
+        LogFormat::Json => subscriber
+            .json()
+            .with_current_span(true)
+            .with_span_list(true)
+            .init(),
+    }
+
+    match &args.command {
+        Some(Command::Verify {
+            zeros,
+            self_suffix,
+            file,
+        }) => verify(&args, zeros.unwrap_or_default(), *self_suffix, file.as_ref()),
+        Some(Command::Benchmark { bench_seconds }) => run_benchmark(*bench_seconds),
+        Some(Command::Collide { digits, force }) => collide(usize::from(*digits), *force),
+        Some(Command::Histogram { max, json }) => histogram(*max, *json),
+        None => search(&args),
+    }
+}
+
+fn search(args: &Args) -> ExitCode {
+    // Required by clap unless a subcommand or another difficulty option is given.
+    let zeros = args.zeros.unwrap_or_default();
+    let max_results = match args.results {
+        Some(max_results) => max_results,
+        None if args.stdin || args.wordlist.is_some() => usize::MAX,
+        None => 0,
+    };
+
+    let raw_file = match (args.hash_encoding, &args.raw_file) {
+        (HashEncoding::RawBytes, Some(path)) => Some(path),
+        (HashEncoding::RawBytes, None) => {
+            eprintln!("Error: --hash-encoding raw-bytes-to-file requires --raw-file <PATH>");
+            return ExitCode::FAILURE;
+        }
+        _ => None,
+    };
+
+    // Opened before searching so a bad path fails fast rather than after a long search.
+    // A dry run leaves the file alone.
+    let mut out: Box<dyn std::io::Write> = match &args.output_file {
+        Some(path) if !args.dry_run => {
+            let file = std::fs::OpenOptions::new()
+                .write(true)
+                .create(true)
+                .append(args.append)
+                .truncate(!args.append)
+                .open(path);
+            match file {
+                Ok(file) => Box::new(std::io::BufWriter::new(file)),
+                Err(e) => {
+                    eprintln!("Error: failed to open {}: {}", path.display(), e);
+                    return ExitCode::FAILURE;
+                }
+            }
+        }
+        _ => Box::new(std::io::stdout().lock()),
+    };
+
+    let mut config = HashFinderConfig::new()
+        .zeros(zeros)
+        .max_results(max_results)
+        .start_from(args.start)
+        .algorithm(args.algorithm)
+        .nonce_encoding(args.nonce_encoding);
+    if let Some(bits) = args.bits {
+        config = config.leading_zero_bits(bits);
+    }
+    if let Some(prefix) = &args.prefix {
+        config = config.prefix(prefix.clone());
+    }
+    match args.suffix.as_slice() {
+        [] => {}
+        [suffix] => config = config.suffix(suffix.clone()),
+        suffixes => config = config.suffixes(suffixes.iter().cloned().collect()),
+    }
+    if let Some(needle) = &args.contains {
+        config = config.contains(needle.clone());
+    }
+    if let Some(wildcard) = &args.wildcard {
+        config = config.wildcard(wildcard.clone());
+        if args.wildcard_at_start {
+            config = config.direction(MatchDirection::Leading);
+        }
+    }
+    // clap drops `requires = "palindrome"` when -N is given, since -N conflicts with it.
+    if args.force && args.palindrome.is_none() {
+        eprintln!("Error: --force only applies to --palindrome");
+        return ExitCode::FAILURE;
+    }
+    if let Some(len) = args.palindrome {
+        let whole = args.algorithm.hex_len();
+        if len.is_none() && !args.force && !args.estimate {
+            eprintln!(
+                "Error: a whole-digest palindrome takes about 16^{} hashes to find; give a \
+                 length such as --palindrome 4, or pass --force to search anyway",
+                whole / 2
+            );
+            return ExitCode::FAILURE;
+        }
+        config = config.palindrome(len.unwrap_or(whole));
+    }
+    if let Some(target) = args.target {
+        config = config.target(target);
+    }
+    if let Some(count) = args.min_zero_count {
+        config = config.min_zero_count(count);
+    }
+    config = config.self_suffix(args.self_suffix);
+    if let Some(pattern) = &args.pattern {
+        config = match with_regex(config, pattern) {
+            Ok(config) => config,
+            Err(e) => {
+                eprintln!("Error: {}", e);
+                return ExitCode::FAILURE;
+            }
+        };
+    }
+    if let Some(salt) = args.salt_bytes() {
+        config = config.salt(salt);
+    }
+    if let Some(template) = &args.template {
+        config = config.template(template.clone());
+    }
+    if let Some(key) = &args.hmac_key {
+        config = config.hmac_key(key.clone());
+    }
+    config = config.iterations(args.iterations);
+    if let Some(width) = args.pad_width {
+        config = config.pad_width(width);
+    }
+    if let Some(threads) = args.threads {
+        config = config.threads(threads);
+    }
+    if let Some(chunk_size) = args.chunk_size {
+        config = config.chunk_size(chunk_size);
+    }
+    if args.randomize {
+        config = config.randomize(args.seed.unwrap_or_else(random_seed));
+    }
+    if let (Some(index), Some(count)) = (args.shard_index, args.shard_count) {
+        config = config.shard(index, count);
+    }
+    if let Some(timeout) = args.timeout {
+        config = config.timeout(timeout);
+    }
+    #[cfg(feature = "checkpoint")]
+    {
+        config = config.checkpoint_interval(Duration::from_secs(args.checkpoint_interval));
+    }
+    for constraint in &args.also {
+        config = config.also(*constraint);
+    }
+    if let Some(end) = args.end {
+        config = config.end(end);
+    }
+    if args.count_only {
+        config = config.max_results(usize::MAX);
+    }
+    if args.estimate {
+        return estimate(&config);
+    }
+    if let Some(k) = args.top_k {
+        return top_k(&config, k, args, &mut out);
+    }
+    let progress = args.counts_attempts().then(SearchProgress::new);
+    if let Some(progress) = &progress {
+        config = config.progress(progress.clone());
+    }
+    let config = match config.build() {
+        Ok(config) => config,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+    if args.dry_run {
+        return dry_run(&config);
+    }
+
+    if let (true, Some(end)) = (args.count_only, args.end) {
+        info!("Counting matches in {}..{}", args.start, end);
+        let count = match config.count_matches() {
+            Ok(count) => count,
+            Err(e) => {
+                eprintln!("Error: {}", e);
+                return ExitCode::FAILURE;
+            }
+        };
+        let written = writeln!(out, "{}, {}..{}, {}", count, args.start, end, config.difficulty())
+            .and_then(|()| out.flush());
+        if let Err(e) = written {
+            eprintln!("Error: failed to write count: {}", e);
+            return ExitCode::FAILURE;
+        }
+        return ExitCode::SUCCESS;
+    }
+
     info!("Hash Finder starting...");
-    info!("Configuration: N={}, F={}", args.zeros, args.results);
-    
-    let results = find_hashes(args.zeros, args.results);
-    
-    for (num, hash) in results {
-        println!("{}, \"{}\"", num, hash);
-    }
-    
+    info!(
+        "Configuration: N={}, F={}, start={}, algorithm={}, nonce encoding={}",
+        zeros, max_results, args.start, args.algorithm, args.nonce_encoding
+    );
+    if let Some(bits) = args.bits {
+        info!("Leading zero bits: {}", bits);
+    }
+    if let Some(prefix) = &args.prefix {
+        info!("Prefix: {}", prefix);
+    }
+    if let Some(suffixes) = &config.suffixes {
+        info!("Suffixes: {}", suffixes);
+    } else if let Some(suffix) = &config.suffix {
+        info!("Suffix: {}", suffix);
+    }
+    if let Some(needle) = &args.contains {
+        info!("Contains: {}", needle);
+    }
+    if let Some(wildcard) = &args.wildcard {
+        let anchor = if args.wildcard_at_start { "start" } else { "end" };
+        info!("Wildcard: {} (at {})", wildcard, anchor);
+    }
+    if let Some(len) = config.palindrome {
+        info!("Palindrome: last {} digits", len);
+        if len == args.algorithm.hex_len() {
+            warn!("A whole-digest palindrome will practically never be found");
+        }
+    }
+    if let Some(pattern) = &args.pattern {
+        info!("Regex: {}", pattern);
+    }
+    if let Some(target) = &args.target {
+        info!("Target: {}", hex_encode(target));
+    }
+    if let Some(count) = args.min_zero_count {
+        info!("Minimum zero count: {}", count);
+    }
+    if args.self_suffix {
+        info!("Self suffix: hashes must end with their own number");
+    }
+    if let Some(salt) = &args.salt {
+        info!("Salt: {:?}", salt);
+    }
+    if let Some(salt) = &args.salt_hex {
+        info!("Salt: {}", hex_encode(salt));
+    }
+    if let Some(template) = &args.template {
+        info!("Template: {}", template);
+    }
+    if args.hmac_key.is_some() {
+        info!("HMAC-SHA256 mode enabled");
+    }
+    if args.iterations > 1 {
+        info!("Iterations: {}", args.iterations);
+    }
+    if let Some(width) = args.pad_width {
+        info!("Padding nonces to {} digits", width);
+    }
+    for constraint in &args.also {
+        info!("Also requiring: {}", constraint);
+    }
+    if args.chained {
+        info!("Chained mode: searching for {} links", max_results);
+    }
+    if let Some(threads) = args.threads {
+        info!("Threads: {}", threads);
+    }
+    if let Some(chunk_size) = args.chunk_size {
+        info!("Chunk size: {}", chunk_size);
+    }
+    if let Some(seed) = config.randomize {
+        info!("Randomized block order with seed {} (repeat with --seed {})", seed, seed);
+    }
+    if let Some(shard) = config.shard {
+        let block = args.chunk_size.unwrap_or(SHARD_BLOCK);
+        info!("Shard {} of blocks of {} numbers", shard, block);
+    }
+    if let Some(timeout) = args.timeout {
+        info!("Timeout: {:?}", timeout);
+    }
+    #[cfg(feature = "argon2")]
+    if let HashAlgorithm::Argon2id(params) = args.algorithm {
+        info!("Argon2id parameters: {}", params);
+    }
+
+    if let Some(path) = &args.salt_file {
+        return search_salt_file(&config, path, args, &mut out);
+    }
+    if args.stdin {
+        return filter_stdin(&config, args, &mut out);
+    }
+    if let Some(path) = &args.wordlist {
+        return search_wordlist(&config, path, args, &mut out);
+    }
+
+    let started = Instant::now();
+    let reporter = progress.clone().filter(|_| args.progress).map(|progress| {
+        let interval = Duration::from_secs(args.progress_interval);
+        ProgressReporter::start(progress, interval, |report| eprintln!("Progress: {}", report))
+    });
+
+    #[cfg(feature = "checkpoint")]
+    let results = run_checkpointed(args, &config);
+    #[cfg(not(feature = "checkpoint"))]
+    let results = run_uncheckpointed(args, &config);
+    drop(reporter);
+    let (results, outcome) = match results {
+        Ok(found) => found,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    if let Some(progress) = progress.filter(|_| args.stats) {
+        let stats = SearchStats::new(started.elapsed(), progress.hashes(), results.len());
+        eprint!("{}", stats);
+    }
+
+    if let Some(path) = raw_file {
+        let written = std::fs::File::create(path)
+            .and_then(|file| write_raw_digests(&results, std::io::BufWriter::new(file)));
+        if let Err(e) = written {
+            eprintln!("Error: failed to write {}: {}", path.display(), e);
+            return ExitCode::FAILURE;
+        }
+        info!("Wrote {} raw digests to {}", results.len(), path.display());
+    }
+
+    #[cfg(feature = "serde")]
+    let saved = args.json_output_file().map(|path| save_results(&results, path));
+    #[cfg(not(feature = "serde"))]
+    let saved = None;
+    let written = if let Some(saved) = saved {
+        saved
+    } else if args.chained {
+        format_chain(&results, args.format, args.hash_encoding, &mut out)
+    } else if !args.also.is_empty() {
+        format_results_with_also(&results, &config, args.format, args.hash_encoding, &mut out)
+    } else {
+        format_results_with_encoding(&results, args.format, args.hash_encoding, &mut out)
+    };
+    if let Err(e) = written {
+        eprintln!("Error: failed to write results: {}", e);
+        return ExitCode::FAILURE;
+    }
+    if let (Some(end), true) = (args.end, results.len() < max_results) {
+        eprintln!(
+            "Found {} of {} requested results in {}..{}",
+            results.len(),
+            max_results,
+            args.start,
+            end
+        );
+    }
+    if let (Some(timeout), SearchOutcome::TimedOut) = (args.timeout, outcome) {
+        eprintln!(
+            "Timed out after {:?} with {} of {} requested results",
+            timeout,
+            results.len(),
+            max_results
+        );
+        return ExitCode::from(TIMED_OUT_EXIT);
+    }
+
     info!("Hash Finder completed successfully");
     ExitCode::SUCCESS
 }
+
+/// A seed for --randomize that differs between runs.
+fn random_seed() -> u64 {
+    let nanos = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |since| since.as_nanos() as u64);
+    nanos ^ u64::from(std::process::id()).rotate_left(32)
+}
+
+/// Prints the expected attempts and time for the config's difficulty on this machine.
+fn estimate(config: &HashFinderConfig) -> ExitCode {
+    if let Err(e) = config.clone().max_results(1).build() {
+        eprintln!("Error: {}", e);
+        return ExitCode::FAILURE;
+    }
+
+    info!("Measuring {} throughput...", config.algorithm);
+    let hashes_per_sec = match config.measure_hashes_per_sec(ESTIMATE_SAMPLE) {
+        Ok(hashes_per_sec) => hashes_per_sec,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+    println!("Expected attempts: {}", config.expected_attempts());
+    println!("Measured throughput: {:.0} H/s", hashes_per_sec);
+    println!("Expected time: {:.1?}", config.expected_duration(hashes_per_sec));
+    ExitCode::SUCCESS
+}
+
+/// Prints the validated config as JSON, with the hash of its first number as a sample.
+#[cfg(feature = "serde")]
+fn dry_run(config: &HashFinderConfig) -> ExitCode {
+    #[derive(serde::Serialize)]
+    struct DryRun<'a> {
+        config: &'a HashFinderConfig,
+        sample: HashResult,
+    }
+
+    let sample = HashResult::new(config.start, config.hash(config.start));
+    match serde_json::to_string_pretty(&DryRun { config, sample }) {
+        Ok(json) => {
+            println!("{}", json);
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            eprintln!("Error: failed to serialize config: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+#[cfg(not(feature = "serde"))]
+fn dry_run(_config: &HashFinderConfig) -> ExitCode {
+    eprintln!("Error: --dry-run needs JSON support; rebuild with `--features serde`");
+    ExitCode::FAILURE
+}
+
+fn run_benchmark(seconds: u64) -> ExitCode {
+    if seconds == 0 {
+        eprintln!("Error: --bench-seconds must be greater than 0");
+        return ExitCode::FAILURE;
+    }
+
+    info!("Hashing with SHA-256 for {}s...", seconds);
+    let result = match benchmark(seconds) {
+        Ok(result) => result,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+    print!("{}", result);
+    println!();
+    println!("Zeros  Expected attempts  Expected time");
+    for zeros in BENCHMARK_ZEROS {
+        println!(
+            "{:<5}  {:<17}  {:.1?}",
+            zeros,
+            estimate_attempts(zeros),
+            result.expected_duration(zeros)
+        );
+    }
+    ExitCode::SUCCESS
+}
+
+/// Prints the `k` numbers in the config's range whose hashes have the most trailing zeros.
+fn top_k(
+    config: &HashFinderConfig,
+    k: usize,
+    args: &Args,
+    out: &mut dyn std::io::Write,
+) -> ExitCode {
+    info!("Ranking {}..{} by trailing zeros", args.start, args.end.unwrap_or_default());
+    let results = match config.run_top_k_zeros(k) {
+        Ok(results) => results,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+    let written = format_results_with_encoding(&results, args.format, args.hash_encoding, out);
+    if let Err(e) = written {
+        eprintln!("Error: failed to write results: {}", e);
+        return ExitCode::FAILURE;
+    }
+    ExitCode::SUCCESS
+}
+
+/// Finds -F results for every salt in `path`, one salt per line, tagged with its line number.
+fn search_salt_file(
+    config: &HashFinderConfig,
+    path: &std::path::Path,
+    args: &Args,
+    out: &mut dyn std::io::Write,
+) -> ExitCode {
+    let contents = match std::fs::read_to_string(path) {
+        Ok(contents) => contents,
+        Err(e) => {
+            eprintln!("Error: failed to read {}: {}", path.display(), e);
+            return ExitCode::FAILURE;
+        }
+    };
+    // Blank lines are skipped but still counted, so tags match the file's line numbers.
+    let (lines, salts): (Vec<usize>, Vec<Vec<u8>>) = contents
+        .lines()
+        .enumerate()
+        .filter(|(_, salt)| !salt.is_empty())
+        .map(|(i, salt)| (i + 1, salt.as_bytes().to_vec()))
+        .unzip();
+
+    info!("Searching {} salts from {}", salts.len(), path.display());
+    let found = match config.run_per_salt(&salts) {
+        Ok(found) => found,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+    let groups: Vec<(usize, Vec<HashResult>)> = lines.into_iter().zip(found).collect();
+    if let Err(e) = format_salted(&groups, args.format, args.hash_encoding, out) {
+        eprintln!("Error: failed to write results: {}", e);
+        return ExitCode::FAILURE;
+    }
+    ExitCode::SUCCESS
+}
+
+/// Hashes the numbers on stdin in batches of [`STDIN_BATCH`] lines and writes the matches in
+/// input order, up to -F of them.
+fn filter_stdin(config: &HashFinderConfig, args: &Args, out: &mut dyn std::io::Write) -> ExitCode {
+    use std::io::BufRead;
+
+    let max_results = args.results.unwrap_or(usize::MAX);
+    let mut lines = std::io::stdin().lock().lines().enumerate();
+    let mut results = Vec::new();
+    while results.len() < max_results {
+        let mut batch = Vec::with_capacity(STDIN_BATCH);
+        let mut read = 0;
+        for (i, line) in lines.by_ref().take(STDIN_BATCH) {
+            read += 1;
+            let line = match line {
+                Ok(line) => line,
+                Err(e) => {
+                    eprintln!("Error: failed to read stdin: {}", e);
+                    return ExitCode::FAILURE;
+                }
+            };
+            let line = line.trim();
+            match line.parse::<u64>() {
+                Ok(num) => batch.push(num),
+                Err(_) if line.is_empty() => {}
+                Err(e) if args.strict => {
+                    eprintln!("Error: line {}: invalid number {:?}: {}", i + 1, line, e);
+                    return ExitCode::FAILURE;
+                }
+                Err(e) => {
+                    eprintln!("Warning: skipping line {}, invalid number {:?}: {}", i + 1, line, e)
+                }
+            }
+        }
+        if read == 0 {
+            break;
+        }
+        match config.filter_numbers(&batch) {
+            Ok(found) => results.extend(found),
+            Err(e) => {
+                eprintln!("Error: {}", e);
+                return ExitCode::FAILURE;
+            }
+        }
+    }
+    results.truncate(max_results);
+
+    if let Err(e) = format_results_with_encoding(&results, args.format, args.hash_encoding, out) {
+        eprintln!("Error: failed to write results: {}", e);
+        return ExitCode::FAILURE;
+    }
+    ExitCode::SUCCESS
+}
+
+/// Hashes every non-empty line of `path` as a word and writes the matches in file order, up
+/// to -F of them.
+fn search_wordlist(
+    config: &HashFinderConfig,
+    path: &std::path::Path,
+    args: &Args,
+    out: &mut dyn std::io::Write,
+) -> ExitCode {
+    let contents = match std::fs::read_to_string(path) {
+        Ok(contents) => contents,
+        Err(e) => {
+            eprintln!("Error: failed to read {}: {}", path.display(), e);
+            return ExitCode::FAILURE;
+        }
+    };
+    let words: Vec<&str> = contents.lines().filter(|word| !word.is_empty()).collect();
+
+    info!("Hashing {} words from {}", words.len(), path.display());
+    let mut results = match config.filter_words(&words) {
+        Ok(results) => results,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+    results.truncate(args.results.unwrap_or(usize::MAX));
+    if let Err(e) = format_words(&results, args.format, args.hash_encoding, out) {
+        eprintln!("Error: failed to write results: {}", e);
+        return ExitCode::FAILURE;
+    }
+    ExitCode::SUCCESS
+}
+
+/// Prints how many hashes of `1..=max` end with each number of zeros.
+fn histogram(max: u64, json: bool) -> ExitCode {
+    info!("Hashing 1..={}...", max);
+    let buckets = trailing_zero_histogram(1..=max);
+    if json {
+        let counts: Vec<String> = buckets.iter().map(u64::to_string).collect();
+        println!("{{\"total\":{},\"buckets\":[{}]}}", max, counts.join(","));
+        return ExitCode::SUCCESS;
+    }
+
+    // Rows past the last non-empty bucket would all read 0.
+    let rows = buckets.iter().rposition(|&count| count > 0).map_or(0, |last| last + 1);
+    println!("Zeros  Count       Expected");
+    for (zeros, count) in buckets.iter().enumerate().take(rows) {
+        let label = if zeros == HISTOGRAM_BUCKETS - 1 {
+            format!("{}+", zeros)
+        } else {
+            zeros.to_string()
+        };
+        println!("{:<5}  {:<10}  {:.1}", label, count, histogram_expected(max, zeros));
+    }
+    ExitCode::SUCCESS
+}
+
+/// Prints two numbers whose hashes end with the same `digits` hex digits.
+fn collide(digits: usize, force: bool) -> ExitCode {
+    let expected = collision_expected_attempts(digits);
+    let mib = expected.saturating_mul(COLLIDE_BYTES_PER_HASH) >> 20;
+    if digits > COLLIDE_DIGITS_UNFORCED && !force {
+        eprintln!(
+            "Error: -K {} remembers about {} hashes (~{} MiB) before a collision; pass --force \
+             to search anyway",
+            digits, expected, mib
+        );
+        return ExitCode::FAILURE;
+    }
+
+    info!("Expecting about {} hashes (~{} MiB) before a collision", expected, mib);
+    let (first, second, suffix) = match find_suffix_collision(digits) {
+        Ok(collision) => collision,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+    info!("Shared suffix: {}", suffix);
+    println!("{}, \"{}\"", first, compute_hash(first));
+    println!("{}, \"{}\"", second, compute_hash(second));
+    ExitCode::SUCCESS
+}
+
+fn run_uncheckpointed(
+    args: &Args,
+    config: &HashFinderConfig,
+) -> Result<(Vec<HashResult>, SearchOutcome), HashFinderError> {
+    if args.chained {
+        config.run_chained_with_outcome()
+    } else if args.sort {
+        config.run_sorted_with_outcome()
+    } else {
+        config.run_with_outcome()
+    }
+}
+
+#[cfg(feature = "checkpoint")]
+fn run_checkpointed(
+    args: &Args,
+    config: &HashFinderConfig,
+) -> Result<(Vec<HashResult>, SearchOutcome), HashFinderError> {
+    let Some(path) = &args.checkpoint_file else {
+        return run_uncheckpointed(args, config);
+    };
+
+    let mut checkpoint = if args.resume {
+        let checkpoint = SearchCheckpoint::load(path).map_err(|e| {
+            std::io::Error::new(
+                e.kind(),
+                format!("failed to load checkpoint {}: {}", path.display(), e),
+            )
+        })?;
+        info!(
+            "Resuming from checkpoint: last checked={}, found={}",
+            checkpoint.last_checked,
+            checkpoint.found.len()
+        );
+        checkpoint
+    } else {
+        SearchCheckpoint::new()
+    };
+
+    config.run_resumable_with_outcome(&mut checkpoint, Some(path))
+}
+
+fn verify(args: &Args, zeros: usize, self_suffix: bool, file: Option<&PathBuf>) -> ExitCode {
+    let mut config = HashFinderConfig::new()
+        .zeros(zeros)
+        .self_suffix(self_suffix)
+        .algorithm(args.algorithm)
+        .nonce_encoding(args.nonce_encoding);
+    if let Some(salt) = args.salt_bytes() {
+        config = config.salt(salt);
+    }
+    if let Some(template) = &args.template {
+        config = config.template(template.clone());
+    }
+    if let Some(key) = &args.hmac_key {
+        config = config.hmac_key(key.clone());
+    }
+    config = config.iterations(args.iterations);
+    if let Some(width) = args.pad_width {
+        config = config.pad_width(width);
+    }
+
+    let input = match file {
+        Some(path) => std::fs::read_to_string(path),
+        None => std::io::read_to_string(std::io::stdin()),
+    };
+    let input = match input {
+        Ok(input) => input,
+        Err(e) => {
+            eprintln!("Error: failed to read results: {}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let mut all_valid = true;
+    for line in input.lines().filter(|line| !line.trim().is_empty()) {
+        match line.parse::<HashResult>() {
+            Ok(result) => {
+                let valid = verify_result_with_config(&config, &result);
+                println!("{} {}", if valid { "OK" } else { "FAIL" }, result);
+                all_valid &= valid;
+            }
+            Err(e) => {
+                eprintln!("Error: {}", e);
+                all_valid = false;
+            }
+        }
+    }
+
+    info!("Verification finished, all valid: {}", all_valid);
+    if all_valid {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
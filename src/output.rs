@@ -0,0 +1,828 @@
+use crate::{hex_decode, hex_encode, HashFinderConfig, HashResult, WordResult};
+use std::fmt;
+use std::io::{self, Write};
+use std::str::FromStr;
+
+/// How search results are rendered by [`format_results`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OutputFormat {
+    /// `4163, "95d4...3000"` per line.
+    #[default]
+    Text,
+    /// A JSON array of `{"number": ..., "hash": ...}` objects.
+    Json,
+    /// `number,hash` rows with a header row.
+    Csv,
+}
+
+impl OutputFormat {
+    pub const ALL: &'static [OutputFormat] =
+        &[OutputFormat::Text, OutputFormat::Json, OutputFormat::Csv];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            OutputFormat::Text => "text",
+            OutputFormat::Json => "json",
+            OutputFormat::Csv => "csv",
+        }
+    }
+}
+
+impl fmt::Display for OutputFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for OutputFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        OutputFormat::ALL
+            .iter()
+            .copied()
+            .find(|format| format.name().eq_ignore_ascii_case(s))
+            .ok_or_else(|| {
+                let names: Vec<&str> = OutputFormat::ALL.iter().map(|f| f.name()).collect();
+                format!("unknown format '{}' (available: {})", s, names.join(", "))
+            })
+    }
+}
+
+/// How digests are rendered in output.
+///
+/// Matching always happens on the lowercase hex form, so this only changes presentation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum HashEncoding {
+    #[default]
+    LowerHex,
+    UpperHex,
+    /// Standard base64 with padding.
+    Base64,
+    /// Raw digest bytes, written to a separate file by [`write_raw_digests`]; rendered
+    /// as lowercase hex in text output.
+    RawBytes,
+}
+
+impl HashEncoding {
+    pub const ALL: &'static [HashEncoding] = &[
+        HashEncoding::LowerHex,
+        HashEncoding::UpperHex,
+        HashEncoding::Base64,
+        HashEncoding::RawBytes,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            HashEncoding::LowerHex => "lowerhex",
+            HashEncoding::UpperHex => "upperhex",
+            HashEncoding::Base64 => "base64",
+            HashEncoding::RawBytes => "raw-bytes-to-file",
+        }
+    }
+
+    pub fn encode(self, digest: &[u8]) -> String {
+        match self {
+            HashEncoding::LowerHex | HashEncoding::RawBytes => hex_encode(digest),
+            HashEncoding::UpperHex => hex_encode(digest).to_ascii_uppercase(),
+            HashEncoding::Base64 => base64_encode(digest),
+        }
+    }
+
+    /// Inverse of [`HashEncoding::encode`].
+    pub fn decode(self, encoded: &str) -> Result<Vec<u8>, String> {
+        match self {
+            HashEncoding::LowerHex | HashEncoding::UpperHex | HashEncoding::RawBytes => {
+                hex_decode(encoded)
+            }
+            HashEncoding::Base64 => base64_decode(encoded),
+        }
+    }
+
+    /// Renders a hex hash, keeping it as-is if it is not valid hex.
+    fn render(self, hash: &str) -> String {
+        match (self, hex_decode(hash)) {
+            (HashEncoding::LowerHex | HashEncoding::RawBytes, _) | (_, Err(_)) => hash.to_string(),
+            (_, Ok(digest)) => self.encode(&digest),
+        }
+    }
+}
+
+impl fmt::Display for HashEncoding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for HashEncoding {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        HashEncoding::ALL
+            .iter()
+            .copied()
+            .find(|encoding| encoding.name().eq_ignore_ascii_case(s))
+            .ok_or_else(|| {
+                let names: Vec<&str> = HashEncoding::ALL.iter().map(|e| e.name()).collect();
+                format!("unknown hash encoding '{}' (available: {})", s, names.join(", "))
+            })
+    }
+}
+
+const BASE64: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+fn base64_encode(bytes: &[u8]) -> String {
+    let mut out = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let b = [chunk[0], *chunk.get(1).unwrap_or(&0), *chunk.get(2).unwrap_or(&0)];
+        let n = (b[0] as u32) << 16 | (b[1] as u32) << 8 | b[2] as u32;
+        for i in 0..4 {
+            if i <= chunk.len() {
+                out.push(BASE64[(n >> (18 - 6 * i) & 0x3f) as usize] as char);
+            } else {
+                out.push('=');
+            }
+        }
+    }
+    out
+}
+
+fn base64_decode(encoded: &str) -> Result<Vec<u8>, String> {
+    if !encoded.len().is_multiple_of(4) {
+        return Err(format!("base64 length must be a multiple of 4 (got {})", encoded.len()));
+    }
+    let mut out = Vec::with_capacity(encoded.len() / 4 * 3);
+    let chunks = encoded.as_bytes().chunks(4);
+    let last = chunks.len().saturating_sub(1);
+    for (index, chunk) in chunks.enumerate() {
+        let padding = chunk.iter().rev().take_while(|&&c| c == b'=').count();
+        if padding > 0 && index != last {
+            return Err("base64 padding is only allowed at the end".to_string());
+        }
+        let mut n = 0u32;
+        for (i, &c) in chunk.iter().enumerate() {
+            let value = match c {
+                b'=' if i >= 4 - padding && padding <= 2 => 0,
+                _ => BASE64
+                    .iter()
+                    .position(|&b| b == c)
+                    .ok_or_else(|| format!("invalid base64 character '{}'", c as char))?
+                    as u32,
+            };
+            n = n << 6 | value;
+        }
+        out.extend_from_slice(&n.to_be_bytes()[1..4 - padding]);
+    }
+    Ok(out)
+}
+
+/// Writes the raw digest bytes of `results` back to back, in order.
+pub fn write_raw_digests<W: Write>(results: &[HashResult], mut writer: W) -> io::Result<()> {
+    for result in results {
+        let digest = result
+            .digest()
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        writer.write_all(&digest)?;
+    }
+    writer.flush()
+}
+
+/// Writes `results` to `out` in the given format, terminated by a newline.
+///
+/// `out` is flushed after each result, so a file being written can be followed while it
+/// grows. Hashes are hex strings, so neither JSON nor CSV output needs escaping.
+pub fn format_results(
+    results: &[HashResult],
+    fmt: OutputFormat,
+    out: &mut dyn Write,
+) -> io::Result<()> {
+    format_results_with_encoding(results, fmt, HashEncoding::LowerHex, out)
+}
+
+/// Like [`format_results`], rendering each hash with `encoding`.
+///
+/// Matched suffixes, attempt counts, trailing zeros and shards are included when the
+/// results carry them.
+pub fn format_results_with_encoding(
+    results: &[HashResult],
+    fmt: OutputFormat,
+    encoding: HashEncoding,
+    out: &mut dyn Write,
+) -> io::Result<()> {
+    match fmt {
+        OutputFormat::Text => {
+            for result in results {
+                write!(out, "{}, \"{}\"", result.number, encoding.render(&result.hash))?;
+                if let Some(suffix) = &result.suffix {
+                    write!(out, ", suffix={}", suffix)?;
+                }
+                if let Some(attempts) = result.attempts {
+                    write!(out, ", attempts={}", attempts)?;
+                }
+                if let Some(trailing_zeros) = result.trailing_zeros {
+                    write!(out, ", trailing_zeros={}", trailing_zeros)?;
+                }
+                if let Some(shard) = result.shard {
+                    write!(out, ", shard={}", shard)?;
+                }
+                writeln!(out)?;
+                out.flush()?;
+            }
+        }
+        OutputFormat::Json => {
+            out.write_all(b"[")?;
+            for (i, result) in results.iter().enumerate() {
+                if i > 0 {
+                    out.write_all(b",")?;
+                }
+                write!(
+                    out,
+                    "{{\"number\":{},\"hash\":\"{}\"",
+                    result.number,
+                    encoding.render(&result.hash)
+                )?;
+                if let Some(suffix) = &result.suffix {
+                    write!(out, ",\"suffix\":\"{}\"", suffix)?;
+                }
+                if let Some(attempts) = result.attempts {
+                    write!(out, ",\"attempts\":{}", attempts)?;
+                }
+                if let Some(trailing_zeros) = result.trailing_zeros {
+                    write!(out, ",\"trailing_zeros\":{}", trailing_zeros)?;
+                }
+                if let Some(shard) = result.shard {
+                    write!(
+                        out,
+                        ",\"shard\":{{\"index\":{},\"count\":{}}}",
+                        shard.index, shard.count
+                    )?;
+                }
+                out.write_all(b"}")?;
+                out.flush()?;
+            }
+            out.write_all(b"]\n")?;
+        }
+        OutputFormat::Csv => {
+            let with_suffix = results.iter().any(|r| r.suffix.is_some());
+            let with_attempts = results.iter().any(|r| r.attempts.is_some());
+            let with_trailing_zeros = results.iter().any(|r| r.trailing_zeros.is_some());
+            let with_shard = results.iter().any(|r| r.shard.is_some());
+            out.write_all(b"number,hash")?;
+            if with_suffix {
+                out.write_all(b",suffix")?;
+            }
+            if with_attempts {
+                out.write_all(b",attempts")?;
+            }
+            if with_trailing_zeros {
+                out.write_all(b",trailing_zeros")?;
+            }
+            out.write_all(if with_shard { b",shard\n" } else { b"\n" })?;
+            for result in results {
+                write!(out, "{},{}", result.number, encoding.render(&result.hash))?;
+                if with_suffix {
+                    out.write_all(b",")?;
+                    if let Some(suffix) = &result.suffix {
+                        out.write_all(suffix.as_bytes())?;
+                    }
+                }
+                if with_attempts {
+                    out.write_all(b",")?;
+                    if let Some(attempts) = result.attempts {
+                        write!(out, "{}", attempts)?;
+                    }
+                }
+                if with_trailing_zeros {
+                    out.write_all(b",")?;
+                    if let Some(trailing_zeros) = result.trailing_zeros {
+                        write!(out, "{}", trailing_zeros)?;
+                    }
+                }
+                if with_shard {
+                    out.write_all(b",")?;
+                    if let Some(shard) = result.shard {
+                        write!(out, "{}", shard)?;
+                    }
+                }
+                writeln!(out)?;
+                out.flush()?;
+            }
+        }
+    }
+    out.flush()
+}
+
+/// Like [`format_results_with_encoding`], but prefixes each link of a chain with its
+/// height, starting at 0.
+pub fn format_chain(
+    chain: &[HashResult],
+    fmt: OutputFormat,
+    encoding: HashEncoding,
+    out: &mut dyn Write,
+) -> io::Result<()> {
+    match fmt {
+        OutputFormat::Text => {
+            for (height, link) in chain.iter().enumerate() {
+                writeln!(out, "{}: {}, \"{}\"", height, link.number, encoding.render(&link.hash))?;
+                out.flush()?;
+            }
+        }
+        OutputFormat::Json => {
+            out.write_all(b"[")?;
+            for (height, link) in chain.iter().enumerate() {
+                if height > 0 {
+                    out.write_all(b",")?;
+                }
+                write!(
+                    out,
+                    "{{\"height\":{},\"number\":{},\"hash\":\"{}\"}}",
+                    height,
+                    link.number,
+                    encoding.render(&link.hash)
+                )?;
+                out.flush()?;
+            }
+            out.write_all(b"]\n")?;
+        }
+        OutputFormat::Csv => {
+            out.write_all(b"height,number,hash\n")?;
+            for (height, link) in chain.iter().enumerate() {
+                writeln!(out, "{},{},{}", height, link.number, encoding.render(&link.hash))?;
+                out.flush()?;
+            }
+        }
+    }
+    out.flush()
+}
+
+/// Like [`format_chain`], but for the results of several salts, each prefixed with the line
+/// its salt was read from.
+pub fn format_salted(
+    groups: &[(usize, Vec<HashResult>)],
+    fmt: OutputFormat,
+    encoding: HashEncoding,
+    out: &mut dyn Write,
+) -> io::Result<()> {
+    let rows = groups.iter().flat_map(|(line, results)| results.iter().map(move |r| (*line, r)));
+    match fmt {
+        OutputFormat::Text => {
+            for (line, result) in rows {
+                let hash = encoding.render(&result.hash);
+                writeln!(out, "{}: {}, \"{}\"", line, result.number, hash)?;
+                out.flush()?;
+            }
+        }
+        OutputFormat::Json => {
+            out.write_all(b"[")?;
+            for (i, (line, result)) in rows.enumerate() {
+                if i > 0 {
+                    out.write_all(b",")?;
+                }
+                write!(
+                    out,
+                    "{{\"line\":{},\"number\":{},\"hash\":\"{}\"}}",
+                    line,
+                    result.number,
+                    encoding.render(&result.hash)
+                )?;
+                out.flush()?;
+            }
+            out.write_all(b"]\n")?;
+        }
+        OutputFormat::Csv => {
+            out.write_all(b"line,number,hash\n")?;
+            for (line, result) in rows {
+                writeln!(out, "{},{},{}", line, result.number, encoding.render(&result.hash))?;
+                out.flush()?;
+            }
+        }
+    }
+    out.flush()
+}
+
+/// Like [`format_results_with_encoding`], but for the words of a wordlist search. Words are
+/// escaped as JSON strings and quoted in CSV when they need it.
+pub fn format_words(
+    results: &[WordResult],
+    fmt: OutputFormat,
+    encoding: HashEncoding,
+    out: &mut dyn Write,
+) -> io::Result<()> {
+    match fmt {
+        OutputFormat::Text => {
+            for result in results {
+                writeln!(out, "{}, \"{}\"", result.word, encoding.render(&result.hash))?;
+                out.flush()?;
+            }
+        }
+        OutputFormat::Json => {
+            out.write_all(b"[")?;
+            for (i, result) in results.iter().enumerate() {
+                if i > 0 {
+                    out.write_all(b",")?;
+                }
+                write!(
+                    out,
+                    "{{\"word\":{},\"hash\":\"{}\"}}",
+                    json_string(&result.word),
+                    encoding.render(&result.hash)
+                )?;
+                out.flush()?;
+            }
+            out.write_all(b"]\n")?;
+        }
+        OutputFormat::Csv => {
+            out.write_all(b"word,hash\n")?;
+            for result in results {
+                let word = if result.word.contains([',', '"', '\r', '\n']) {
+                    format!("\"{}\"", result.word.replace('"', "\"\""))
+                } else {
+                    result.word.clone()
+                };
+                writeln!(out, "{},{}", word, encoding.render(&result.hash))?;
+                out.flush()?;
+            }
+        }
+    }
+    out.flush()
+}
+
+/// `s` as a quoted JSON string.
+fn json_string(s: &str) -> String {
+    let mut quoted = String::with_capacity(s.len() + 2);
+    quoted.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            c if c < ' ' => quoted.push_str(&format!("\\u{:04x}", c as u32)),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+/// Like [`format_results_with_encoding`], adding each result's digests under the
+/// config's `also` constraints after its main hash.
+pub fn format_results_with_also(
+    results: &[HashResult],
+    config: &HashFinderConfig,
+    fmt: OutputFormat,
+    encoding: HashEncoding,
+    out: &mut dyn Write,
+) -> io::Result<()> {
+    match fmt {
+        OutputFormat::Text => {
+            for result in results {
+                write!(out, "{}, \"{}\"", result.number, encoding.render(&result.hash))?;
+                for (c, hash) in config.also.iter().zip(config.also_hashes(result.number)) {
+                    write!(out, ", {}=\"{}\"", c.algorithm, encoding.render(&hash))?;
+                }
+                writeln!(out)?;
+                out.flush()?;
+            }
+        }
+        OutputFormat::Json => {
+            out.write_all(b"[")?;
+            for (i, result) in results.iter().enumerate() {
+                if i > 0 {
+                    out.write_all(b",")?;
+                }
+                write!(
+                    out,
+                    "{{\"number\":{},\"hash\":\"{}\",\"also\":[",
+                    result.number,
+                    encoding.render(&result.hash)
+                )?;
+                let also = config.also.iter().zip(config.also_hashes(result.number));
+                for (j, (c, hash)) in also.enumerate() {
+                    if j > 0 {
+                        out.write_all(b",")?;
+                    }
+                    write!(
+                        out,
+                        "{{\"algorithm\":\"{}\",\"hash\":\"{}\"}}",
+                        c.algorithm,
+                        encoding.render(&hash)
+                    )?;
+                }
+                out.write_all(b"]}")?;
+                out.flush()?;
+            }
+            out.write_all(b"]\n")?;
+        }
+        OutputFormat::Csv => {
+            out.write_all(b"number,hash")?;
+            for c in &config.also {
+                write!(out, ",{}", c.algorithm)?;
+            }
+            writeln!(out)?;
+            for result in results {
+                write!(out, "{},{}", result.number, encoding.render(&result.hash))?;
+                for hash in config.also_hashes(result.number) {
+                    write!(out, ",{}", encoding.render(&hash))?;
+                }
+                writeln!(out)?;
+                out.flush()?;
+            }
+        }
+    }
+    out.flush()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Runs a writer-based formatter and returns what it wrote.
+    fn render(format: impl FnOnce(&mut dyn Write) -> io::Result<()>) -> String {
+        let mut out = Vec::new();
+        format(&mut out).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    fn sample() -> Vec<HashResult> {
+        vec![
+            HashResult::new(4163, "ab000".to_string()),
+            HashResult::new(11848, "cd000".to_string()),
+        ]
+    }
+
+    #[test]
+    fn test_format_text() {
+        assert_eq!(
+            render(|out| format_results(&sample(), OutputFormat::Text, out)),
+            "4163, \"ab000\"\n11848, \"cd000\"\n"
+        );
+    }
+
+    #[test]
+    fn test_format_json() {
+        assert_eq!(
+            render(|out| format_results(&sample(), OutputFormat::Json, out)),
+            "[{\"number\":4163,\"hash\":\"ab000\"},{\"number\":11848,\"hash\":\"cd000\"}]\n"
+        );
+        assert_eq!(render(|out| format_results(&[], OutputFormat::Json, out)), "[]\n");
+    }
+
+    #[test]
+    fn test_format_csv() {
+        assert_eq!(
+            render(|out| format_results(&sample(), OutputFormat::Csv, out)),
+            "number,hash\n4163,ab000\n11848,cd000\n"
+        );
+    }
+
+    /// Records the output length at each flush.
+    #[derive(Default)]
+    struct FlushLog {
+        written: Vec<u8>,
+        flushed_at: Vec<usize>,
+    }
+
+    impl Write for FlushLog {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.written.extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            self.flushed_at.push(self.written.len());
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_format_flushes_each_result() {
+        let mut log = FlushLog::default();
+        format_results(&sample(), OutputFormat::Text, &mut log).unwrap();
+        let first = "4163, \"ab000\"\n".len();
+        assert_eq!(&log.flushed_at[..2], [first, log.written.len()]);
+    }
+
+    #[test]
+    fn test_hash_encodings_round_trip() {
+        for len in [0, 1, 2, 3, 4, 20, 32, 64] {
+            let digest: Vec<u8> = (0..len).map(|i| (i * 37 + 11) as u8).collect();
+            for &encoding in HashEncoding::ALL {
+                let encoded = encoding.encode(&digest);
+                assert_eq!(encoding.decode(&encoded), Ok(digest.clone()), "{}", encoding);
+            }
+        }
+        assert_eq!(HashEncoding::Base64.encode(b"foob"), "Zm9vYg==");
+        assert_eq!(HashEncoding::UpperHex.encode(&[0xab, 0x00]), "AB00");
+        assert!(HashEncoding::Base64.decode("Zm9").is_err());
+        assert!(HashEncoding::Base64.decode("Zm9v!g==").is_err());
+        assert!(HashEncoding::Base64.decode("Zg==Zg==").is_err());
+    }
+
+    #[test]
+    fn test_format_with_encoding() {
+        let results = [HashResult::new(1, "ff00".to_string())];
+        assert_eq!(
+            render(|out| format_results_with_encoding(
+                &results,
+                OutputFormat::Text,
+                HashEncoding::UpperHex,
+                out
+            )),
+            "1, \"FF00\"\n"
+        );
+        assert_eq!(
+            render(|out| format_results_with_encoding(
+                &results,
+                OutputFormat::Csv,
+                HashEncoding::Base64,
+                out
+            )),
+            "number,hash\n1,/wA=\n"
+        );
+
+        let mut raw = Vec::new();
+        write_raw_digests(&results, &mut raw).unwrap();
+        assert_eq!(raw, [0xff, 0x00]);
+    }
+
+    #[test]
+    fn test_format_with_attempts() {
+        let results = [
+            HashResult::new(4163, "ab000".to_string()).with_attempts(4200),
+            HashResult::new(11848, "cd000".to_string()),
+        ];
+        assert_eq!(
+            render(|out| format_results(&results, OutputFormat::Text, out)),
+            "4163, \"ab000\", attempts=4200\n11848, \"cd000\"\n"
+        );
+        assert_eq!(
+            render(|out| format_results(&results[..1], OutputFormat::Json, out)),
+            "[{\"number\":4163,\"hash\":\"ab000\",\"attempts\":4200}]\n"
+        );
+        assert_eq!(
+            render(|out| format_results(&results, OutputFormat::Csv, out)),
+            "number,hash,attempts\n4163,ab000,4200\n11848,cd000,\n"
+        );
+    }
+
+    #[test]
+    fn test_format_with_suffix() {
+        let results = [
+            HashResult::new(4163, "ab000".to_string()).with_suffix("000".to_string()),
+            HashResult::new(99, "cdaaa".to_string())
+                .with_suffix("aaa".to_string())
+                .with_attempts(120),
+        ];
+        assert_eq!(
+            render(|out| format_results(&results, OutputFormat::Text, out)),
+            "4163, \"ab000\", suffix=000\n99, \"cdaaa\", suffix=aaa, attempts=120\n"
+        );
+        assert_eq!(
+            render(|out| format_results(&results[..1], OutputFormat::Json, out)),
+            "[{\"number\":4163,\"hash\":\"ab000\",\"suffix\":\"000\"}]\n"
+        );
+        assert_eq!(
+            render(|out| format_results(&results, OutputFormat::Csv, out)),
+            "number,hash,suffix,attempts\n4163,ab000,000,\n99,cdaaa,aaa,120\n"
+        );
+    }
+
+    #[test]
+    fn test_format_with_shard() {
+        let shard = crate::Shard::new(1, 4);
+        let results = [
+            HashResult::new(4163, "ab000".to_string()).with_shard(shard),
+            HashResult::new(11848, "cd000".to_string()),
+        ];
+        assert_eq!(
+            render(|out| format_results(&results, OutputFormat::Text, out)),
+            "4163, \"ab000\", shard=1/4\n11848, \"cd000\"\n"
+        );
+        assert_eq!(
+            render(|out| format_results(&results[..1], OutputFormat::Json, out)),
+            "[{\"number\":4163,\"hash\":\"ab000\",\"shard\":{\"index\":1,\"count\":4}}]\n"
+        );
+        assert_eq!(
+            render(|out| format_results(&results, OutputFormat::Csv, out)),
+            "number,hash,shard\n4163,ab000,1/4\n11848,cd000,\n"
+        );
+    }
+
+    #[test]
+    fn test_format_with_trailing_zeros() {
+        let results = [HashResult::new(4163, "ab000".to_string()).with_trailing_zeros(3)];
+        assert_eq!(
+            render(|out| format_results(&results, OutputFormat::Text, out)),
+            "4163, \"ab000\", trailing_zeros=3\n"
+        );
+        assert_eq!(
+            render(|out| format_results(&results, OutputFormat::Json, out)),
+            "[{\"number\":4163,\"hash\":\"ab000\",\"trailing_zeros\":3}]\n"
+        );
+        assert_eq!(
+            render(|out| format_results(&results, OutputFormat::Csv, out)),
+            "number,hash,trailing_zeros\n4163,ab000,3\n"
+        );
+    }
+
+    #[test]
+    fn test_format_chain() {
+        let chain = sample();
+        assert_eq!(
+            render(|out| format_chain(&chain, OutputFormat::Text, HashEncoding::LowerHex, out)),
+            "0: 4163, \"ab000\"\n1: 11848, \"cd000\"\n"
+        );
+        assert_eq!(
+            render(|out| format_chain(
+                &chain[..1],
+                OutputFormat::Json,
+                HashEncoding::LowerHex,
+                out
+            )),
+            "[{\"height\":0,\"number\":4163,\"hash\":\"ab000\"}]\n"
+        );
+        assert_eq!(
+            render(|out| format_chain(&chain, OutputFormat::Csv, HashEncoding::LowerHex, out)),
+            "height,number,hash\n0,4163,ab000\n1,11848,cd000\n"
+        );
+    }
+
+    #[test]
+    fn test_format_salted() {
+        let results = sample();
+        let groups = [(1, results[..1].to_vec()), (3, Vec::new()), (4, results[1..].to_vec())];
+        let salted = |fmt| render(|out| format_salted(&groups, fmt, HashEncoding::LowerHex, out));
+        assert_eq!(salted(OutputFormat::Text), "1: 4163, \"ab000\"\n4: 11848, \"cd000\"\n");
+        assert_eq!(
+            salted(OutputFormat::Json),
+            "[{\"line\":1,\"number\":4163,\"hash\":\"ab000\"},\
+             {\"line\":4,\"number\":11848,\"hash\":\"cd000\"}]\n"
+        );
+        assert_eq!(salted(OutputFormat::Csv), "line,number,hash\n1,4163,ab000\n4,11848,cd000\n");
+    }
+
+    #[test]
+    fn test_format_words() {
+        let results = [
+            WordResult::new("word1690".to_string(), "ab0000".to_string()),
+            WordResult::new("say \"hi\", \\o\t".to_string(), "cd0000".to_string()),
+        ];
+        let words = |fmt| render(|out| format_words(&results, fmt, HashEncoding::LowerHex, out));
+        assert_eq!(
+            words(OutputFormat::Text),
+            "word1690, \"ab0000\"\nsay \"hi\", \\o\t, \"cd0000\"\n"
+        );
+        assert_eq!(
+            words(OutputFormat::Json),
+            "[{\"word\":\"word1690\",\"hash\":\"ab0000\"},\
+             {\"word\":\"say \\\"hi\\\", \\\\o\\u0009\",\"hash\":\"cd0000\"}]\n"
+        );
+        assert_eq!(
+            words(OutputFormat::Csv),
+            "word,hash\nword1690,ab0000\n\"say \"\"hi\"\", \\o\t\",cd0000\n"
+        );
+    }
+
+    #[test]
+    fn test_format_results_with_also() {
+        let config = HashFinderConfig::new()
+            .also(crate::AlgorithmConstraint::new(crate::HashAlgorithm::Crc32, 1));
+        let results = [HashResult::new(123, "ab00".to_string())];
+        assert_eq!(
+            render(|out| format_results_with_also(
+                &results,
+                &config,
+                OutputFormat::Text,
+                HashEncoding::LowerHex,
+                out
+            )),
+            "123, \"ab00\", crc32=\"884863d2\"\n"
+        );
+        assert_eq!(
+            render(|out| format_results_with_also(
+                &results,
+                &config,
+                OutputFormat::Json,
+                HashEncoding::UpperHex,
+                out
+            )),
+            "[{\"number\":123,\"hash\":\"AB00\",\"also\":\
+             [{\"algorithm\":\"crc32\",\"hash\":\"884863D2\"}]}]\n"
+        );
+        assert_eq!(
+            render(|out| format_results_with_also(
+                &results,
+                &config,
+                OutputFormat::Csv,
+                HashEncoding::LowerHex,
+                out
+            )),
+            "number,hash,crc32\n123,ab00,884863d2\n"
+        );
+    }
+
+    #[test]
+    fn test_parse_format() {
+        assert_eq!("JSON".parse::<OutputFormat>(), Ok(OutputFormat::Json));
+        assert!("xml".parse::<OutputFormat>().is_err());
+    }
+}
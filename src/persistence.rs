@@ -0,0 +1,55 @@
+use crate::HashResult;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+/// Writes `results` to `path` as a pretty-printed JSON array, replacing any existing file.
+///
+/// ```no_run
+/// use rust_hash_finder::{find_hashes_from, load_results, save_results};
+/// use std::path::Path;
+///
+/// let path = Path::new("found.json");
+/// let results = find_hashes_from(4, 3, 1).unwrap();
+/// save_results(&results, path).unwrap();
+/// assert_eq!(load_results(path).unwrap(), results);
+/// ```
+pub fn save_results(results: &[HashResult], path: &Path) -> io::Result<()> {
+    fs::write(path, serde_json::to_string_pretty(results)?)
+}
+
+/// Reads a JSON array of results, as written by [`save_results`] or `--format json`.
+pub fn load_results(path: &Path) -> io::Result<Vec<HashResult>> {
+    let contents = fs::read_to_string(path)?;
+    Ok(serde_json::from_str(&contents)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::compute_hash;
+
+    #[test]
+    fn test_save_and_load_results_round_trip() {
+        let path = std::env::temp_dir().join(format!("hash-finder-{}.json", std::process::id()));
+        let results = vec![
+            HashResult::new(4163, compute_hash(4163)),
+            HashResult::new(11848, compute_hash(11848)).with_trailing_zeros(3),
+        ];
+
+        save_results(&results, &path).unwrap();
+        assert_eq!(load_results(&path).unwrap(), results);
+        save_results(&[], &path).unwrap();
+        assert!(load_results(&path).unwrap().is_empty());
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_load_results_rejects_bad_json() {
+        let path =
+            std::env::temp_dir().join(format!("hash-finder-{}-bad.json", std::process::id()));
+        fs::write(&path, "[{\"number\": \"4163\"}]").unwrap();
+        assert_eq!(load_results(&path).unwrap_err().kind(), io::ErrorKind::InvalidData);
+        fs::remove_file(&path).unwrap();
+    }
+}
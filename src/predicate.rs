@@ -0,0 +1,279 @@
+use crate::{
+    digest_below_target, digest_leading_zero_bits, hash_ends_with_palindrome, hash_zero_count,
+    hex_decode, HashFinderConfig, HashPattern, MatchDirection,
+};
+use std::fmt;
+use std::ops::Not;
+use std::sync::Arc;
+
+/// A condition on the lowercase hex hash, composed from [`HashPattern`]s with
+/// [`Predicate::and`], [`Predicate::or`] and `!`.
+///
+/// Combinators apply in the order they are chained, and short-circuit like `&&` and `||`:
+///
+/// ```
+/// use rust_hash_finder::Predicate;
+///
+/// // (ends with 000 and starts with a) or contains dead
+/// let predicate = Predicate::suffix("000")
+///     .and(Predicate::prefix("a"))
+///     .or(Predicate::contains("dead"));
+/// assert!(predicate.matches("a12000"));
+/// assert!(predicate.matches("0dead0"));
+/// assert!(!predicate.matches("b12000"));
+/// ```
+#[derive(Clone)]
+pub enum Predicate {
+    Pattern(HashPattern),
+    And(Box<Predicate>, Box<Predicate>),
+    Or(Box<Predicate>, Box<Predicate>),
+    Not(Box<Predicate>),
+    /// Arbitrary logic, for conditions no [`HashPattern`] covers.
+    Fn(Arc<dyn Fn(&str) -> bool + Send + Sync>),
+}
+
+impl Predicate {
+    pub fn prefix(prefix: &str) -> Self {
+        Predicate::Pattern(HashPattern::StartsWith(prefix.to_string()))
+    }
+
+    pub fn suffix(suffix: &str) -> Self {
+        Predicate::Pattern(HashPattern::EndsWith(suffix.to_string()))
+    }
+
+    pub fn contains(needle: &str) -> Self {
+        Predicate::Pattern(HashPattern::Contains(needle.to_string()))
+    }
+
+    pub fn trailing_zeros(zeros: usize) -> Self {
+        Predicate::Pattern(HashPattern::TrailingZeros(zeros))
+    }
+
+    pub fn leading_zeros(zeros: usize) -> Self {
+        Predicate::Pattern(HashPattern::LeadingZeros(zeros))
+    }
+
+    pub fn from_fn(f: impl Fn(&str) -> bool + Send + Sync + 'static) -> Self {
+        Predicate::Fn(Arc::new(f))
+    }
+
+    /// Matches when both match; `other` is only checked when `self` matches.
+    pub fn and(self, other: Predicate) -> Self {
+        Predicate::And(Box::new(self), Box::new(other))
+    }
+
+    /// Matches when either matches; `other` is only checked when `self` does not.
+    pub fn or(self, other: Predicate) -> Self {
+        Predicate::Or(Box::new(self), Box::new(other))
+    }
+
+    pub fn matches(&self, hash: &str) -> bool {
+        match self {
+            Predicate::Pattern(pattern) => pattern.matches(hash),
+            Predicate::And(a, b) => a.matches(hash) && b.matches(hash),
+            Predicate::Or(a, b) => a.matches(hash) || b.matches(hash),
+            Predicate::Not(p) => !p.matches(hash),
+            Predicate::Fn(f) => f(hash),
+        }
+    }
+
+    /// The match criteria of `config` as a predicate, all of which must hold, e.g. a prefix
+    /// and a suffix. Matches the same hashes as [`HashFinderConfig::matches`], ignoring
+    /// `also` constraints and `self_suffix`, which depend on the number rather than the hash.
+    pub fn from_config(config: &HashFinderConfig) -> Self {
+        let zero_count = config
+            .min_zero_count
+            .map(|min| Predicate::from_fn(move |hash| hash_zero_count(hash) >= min));
+        match (zero_count, Predicate::criteria(config)) {
+            (Some(zero_count), Some(criteria)) => zero_count.and(criteria),
+            (Some(zero_count), None) => zero_count,
+            (None, None) if config.self_suffix => Predicate::from_fn(|_| true),
+            (None, criteria) => criteria.unwrap_or_else(|| Predicate::trailing_zeros(0)),
+        }
+    }
+
+    /// The criteria of `config` apart from `min_zero_count`, or `None` when there are none.
+    fn criteria(config: &HashFinderConfig) -> Option<Self> {
+        #[cfg(feature = "regex")]
+        if let Some(regex) = &config.regex {
+            return Some(Predicate::Pattern(HashPattern::Regex(regex.clone())));
+        }
+        if let Some(target) = config.target {
+            return Some(Predicate::from_fn(move |hash| {
+                hex_decode(hash)
+                    .ok()
+                    .and_then(|digest| <[u8; 32]>::try_from(digest).ok())
+                    .is_some_and(|digest| digest_below_target(&digest, &target))
+            }));
+        }
+        if let Some(wildcard) = config.wildcard.clone() {
+            return Some(match config.direction {
+                MatchDirection::Trailing => {
+                    Predicate::from_fn(move |hash| wildcard.is_suffix_of(hash))
+                }
+                MatchDirection::Leading => {
+                    Predicate::from_fn(move |hash| wildcard.is_prefix_of(hash))
+                }
+            });
+        }
+        if let Some(len) = config.palindrome {
+            return Some(Predicate::from_fn(move |hash| hash_ends_with_palindrome(hash, len)));
+        }
+        if let Some(bits) = config.leading_zero_bits {
+            return Some(Predicate::from_fn(move |hash| {
+                hex_decode(hash).is_ok_and(|digest| digest_leading_zero_bits(&digest) >= bits)
+            }));
+        }
+
+        let mut all = Vec::new();
+        if let Some(prefix) = &config.prefix {
+            all.push(Predicate::prefix(prefix.as_str()));
+        }
+        if let Some(suffix) = &config.suffix {
+            all.push(Predicate::suffix(suffix.as_str()));
+        }
+        if let Some(suffixes) = &config.suffixes {
+            let any = suffixes.iter().map(Predicate::suffix).reduce(Predicate::or);
+            // An empty set matches nothing.
+            all.push(any.unwrap_or_else(|| Predicate::from_fn(|_| false)));
+        }
+        if let Some(needle) = &config.contains {
+            all.push(Predicate::contains(needle.as_str()));
+        }
+        all.into_iter().reduce(Predicate::and).or_else(|| {
+            (config.zeros > 0).then(|| match config.direction {
+                MatchDirection::Trailing => Predicate::trailing_zeros(config.zeros),
+                MatchDirection::Leading => Predicate::leading_zeros(config.zeros),
+            })
+        })
+    }
+}
+
+impl Not for Predicate {
+    type Output = Predicate;
+
+    fn not(self) -> Predicate {
+        Predicate::Not(Box::new(self))
+    }
+}
+
+impl From<HashPattern> for Predicate {
+    fn from(pattern: HashPattern) -> Self {
+        Predicate::Pattern(pattern)
+    }
+}
+
+impl fmt::Debug for Predicate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Predicate::Pattern(pattern) => write!(f, "{:?}", pattern),
+            Predicate::And(a, b) => write!(f, "({:?} && {:?})", a, b),
+            Predicate::Or(a, b) => write!(f, "({:?} || {:?})", a, b),
+            Predicate::Not(p) => write!(f, "!{:?}", p),
+            Predicate::Fn(_) => f.write_str("Fn(..)"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    fn counting(result: bool) -> (Predicate, Arc<AtomicUsize>) {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&calls);
+        let predicate = Predicate::from_fn(move |_| {
+            counter.fetch_add(1, Ordering::Relaxed);
+            result
+        });
+        (predicate, calls)
+    }
+
+    #[test]
+    fn test_combinators() {
+        let a = || Predicate::prefix("a");
+        let zeros = || Predicate::suffix("000");
+        let dead = || Predicate::contains("dead");
+
+        for (predicate, hash, expected) in [
+            (a().and(zeros()), "a1000", true),
+            (a().and(zeros()), "b1000", false),
+            (a().and(zeros()), "a1001", false),
+            (a().or(zeros()), "b1000", true),
+            (a().or(zeros()), "b1001", false),
+            (!a(), "b", true),
+            (!a(), "a", false),
+            (!a().and(zeros()), "b000", true),
+            (!(a().and(zeros())), "a000", false),
+            (!(a().and(zeros())), "b000", true),
+        ] {
+            assert_eq!(predicate.matches(hash), expected, "{:?} on {}", predicate, hash);
+        }
+
+        // Chaining groups left to right: (zeros && a) || dead, not zeros && (a || dead).
+        let chained = zeros().and(a()).or(dead());
+        assert!(chained.matches("bdead1"));
+        assert!(chained.matches("a12000"));
+        assert!(!chained.matches("b12000"));
+        let nested = zeros().and(a().or(dead()));
+        assert!(!nested.matches("bdead1"));
+        assert!(nested.matches("bdead000"));
+    }
+
+    #[test]
+    fn test_short_circuit() {
+        let (rhs, calls) = counting(true);
+        assert!(!Predicate::prefix("a").and(rhs).matches("b"));
+        assert_eq!(calls.load(Ordering::Relaxed), 0);
+
+        let (rhs, calls) = counting(true);
+        assert!(Predicate::prefix("a").and(rhs).matches("a"));
+        assert_eq!(calls.load(Ordering::Relaxed), 1);
+
+        let (rhs, calls) = counting(false);
+        assert!(Predicate::prefix("a").or(rhs).matches("a"));
+        assert_eq!(calls.load(Ordering::Relaxed), 0);
+
+        let (rhs, calls) = counting(false);
+        assert!(!Predicate::prefix("a").or(rhs).matches("b"));
+        assert_eq!(calls.load(Ordering::Relaxed), 1);
+    }
+
+    #[test]
+    fn test_from_config_agrees_with_config() {
+        let base = HashFinderConfig::new().max_results(1);
+        let configs = [
+            base.clone().zeros(2),
+            base.clone().zeros(2).direction(MatchDirection::Leading),
+            base.clone().prefix("a".parse().unwrap()).suffix("0".parse().unwrap()),
+            base.clone().contains("ab".parse().unwrap()),
+            base.clone()
+                .suffixes(["00", "f"].iter().map(|s| s.parse().unwrap()).collect()),
+            base.clone().leading_zero_bits(5),
+            base.clone().wildcard("a?0".parse().unwrap()),
+            base.clone()
+                .wildcard("?0".parse().unwrap())
+                .direction(MatchDirection::Leading),
+            base.clone().palindrome(3),
+            base.clone().target([0x0f; 32]),
+            base.clone().min_zero_count(9),
+            base.clone().zeros(1).min_zero_count(8),
+            base.clone().self_suffix(true),
+            base.clone().zeros(1).self_suffix(true),
+        ];
+        for config in &configs {
+            let predicate = Predicate::from_config(config);
+            for num in 1..2000 {
+                let hash = config.hash(num);
+                assert_eq!(predicate.matches(&hash), config.matches(&hash), "{:?}", predicate);
+            }
+        }
+    }
+
+    #[test]
+    fn test_send_sync() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<Predicate>();
+    }
+}
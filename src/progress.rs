@@ -0,0 +1,292 @@
+use std::fmt;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{mpsc, Arc};
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+
+/// Counters a running search updates, so progress can be read from another thread.
+///
+/// Clones share the same counters. Every hash computed is counted, so a search with
+/// progress attached is slightly slower than one without.
+///
+/// ```
+/// use rust_hash_finder::{HashFinderConfig, SearchProgress};
+///
+/// let progress = SearchProgress::new();
+/// let results = HashFinderConfig::new()
+///     .zeros(2)
+///     .max_results(1)
+///     .progress(progress.clone())
+///     .run()
+///     .unwrap();
+/// assert!(progress.hashes() > 0);
+/// assert!(progress.matches() >= results.len() as u64);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct SearchProgress {
+    hashes: Arc<AtomicU64>,
+    matches: Arc<AtomicU64>,
+}
+
+impl SearchProgress {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Hashes computed so far.
+    pub fn hashes(&self) -> u64 {
+        self.hashes.load(Ordering::Relaxed)
+    }
+
+    /// Matches found so far, which can exceed `max_results` by the few found concurrently
+    /// with the last one kept.
+    pub fn matches(&self) -> u64 {
+        self.matches.load(Ordering::Relaxed)
+    }
+
+    /// Counts one hash and returns the new total.
+    pub(crate) fn record_hash(&self) -> u64 {
+        self.hashes.fetch_add(1, Ordering::Relaxed) + 1
+    }
+
+    pub(crate) fn record_match(&self) {
+        self.matches.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+/// Progress handles are equal when they are clones sharing the same counters.
+impl PartialEq for SearchProgress {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.hashes, &other.hashes)
+    }
+}
+
+impl Eq for SearchProgress {}
+
+/// A snapshot of [`SearchProgress`] taken by a [`ProgressReporter`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ProgressReport {
+    pub elapsed: Duration,
+    pub hashes: u64,
+    pub matches: u64,
+}
+
+impl ProgressReport {
+    pub fn hashes_per_sec(&self) -> f64 {
+        match self.elapsed.as_secs_f64() {
+            secs if secs > 0.0 => self.hashes as f64 / secs,
+            _ => 0.0,
+        }
+    }
+}
+
+/// Progress passed to the callback of
+/// [`find_hashes_with_progress`](crate::find_hashes_with_progress).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ProgressEvent {
+    pub candidates_checked: u64,
+    /// Matches found so far, at most `max_results`.
+    pub results_found: usize,
+    pub elapsed: Duration,
+}
+
+/// `elapsed 5.0s, 12000000 hashes, 2400000 H/s, 1 found`
+impl fmt::Display for ProgressReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "elapsed {:.1}s, {} hashes, {:.0} H/s, {} found",
+            self.elapsed.as_secs_f64(),
+            self.hashes,
+            self.hashes_per_sec(),
+            self.matches
+        )
+    }
+}
+
+/// Totals for a finished search, from [`HashFinderConfig::run_with_stats`].
+///
+/// [`HashFinderConfig::run_with_stats`]: crate::HashFinderConfig::run_with_stats
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SearchStats {
+    pub elapsed: Duration,
+    pub candidates_checked: u64,
+    /// Candidates checked per second, or 0 when no time elapsed.
+    pub hash_rate: f64,
+    pub results_found: usize,
+}
+
+impl SearchStats {
+    pub fn new(elapsed: Duration, candidates_checked: u64, results_found: usize) -> Self {
+        let report = ProgressReport {
+            elapsed,
+            hashes: candidates_checked,
+            matches: results_found as u64,
+        };
+        SearchStats {
+            elapsed,
+            candidates_checked,
+            hash_rate: report.hashes_per_sec(),
+            results_found,
+        }
+    }
+}
+
+/// One `Label: value` line per field, as printed by `--stats`.
+impl fmt::Display for SearchStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Elapsed: {:.3}s", self.elapsed.as_secs_f64())?;
+        writeln!(f, "Candidates checked: {}", self.candidates_checked)?;
+        writeln!(f, "Hash rate: {:.0} H/s", self.hash_rate)?;
+        writeln!(f, "Results found: {}", self.results_found)
+    }
+}
+
+/// Throughput measured by [`benchmark`](crate::benchmark).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BenchmarkResult {
+    pub elapsed: Duration,
+    pub hashes: u64,
+    /// Hashes per second, or 0 when no time elapsed.
+    pub hashes_per_sec: f64,
+}
+
+impl BenchmarkResult {
+    pub fn new(elapsed: Duration, hashes: u64) -> Self {
+        let report = ProgressReport {
+            elapsed,
+            hashes,
+            matches: 0,
+        };
+        BenchmarkResult {
+            elapsed,
+            hashes,
+            hashes_per_sec: report.hashes_per_sec(),
+        }
+    }
+
+    /// Expected time to find one hash ending with `zeros` zeros at the measured rate; see
+    /// [`estimate_duration`](crate::estimate_duration).
+    pub fn expected_duration(&self, zeros: usize) -> Duration {
+        crate::estimate_duration(zeros, self.hashes_per_sec)
+    }
+}
+
+/// One `Label: value` line per field, like [`SearchStats`].
+impl fmt::Display for BenchmarkResult {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Elapsed: {:.3}s", self.elapsed.as_secs_f64())?;
+        writeln!(f, "Hashes computed: {}", self.hashes)?;
+        writeln!(f, "Hash rate: {:.0} H/s", self.hashes_per_sec)
+    }
+}
+
+/// Background thread passing a [`ProgressReport`] to a callback every `interval`.
+///
+/// Like the search timeout, the thread waits on a channel, so dropping the reporter stops
+/// it immediately instead of waiting out the interval.
+pub struct ProgressReporter {
+    done: Option<mpsc::Sender<()>>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl ProgressReporter {
+    pub fn start<F>(progress: SearchProgress, interval: Duration, mut report: F) -> Self
+    where
+        F: FnMut(ProgressReport) + Send + 'static,
+    {
+        let (done, done_rx) = mpsc::channel::<()>();
+        let started = Instant::now();
+        let thread = std::thread::spawn(move || {
+            while let Err(mpsc::RecvTimeoutError::Timeout) = done_rx.recv_timeout(interval) {
+                report(ProgressReport {
+                    elapsed: started.elapsed(),
+                    hashes: progress.hashes(),
+                    matches: progress.matches(),
+                });
+            }
+        });
+
+        ProgressReporter {
+            done: Some(done),
+            thread: Some(thread),
+        }
+    }
+}
+
+impl Drop for ProgressReporter {
+    fn drop(&mut self) {
+        self.done.take();
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::HashFinderConfig;
+    use std::sync::Mutex;
+
+    #[test]
+    fn test_progress_counts_hashes() {
+        let progress = SearchProgress::new();
+        let config = HashFinderConfig::new()
+            .zeros(3)
+            .max_results(2)
+            .end(5000)
+            .progress(progress.clone());
+        let results = config.run().unwrap();
+
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].number, 4163);
+        assert_eq!(progress.hashes(), 4999);
+        assert_eq!(progress.matches(), 1);
+    }
+
+    #[test]
+    fn test_reporter_reports_and_stops() {
+        let reports = Arc::new(Mutex::new(Vec::new()));
+        let progress = SearchProgress::new();
+        let reporter = {
+            let reports = Arc::clone(&reports);
+            ProgressReporter::start(progress.clone(), Duration::from_millis(10), move |r| {
+                reports.lock().unwrap().push(r)
+            })
+        };
+
+        progress.record_hash();
+        std::thread::sleep(Duration::from_millis(50));
+        drop(reporter);
+
+        let count = reports.lock().unwrap().len();
+        assert!(count > 0);
+        std::thread::sleep(Duration::from_millis(30));
+        assert_eq!(reports.lock().unwrap().len(), count);
+        assert!(reports.lock().unwrap().iter().all(|r| r.hashes <= 1));
+    }
+
+    #[test]
+    fn test_report_display() {
+        let report = ProgressReport {
+            elapsed: Duration::from_secs(5),
+            hashes: 10_000,
+            matches: 2,
+        };
+        assert_eq!(report.hashes_per_sec(), 2000.0);
+        assert_eq!(report.to_string(), "elapsed 5.0s, 10000 hashes, 2000 H/s, 2 found");
+    }
+
+    #[test]
+    fn test_stats_display() {
+        let stats = SearchStats::new(Duration::from_millis(2500), 10_000, 3);
+        assert_eq!(stats.hash_rate, 4000.0);
+        assert_eq!(
+            stats.to_string(),
+            "Elapsed: 2.500s\nCandidates checked: 10000\nHash rate: 4000 H/s\nResults found: 3\n"
+        );
+        assert_eq!(SearchStats::new(Duration::ZERO, 5, 0).hash_rate, 0.0);
+    }
+}
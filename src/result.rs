@@ -0,0 +1,243 @@
+use crate::Shard;
+use std::fmt;
+use std::str::FromStr;
+
+/// A number together with the hash that matched the search criteria.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct HashResult {
+    pub number: u64,
+    pub hash: String,
+    /// Which of several alternative suffixes the hash ends with; only set by searches
+    /// over a [`SuffixSet`](crate::SuffixSet).
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+    pub suffix: Option<String>,
+    /// Hashes computed across all threads by the time this match was found; only set
+    /// when the search tracks [`SearchProgress`](crate::SearchProgress).
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+    pub attempts: Option<u64>,
+    /// Trailing zeros of the hash; only set by
+    /// [`HashFinderConfig::run_top_k_zeros`](crate::HashFinderConfig::run_top_k_zeros),
+    /// which ranks results by them.
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+    pub trailing_zeros: Option<usize>,
+    /// The shard of a sharded search that found this match, so the outputs of several
+    /// shards can be merged later.
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+    pub shard: Option<Shard>,
+}
+
+impl HashResult {
+    pub fn new(number: u64, hash: String) -> Self {
+        HashResult {
+            number,
+            hash,
+            suffix: None,
+            attempts: None,
+            trailing_zeros: None,
+            shard: None,
+        }
+    }
+
+    pub fn with_suffix(mut self, suffix: String) -> Self {
+        self.suffix = Some(suffix);
+        self
+    }
+
+    pub fn with_attempts(mut self, attempts: u64) -> Self {
+        self.attempts = Some(attempts);
+        self
+    }
+
+    pub fn with_trailing_zeros(mut self, trailing_zeros: usize) -> Self {
+        self.trailing_zeros = Some(trailing_zeros);
+        self
+    }
+
+    pub fn with_shard(mut self, shard: Shard) -> Self {
+        self.shard = Some(shard);
+        self
+    }
+
+    /// The digest bytes behind the hex `hash`.
+    pub fn digest(&self) -> Result<Vec<u8>, String> {
+        crate::hex_decode(&self.hash)
+    }
+}
+
+/// Formats as the CLI output line: `4163, "95d4...3000"`, followed by `, suffix=HEX`,
+/// `, attempts=N`, `, trailing_zeros=N` and `, shard=I/N` when those are known.
+impl fmt::Display for HashResult {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}, \"{}\"", self.number, self.hash)?;
+        if let Some(suffix) = &self.suffix {
+            write!(f, ", suffix={}", suffix)?;
+        }
+        if let Some(attempts) = self.attempts {
+            write!(f, ", attempts={}", attempts)?;
+        }
+        if let Some(trailing_zeros) = self.trailing_zeros {
+            write!(f, ", trailing_zeros={}", trailing_zeros)?;
+        }
+        if let Some(shard) = self.shard {
+            write!(f, ", shard={}", shard)?;
+        }
+        Ok(())
+    }
+}
+
+/// Parses the CLI output line format produced by `Display`.
+impl FromStr for HashResult {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (number, hash) = s
+            .split_once(',')
+            .ok_or_else(|| format!("expected `number, \"hash\"`, got '{}'", s))?;
+        let number = number
+            .trim()
+            .parse::<u64>()
+            .map_err(|e| format!("invalid number '{}': {}", number.trim(), e))?;
+        let (hash, shard) = match hash.rsplit_once(", shard=") {
+            Some((hash, shard)) => (hash, Some(shard.parse::<Shard>()?)),
+            None => (hash, None),
+        };
+        let (hash, trailing_zeros) = match hash.rsplit_once(", trailing_zeros=") {
+            Some((hash, zeros)) => {
+                let zeros = zeros
+                    .trim()
+                    .parse::<usize>()
+                    .map_err(|e| format!("invalid trailing_zeros '{}': {}", zeros.trim(), e))?;
+                (hash, Some(zeros))
+            }
+            None => (hash, None),
+        };
+        let (hash, attempts) = match hash.rsplit_once(", attempts=") {
+            Some((hash, attempts)) => {
+                let attempts = attempts
+                    .trim()
+                    .parse::<u64>()
+                    .map_err(|e| format!("invalid attempts '{}': {}", attempts.trim(), e))?;
+                (hash, Some(attempts))
+            }
+            None => (hash, None),
+        };
+        let (hash, suffix) = match hash.rsplit_once(", suffix=") {
+            Some((hash, suffix)) => (hash, Some(suffix.trim().to_string())),
+            None => (hash, None),
+        };
+        let hash = hash.trim();
+        let hash = hash
+            .strip_prefix('"')
+            .and_then(|h| h.strip_suffix('"'))
+            .unwrap_or(hash);
+        Ok(HashResult {
+            number,
+            hash: hash.to_string(),
+            suffix,
+            attempts,
+            trailing_zeros,
+            shard,
+        })
+    }
+}
+
+/// A word that matched the search criteria, from a search over a wordlist instead of
+/// numbers.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct WordResult {
+    pub word: String,
+    pub hash: String,
+}
+
+impl WordResult {
+    pub fn new(word: String, hash: String) -> Self {
+        WordResult { word, hash }
+    }
+}
+
+/// Formats as the CLI output line: `word, "95d4...3000"`.
+impl fmt::Display for WordResult {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}, \"{}\"", self.word, self.hash)
+    }
+}
+
+impl From<(u64, String)> for HashResult {
+    fn from((number, hash): (u64, String)) -> Self {
+        HashResult::new(number, hash)
+    }
+}
+
+impl From<HashResult> for (u64, String) {
+    fn from(result: HashResult) -> Self {
+        (result.number, result.hash)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display_matches_cli_format() {
+        let result = HashResult::from((4163, "abc000".to_string()));
+        assert_eq!(result.to_string(), "4163, \"abc000\"");
+        assert_eq!(result.to_string().parse::<HashResult>(), Ok(result.clone()));
+        assert_eq!(<(u64, String)>::from(result), (4163, "abc000".to_string()));
+    }
+
+    #[test]
+    fn test_word_result_display() {
+        let result = WordResult::new("word1690".to_string(), "abc000".to_string());
+        assert_eq!(result.to_string(), "word1690, \"abc000\"");
+    }
+
+    #[test]
+    fn test_attempts_round_trip() {
+        let result = HashResult::new(4163, "abc000".to_string()).with_attempts(5000);
+        assert_eq!(result.to_string(), "4163, \"abc000\", attempts=5000");
+        assert_eq!(result.to_string().parse::<HashResult>(), Ok(result));
+        assert!("4163, \"abc000\", attempts=x".parse::<HashResult>().is_err());
+    }
+
+    #[test]
+    fn test_shard_round_trip() {
+        let result = HashResult::new(4163, "abc000".to_string())
+            .with_attempts(5000)
+            .with_shard(Shard::new(1, 4));
+        assert_eq!(result.to_string(), "4163, \"abc000\", attempts=5000, shard=1/4");
+        assert_eq!(result.to_string().parse::<HashResult>(), Ok(result));
+        assert!("4163, \"abc000\", shard=1".parse::<HashResult>().is_err());
+    }
+
+    #[test]
+    fn test_suffix_round_trip() {
+        let result = HashResult::new(4163, "abc000".to_string()).with_suffix("000".to_string());
+        assert_eq!(result.to_string(), "4163, \"abc000\", suffix=000");
+        assert_eq!(result.to_string().parse::<HashResult>(), Ok(result.clone()));
+
+        let result = result.with_attempts(5000);
+        assert_eq!(result.to_string(), "4163, \"abc000\", suffix=000, attempts=5000");
+        assert_eq!(result.to_string().parse::<HashResult>(), Ok(result));
+    }
+
+    #[test]
+    fn test_trailing_zeros_round_trip() {
+        let result = HashResult::new(4163, "abc000".to_string()).with_trailing_zeros(3);
+        assert_eq!(result.to_string(), "4163, \"abc000\", trailing_zeros=3");
+        assert_eq!(result.to_string().parse::<HashResult>(), Ok(result.clone()));
+
+        let result = result.with_attempts(5000);
+        assert_eq!(result.to_string(), "4163, \"abc000\", attempts=5000, trailing_zeros=3");
+        assert_eq!(result.to_string().parse::<HashResult>(), Ok(result));
+        assert!("4163, \"abc000\", trailing_zeros=-1".parse::<HashResult>().is_err());
+    }
+
+    #[test]
+    fn test_parse_rejects_malformed_lines() {
+        assert!("4163".parse::<HashResult>().is_err());
+        assert!("abc, \"000\"".parse::<HashResult>().is_err());
+    }
+}
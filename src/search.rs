@@ -0,0 +1,231 @@
+use crate::{CancellationToken, HashResult};
+use rayon::prelude::*;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc;
+#[cfg(not(feature = "atomics"))]
+use std::sync::Arc;
+use std::thread::JoinHandle;
+use std::time::Duration;
+use tracing::{debug, info, Span};
+
+#[cfg(not(feature = "atomics"))]
+use crossbeam_channel::bounded;
+
+/// Scans `candidates` in parallel until `max_results` numbers accepted by `matcher` are
+/// found or the candidates run out.
+///
+/// `matcher` returns the result to report for a matching number and `None` otherwise.
+///
+/// Each Rayon worker collects its matches in its own `Vec` and the vectors are merged once
+/// the scan ends, so finding a result takes no lock. Thread-local storage would do the same
+/// but is shared by every search running on a pool, so concurrent searches would mix their
+/// results.
+#[cfg(feature = "atomics")]
+pub(crate) fn search<I, F>(candidates: I, max_results: usize, matcher: F) -> Vec<HashResult>
+where
+    I: ParallelIterator<Item = u64>,
+    F: Fn(u64) -> Option<HashResult> + Sync,
+{
+    info!("Starting hash search with atomics implementation");
+
+    let found_count = AtomicUsize::new(0);
+    // Worker threads do not inherit the caller's span, so matches enter it themselves; an
+    // explicit `parent:` would leave the event out of the JSON log's `spans` list.
+    let span = Span::current();
+
+    let results = candidates
+        .take_any_while(|_| found_count.load(Ordering::Acquire) < max_results)
+        .filter_map(|num| {
+            let result = matcher(num)?;
+            let current = claim_slot(&found_count, max_results).ok()?;
+
+            let _entered = span.enter();
+            debug!(num, hash = %result.hash, "Found hash");
+            if current + 1 >= max_results {
+                info!("Reached target of {} results", max_results);
+            }
+            Some(result)
+        })
+        .fold(Vec::new, |mut found, result| {
+            found.push(result);
+            found
+        })
+        .reduce(Vec::new, |mut found, mut more| {
+            found.append(&mut more);
+            found
+        });
+
+    info!("Search completed, found {} results", results.len());
+    results
+}
+
+/// Scans `candidates` in parallel until `max_results` numbers accepted by `matcher` are
+/// found or the candidates run out.
+///
+/// `matcher` returns the result to report for a matching number and `None` otherwise.
+///
+/// The default backend, used by any `std` build without `atomics`.
+#[cfg(not(feature = "atomics"))]
+pub(crate) fn search<I, F>(candidates: I, max_results: usize, matcher: F) -> Vec<HashResult>
+where
+    I: ParallelIterator<Item = u64>,
+    F: Fn(u64) -> Option<HashResult> + Sync,
+{
+    info!("Starting hash search with crossbeam-channel implementation");
+
+    let (tx, rx) = bounded::<HashResult>(100);
+    let found_count = Arc::new(AtomicUsize::new(0));
+    let found_count_clone = Arc::clone(&found_count);
+    // Worker threads do not inherit the caller's span, so matches enter it themselves; an
+    // explicit `parent:` would leave the event out of the JSON log's `spans` list.
+    let span = Span::current();
+
+    let consumer_span = span.clone();
+    let consumer = std::thread::spawn(move || {
+        let _entered = consumer_span.enter();
+        let mut results = Vec::new();
+        for result in rx {
+            debug!(num = result.number, hash = %result.hash, "Received hash");
+            results.push(result);
+        }
+        results
+    });
+
+    candidates.find_any(|&num| {
+        if found_count_clone.load(Ordering::Acquire) >= max_results {
+            return true;
+        }
+
+        if let Some(result) = matcher(num) {
+            let Ok(current) = claim_slot(&found_count_clone, max_results) else {
+                return true;
+            };
+
+            let _entered = span.enter();
+            debug!(num, hash = %result.hash, "Found hash");
+            let _ = tx.send(result);
+
+            if current + 1 >= max_results {
+                info!("Reached target of {} results", max_results);
+                return true;
+            }
+        }
+
+        false
+    });
+
+    drop(tx);
+    let results = consumer.join().unwrap();
+    info!("Search completed, found {} results", results.len());
+    results
+}
+
+/// Reserves one of the `max_results` result slots, returning its index, or `Err` once all
+/// are taken.
+///
+/// The count is only incremented while below `max_results`, so it never overshoots and
+/// every reserved slot is filled: searches return exactly `max_results` results unless
+/// the candidates run out or the search is stopped.
+fn claim_slot(found_count: &AtomicUsize, max_results: usize) -> Result<usize, usize> {
+    found_count.fetch_update(Ordering::AcqRel, Ordering::Acquire, |count| {
+        (count < max_results).then_some(count + 1)
+    })
+}
+
+/// Returns the first candidate accepted by `matcher`, without any result collection.
+///
+/// Shared by both implementations: a single hit needs neither a counter nor a channel.
+pub(crate) fn search_first<I, F>(candidates: I, matcher: F) -> Option<HashResult>
+where
+    I: ParallelIterator<Item = u64>,
+    F: Fn(u64) -> Option<HashResult> + Sync,
+{
+    info!("Starting search for the first matching hash");
+
+    let found = candidates.find_map_any(&matcher);
+
+    if let Some(result) = &found {
+        debug!(num = result.number, hash = %result.hash, "Found hash");
+    }
+    found
+}
+
+/// Stop condition for a search: a caller's cancellation token and an optional timeout.
+///
+/// The timer thread waits on a channel rather than sleeping, so dropping the timer wakes
+/// it immediately and the join never waits out the full timeout.
+pub(crate) struct SearchTimer {
+    cancellation: Option<CancellationToken>,
+    stop: Option<CancellationToken>,
+    expired: CancellationToken,
+    done: Option<mpsc::Sender<()>>,
+    timer: Option<JoinHandle<()>>,
+}
+
+impl SearchTimer {
+    /// Starts a timer for `timeout`; `None` never expires.
+    pub(crate) fn start(
+        timeout: Option<Duration>,
+        cancellation: Option<CancellationToken>,
+    ) -> Self {
+        let expired = CancellationToken::new();
+        let Some(timeout) = timeout else {
+            return SearchTimer {
+                cancellation,
+                stop: None,
+                expired,
+                done: None,
+                timer: None,
+            };
+        };
+
+        let (done, done_rx) = mpsc::channel::<()>();
+        let expired_clone = expired.clone();
+        let timer = std::thread::spawn(move || {
+            if let Err(mpsc::RecvTimeoutError::Timeout) = done_rx.recv_timeout(timeout) {
+                info!("Timeout of {:?} reached, stopping search", timeout);
+                expired_clone.cancel();
+            }
+        });
+
+        SearchTimer {
+            cancellation,
+            stop: None,
+            expired,
+            done: Some(done),
+            timer: Some(timer),
+        }
+    }
+
+    /// Also stops once `stop` is cancelled, for an owner that ends the search itself
+    /// alongside the caller's token.
+    pub(crate) fn stop_on(mut self, stop: CancellationToken) -> Self {
+        self.stop = Some(stop);
+        self
+    }
+
+    /// Whether the timeout has elapsed.
+    pub(crate) fn timed_out(&self) -> bool {
+        self.expired.is_cancelled()
+    }
+
+    /// Whether the timeout has elapsed or the caller cancelled the search.
+    pub(crate) fn should_stop(&self) -> bool {
+        self.expired.is_cancelled()
+            || self
+                .cancellation
+                .iter()
+                .chain(&self.stop)
+                .any(CancellationToken::is_cancelled)
+    }
+}
+
+impl Drop for SearchTimer {
+    fn drop(&mut self) {
+        // Disconnecting the channel wakes the timer thread early.
+        self.done.take();
+        if let Some(timer) = self.timer.take() {
+            let _ = timer.join();
+        }
+    }
+}
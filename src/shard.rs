@@ -0,0 +1,98 @@
+use std::fmt;
+use std::str::FromStr;
+
+/// Numbers per block a sharded search deals out unless `chunk_size` is set: block `b` is
+/// `[b * SHARD_BLOCK, (b + 1) * SHARD_BLOCK)` and belongs to shard `b % count`.
+pub const SHARD_BLOCK: u64 = 1 << 20;
+
+/// One of `count` interleaved slices of the search space, so several machines can split a
+/// search without coordinating: shard `index` owns every block `b` with
+/// `b % count == index`, and together the shards cover each number exactly once.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Shard {
+    pub index: u64,
+    pub count: u64,
+}
+
+impl Shard {
+    pub fn new(index: u64, count: u64) -> Self {
+        Shard { index, count }
+    }
+
+    /// Whether `block` belongs to this shard.
+    pub fn owns_block(self, block: u64) -> bool {
+        block % self.count == self.index
+    }
+
+    /// The first block in `first..=last` this shard owns and how many it owns there, every
+    /// `count`th block from that one.
+    pub(crate) fn blocks(self, first: u64, last: u64) -> (u64, u64) {
+        let offset = first % self.count;
+        let skip = match self.index.checked_sub(offset) {
+            Some(skip) => skip,
+            None => self.count - offset + self.index,
+        };
+        match first.checked_add(skip) {
+            Some(owned) if owned <= last => (owned, (last - owned) / self.count + 1),
+            _ => (first, 0),
+        }
+    }
+}
+
+/// Formats as `index/count`, e.g. `0/4`.
+impl fmt::Display for Shard {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}", self.index, self.count)
+    }
+}
+
+impl FromStr for Shard {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (index, count) = s
+            .split_once('/')
+            .ok_or_else(|| format!("expected `index/count`, got '{}'", s))?;
+        let parse = |part: &str| {
+            part.trim()
+                .parse::<u64>()
+                .map_err(|e| format!("invalid shard '{}': {}", s, e))
+        };
+        Ok(Shard::new(parse(index)?, parse(count)?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_blocks_match_owns_block() {
+        for count in 1..=5 {
+            for index in 0..count {
+                let shard = Shard::new(index, count);
+                for (first, last) in [(0, 0), (0, 20), (3, 17), (7, 8), (9, 9)] {
+                    let (owned, len) = shard.blocks(first, last);
+                    let blocks: Vec<u64> = (0..len).map(|i| owned + i * count).collect();
+                    let expected: Vec<u64> =
+                        (first..=last).filter(|&b| shard.owns_block(b)).collect();
+                    assert_eq!(blocks, expected, "{} of {}..={}", shard, first, last);
+                }
+            }
+        }
+        // u64::MAX is a multiple of 3, so shard 1 would start past it.
+        assert_eq!(Shard::new(1, 3).blocks(u64::MAX - 1, u64::MAX), (u64::MAX - 1, 0));
+        assert_eq!(Shard::new(0, 3).blocks(u64::MAX - 1, u64::MAX), (u64::MAX, 1));
+        assert_eq!(Shard::new(0, u64::MAX).blocks(5, u64::MAX), (u64::MAX, 1));
+    }
+
+    #[test]
+    fn test_display_round_trip() {
+        let shard = Shard::new(1, 4);
+        assert_eq!(shard.to_string(), "1/4");
+        assert_eq!("1/4".parse(), Ok(shard));
+        assert!("1".parse::<Shard>().is_err());
+        assert!("a/4".parse::<Shard>().is_err());
+    }
+}
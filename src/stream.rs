@@ -0,0 +1,188 @@
+use crate::search::SearchTimer;
+use crate::{CancellationToken, HashFinderConfig, HashFinderError, HashResult};
+use crossbeam_channel::{bounded, Receiver};
+use std::thread::JoinHandle;
+use tracing::{debug, info};
+
+const STREAM_CAPACITY: usize = 100;
+
+/// Lazy iterator over matching [`HashResult`]s, yielded as they are discovered.
+///
+/// A background thread drives the parallel search and hands results over a bounded
+/// channel, so the search only runs ahead of the consumer by a fixed amount. Dropping
+/// the stream stops the search.
+///
+/// ```
+/// use rust_hash_finder::HashStream;
+///
+/// let found: Vec<_> = HashStream::new(2).unwrap().take(3).collect();
+/// assert_eq!(found.len(), 3);
+/// ```
+pub struct HashStream {
+    rx: Option<Receiver<HashResult>>,
+    stop: CancellationToken,
+    producer: Option<JoinHandle<()>>,
+}
+
+/// [`HashStream`] under the name searches usually start from.
+///
+/// ```
+/// use rust_hash_finder::{HashFinder, HashFinderConfig};
+///
+/// let config = HashFinderConfig::new().zeros(2).salt("docs:");
+/// let found = HashFinder::with_config(config).unwrap().find(|r| r.hash.contains('a'));
+/// assert!(found.is_some_and(|r| r.hash.ends_with("00")));
+/// ```
+pub type HashFinder = HashStream;
+
+impl HashStream {
+    /// Streams SHA-256 matches ending with `zeros` zeros.
+    ///
+    /// Fails with [`HashFinderError::InvalidZeroCount`] when `zeros` is 0 or above 64.
+    pub fn new(zeros: usize) -> Result<Self, HashFinderError> {
+        crate::check_zeros(zeros)?;
+        Self::with_config(HashFinderConfig::default().zeros(zeros))
+    }
+
+    /// Streams matches for `config`, searching on its `threads` and visiting numbers in
+    /// the order its `randomize`, `shard` and `chunk_size` give them, as
+    /// [`HashFinderConfig::run`] does. `max_results` and `timeout` are ignored, but
+    /// cancelling the config's token ends the stream, as does exhausting a bounded range.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the config is invalid apart from `max_results` (see
+    /// [`HashFinderConfig::build`]), or if its thread pool cannot be built.
+    pub fn with_config(config: HashFinderConfig) -> Result<Self, HashFinderError> {
+        let config = config.max_results(usize::MAX).build()?;
+        let pool = config.thread_pool()?;
+        let (tx, rx) = bounded::<HashResult>(STREAM_CAPACITY);
+        let stop = CancellationToken::new();
+        let stop_clone = stop.clone();
+
+        let producer = std::thread::spawn(move || {
+            info!("Starting hash stream at {}", config.start);
+            let timer =
+                SearchTimer::start(None, config.cancellation.clone()).stop_on(stop_clone);
+            let matcher = config.matcher();
+            // Results go straight to the channel, so the search collects none itself.
+            config.search_candidates(pool.as_ref(), &timer, |num| {
+                let result = matcher(num)?;
+                let result = match config.shard {
+                    Some(shard) => result.with_shard(shard),
+                    None => result,
+                };
+                debug!(num, hash = %result.hash, "Found hash");
+                // A send error means the stream was dropped, which also stops the timer.
+                let _ = tx.send(result);
+                None
+            });
+
+            info!("Hash stream stopped");
+        });
+
+        Ok(HashStream {
+            rx: Some(rx),
+            stop,
+            producer: Some(producer),
+        })
+    }
+}
+
+impl Iterator for HashStream {
+    type Item = HashResult;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.rx.as_ref()?.recv().ok()
+    }
+}
+
+impl Drop for HashStream {
+    fn drop(&mut self) {
+        self.stop.cancel();
+        // Dropping the receiver unblocks a producer waiting on a full channel.
+        self.rx.take();
+        if let Some(producer) = self.producer.take() {
+            let _ = producer.join();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{compute_hash, hash_matches_difficulty};
+
+    #[test]
+    fn test_stream_yields_valid_results() {
+        for result in HashStream::new(3).unwrap().take(3) {
+            assert_eq!(result.hash, compute_hash(result.number));
+            assert_eq!(hash_matches_difficulty(&result.hash, 3), Ok(true));
+        }
+    }
+
+    #[test]
+    fn test_hash_finder_with_config() {
+        let config = HashFinderConfig::new().zeros(3).end(20_000);
+        let mut numbers: Vec<u64> =
+            HashFinder::with_config(config.clone()).unwrap().map(|r| r.number).collect();
+        numbers.sort_unstable();
+        assert_eq!(numbers, [4163, 11848, 12843, 13467]);
+        assert_eq!(HashFinder::new(2).unwrap().take(10).count(), 10);
+
+        // Threads, chunks and a shuffled order only change how the range is visited.
+        for config in [
+            config.clone().threads(2),
+            config.clone().chunk_size(1_000),
+            config.clone().randomize(7).chunk_size(1_000),
+        ] {
+            let mut found: Vec<u64> =
+                HashFinder::with_config(config).unwrap().map(|r| r.number).collect();
+            found.sort_unstable();
+            assert_eq!(found, numbers);
+        }
+    }
+
+    #[test]
+    fn test_stream_shards() {
+        let config = HashFinderConfig::new().zeros(2).end(50_000).chunk_size(1000);
+        let mut whole: Vec<u64> =
+            HashFinder::with_config(config.clone()).unwrap().map(|r| r.number).collect();
+        whole.sort_unstable();
+
+        let mut union = Vec::new();
+        for index in 0..3 {
+            let shard = crate::Shard::new(index, 3);
+            for result in HashFinder::with_config(config.clone().shard(index, 3)).unwrap() {
+                assert_eq!(result.shard, Some(shard));
+                assert!(shard.owns_block(result.number / 1000));
+                union.push(result.number);
+            }
+        }
+        union.sort_unstable();
+        assert_eq!(union, whole);
+    }
+
+    #[test]
+    fn test_stream_ends_at_u64_max() {
+        let config = HashFinderConfig::new().zeros(1).start_from(u64::MAX - 200);
+        assert_eq!(HashFinder::with_config(config).unwrap().count(), 9);
+    }
+
+    #[test]
+    fn test_stream_rejects_invalid_config() {
+        assert!(matches!(
+            HashStream::new(0),
+            Err(HashFinderError::InvalidZeroCount { zeros: 0, max: 64 })
+        ));
+        assert!(matches!(
+            HashStream::new(65),
+            Err(HashFinderError::InvalidZeroCount { zeros: 65, max: 64 })
+        ));
+        let config = HashFinderConfig::new().zeros(2).threads(0);
+        assert!(matches!(
+            HashStream::with_config(config),
+            Err(HashFinderError::Config(crate::ConfigError::ZeroThreads))
+        ));
+    }
+}
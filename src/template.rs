@@ -0,0 +1,157 @@
+use std::fmt;
+use std::str::FromStr;
+
+/// Input with the nonce embedded at a `{n}` placeholder, e.g. `block-{n}-v2`.
+///
+/// `{{` and `}}` stand for literal braces.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InputTemplate {
+    prefix: Vec<u8>,
+    suffix: Vec<u8>,
+}
+
+impl InputTemplate {
+    /// Bytes hashed before the nonce.
+    pub fn prefix(&self) -> &[u8] {
+        &self.prefix
+    }
+
+    /// Bytes hashed after the nonce.
+    pub fn suffix(&self) -> &[u8] {
+        &self.suffix
+    }
+
+    /// The template with `{n}` replaced by `nonce`.
+    pub fn render(&self, nonce: &[u8]) -> Vec<u8> {
+        [self.prefix(), nonce, self.suffix()].concat()
+    }
+}
+
+/// Formats back to the template syntax, re-escaping literal braces.
+impl fmt::Display for InputTemplate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let escape = |bytes: &[u8]| {
+            String::from_utf8_lossy(bytes)
+                .replace('{', "{{")
+                .replace('}', "}}")
+        };
+        write!(f, "{}{{n}}{}", escape(&self.prefix), escape(&self.suffix))
+    }
+}
+
+/// Serialized as its display form, the template text.
+#[cfg(feature = "serde")]
+impl serde::Serialize for InputTemplate {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl FromStr for InputTemplate {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut prefix = String::new();
+        let mut suffix = String::new();
+        let mut placeholders = 0;
+        let mut chars = s.chars().peekable();
+
+        while let Some(c) = chars.next() {
+            let literal = match c {
+                '{' if chars.peek() == Some(&'{') => {
+                    chars.next();
+                    '{'
+                }
+                '}' if chars.peek() == Some(&'}') => {
+                    chars.next();
+                    '}'
+                }
+                '{' => {
+                    let mut name = String::new();
+                    let mut closed = false;
+                    for c in chars.by_ref() {
+                        if c == '}' {
+                            closed = true;
+                            break;
+                        }
+                        name.push(c);
+                    }
+                    if !closed {
+                        return Err(format!("unclosed '{{' in template '{}'", s));
+                    }
+                    if name != "n" {
+                        return Err(format!(
+                            "invalid placeholder '{{{}}}' in template '{}' (only {{n}} is supported)",
+                            name, s
+                        ));
+                    }
+                    placeholders += 1;
+                    continue;
+                }
+                '}' => return Err(format!("unmatched '}}' in template '{}'", s)),
+                c => c,
+            };
+            if placeholders == 0 {
+                prefix.push(literal);
+            } else {
+                suffix.push(literal);
+            }
+        }
+
+        if placeholders != 1 {
+            return Err(format!(
+                "template '{}' must contain exactly one {{n}} placeholder (found {})",
+                s, placeholders
+            ));
+        }
+
+        Ok(InputTemplate {
+            prefix: prefix.into_bytes(),
+            suffix: suffix.into_bytes(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{HashAlgorithm, HashFinderConfig};
+
+    #[test]
+    fn test_parse_template() {
+        let template: InputTemplate = "block-{n}-v2".parse().unwrap();
+        assert_eq!(template.render(b"42"), b"block-42-v2");
+
+        let escaped: InputTemplate = "{{{n}}}".parse().unwrap();
+        assert_eq!(escaped.render(b"42"), b"{42}");
+        assert_eq!(escaped.to_string(), "{{{n}}}");
+
+        assert!("block".parse::<InputTemplate>().is_err());
+        assert!("{n}-{n}".parse::<InputTemplate>().is_err());
+        assert!("{x}-{n}".parse::<InputTemplate>().is_err());
+        assert!("{n".parse::<InputTemplate>().is_err());
+        assert!("a}{n}".parse::<InputTemplate>().is_err());
+        assert!("{{n}}".parse::<InputTemplate>().is_err());
+    }
+
+    #[test]
+    fn test_template_search_positions() {
+        for (template, render) in [
+            ("{n}-tail", (|n: u64| format!("{}-tail", n)) as fn(u64) -> String),
+            ("block-{n}-v2", |n| format!("block-{}-v2", n)),
+            ("head-{n}", |n| format!("head-{}", n)),
+        ] {
+            let config = HashFinderConfig::new()
+                .zeros(2)
+                .max_results(3)
+                .template(template.parse().unwrap());
+            let results = config.run().unwrap();
+            assert_eq!(results.len(), 3);
+            for result in results {
+                let expected = HashAlgorithm::Sha256.hash_hex(render(result.number).as_bytes());
+                assert_eq!(result.hash, expected);
+                assert!(result.hash.ends_with("00"));
+            }
+        }
+    }
+}
@@ -0,0 +1,121 @@
+use crate::{
+    compute_hash_with_algo, hash_matches_difficulty, hex_decode, HashAlgorithm, HashFinderConfig,
+    HashResult,
+};
+use subtle::ConstantTimeEq;
+
+/// Checks that `hash` is the SHA-256 hash of `num`, comparing in constant time.
+pub fn verify_hash(num: u64, hash: &str) -> bool {
+    verify_hash_with_algo(num, hash, HashAlgorithm::Sha256)
+}
+
+/// Checks that `hash` is the `algo` hash of `num`, comparing in constant time.
+pub fn verify_hash_with_algo(num: u64, hash: &str, algo: HashAlgorithm) -> bool {
+    let expected = compute_hash_with_algo(num, algo);
+    expected.as_bytes().ct_eq(hash.as_bytes()).into()
+}
+
+/// Checks that `result` is what a search with `config` would report: the hash must be
+/// recomputable from the number and satisfy the config's difficulty, including any `also`
+/// constraints and self suffix. Numbers too wide for the config's pad width are rejected, as is a recorded
+/// suffix the hash does not end with.
+pub fn verify_result_with_config(config: &HashFinderConfig, result: &HashResult) -> bool {
+    let expected = config.hash(result.number);
+    let genuine: bool = expected.as_bytes().ct_eq(result.hash.as_bytes()).into();
+    genuine
+        && config.fits_pad_width(result.number)
+        && config.matches(&result.hash)
+        && hex_decode(&result.hash).is_ok_and(|d| config.matches_self_suffix(result.number, &d))
+        && config.matches_also(result.number)
+        && result.suffix.as_ref().is_none_or(|suffix| result.hash.ends_with(suffix.as_str()))
+}
+
+/// Checks that `chain` is what [`HashFinderConfig::run_chained`] would report: every link
+/// must verify against the config for its height, which depends on the link before it.
+pub fn verify_chain(config: &HashFinderConfig, chain: &[HashResult]) -> bool {
+    chain.iter().enumerate().all(|(height, link)| {
+        let previous = height.checked_sub(1).map(|i| &chain[i]);
+        verify_result_with_config(&config.chain_link(previous), link)
+    })
+}
+
+/// Checks that every result is a genuine SHA-256 hash ending with `zeros` zeros.
+pub fn verify_results(results: &[HashResult], zeros: usize) -> bool {
+    results
+        .iter()
+        .all(|r| {
+            verify_hash(r.number, &r.hash) && hash_matches_difficulty(&r.hash, zeros) == Ok(true)
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{compute_hash, find_hashes};
+
+    #[test]
+    fn test_verify_hash() {
+        assert!(verify_hash(4163, &compute_hash(4163)));
+        assert!(!verify_hash(4164, &compute_hash(4163)));
+        assert!(!verify_hash(4163, "000"));
+        assert!(verify_hash_with_algo(
+            1,
+            &compute_hash_with_algo(1, HashAlgorithm::Sha512),
+            HashAlgorithm::Sha512
+        ));
+    }
+
+    #[test]
+    fn test_verify_result_with_config() {
+        let config = HashFinderConfig::new()
+            .zeros(2)
+            .max_results(2)
+            .nonce_encoding(crate::NonceEncoding::BigEndianBytes);
+        for result in config.run().unwrap() {
+            assert!(verify_result_with_config(&config, &result));
+            assert!(!verify_result_with_config(&HashFinderConfig::new().zeros(2), &result));
+        }
+    }
+
+    #[test]
+    fn test_verify_padded_result() {
+        let config = HashFinderConfig::new().zeros(1).max_results(1).pad_width(2);
+        let result = config.run_first().unwrap().unwrap();
+        assert!(verify_result_with_config(&config, &result));
+        assert!(!verify_result_with_config(&HashFinderConfig::new().zeros(1), &result));
+
+        let wide = HashResult::new(100, config.hash(100));
+        assert!(!verify_result_with_config(&config, &wide));
+    }
+
+    #[test]
+    fn test_verify_self_suffix() {
+        let config = HashFinderConfig::new().self_suffix(true).salt("self");
+        for num in [2, 4020] {
+            assert!(verify_result_with_config(&config, &HashResult::new(num, config.hash(num))));
+        }
+        assert!(!verify_result_with_config(&config, &HashResult::new(3, config.hash(3))));
+        let unsalted = HashFinderConfig::new().self_suffix(true);
+        assert!(!verify_result_with_config(&unsalted, &HashResult::new(2, config.hash(2))));
+    }
+
+    #[test]
+    fn test_verify_chain() {
+        let config = HashFinderConfig::new().zeros(2).max_results(3);
+        let mut chain = config.run_chained().unwrap();
+        assert!(verify_chain(&config, &chain));
+
+        chain.swap(1, 2);
+        assert!(!verify_chain(&config, &chain));
+    }
+
+    #[test]
+    fn test_verify_results() {
+        let results = find_hashes(2, 3).unwrap();
+        assert!(verify_results(&results, 2));
+        assert!(!verify_results(&results, 64));
+
+        let forged = vec![HashResult::new(1, "0".repeat(64))];
+        assert!(!verify_results(&forged, 2));
+    }
+}
@@ -0,0 +1,57 @@
+use crate::{
+    compute_hash, compute_hash_raw, hash_bytes_end_with_zeros, hash_matches_difficulty,
+    hex_encode,
+};
+use alloc::format;
+use alloc::string::{String, ToString};
+use core::fmt::Write;
+use wasm_bindgen::prelude::*;
+
+/// Most trailing zeros [`find_hashes_wasm`] accepts: every digit of a SHA-256 hex digest.
+const MAX_ZEROS: usize = 64;
+
+/// [`compute_hash`] for JavaScript; `num` is a `u32` so it is passed as a plain number
+/// rather than a `BigInt`.
+#[wasm_bindgen]
+pub fn compute_hash_wasm(num: u32) -> String {
+    compute_hash(num.into())
+}
+
+/// [`hash_matches_difficulty`] for JavaScript; throws on invalid input.
+#[wasm_bindgen]
+pub fn hash_ends_with_zeros_wasm(hash: &str, zeros: usize) -> Result<bool, JsValue> {
+    hash_matches_difficulty(hash, zeros).map_err(|e| JsError::new(&e.to_string()).into())
+}
+
+/// The first `max_results` numbers from 1 whose SHA-256 hash ends with `zeros` zeros, as an
+/// array of `{number, hash}` objects.
+///
+/// WebAssembly has no threads by default, so unlike
+/// [`find_hashes`](crate::find_hashes) this checks one number after another on the
+/// calling thread. Throws when `zeros` is 0 or above 64, or `max_results` is 0.
+#[wasm_bindgen]
+pub fn find_hashes_wasm(zeros: usize, max_results: usize) -> Result<JsValue, JsValue> {
+    if zeros == 0 || zeros > MAX_ZEROS {
+        let message = format!("zeros must be between 1 and {} (got {})", MAX_ZEROS, zeros);
+        return Err(JsError::new(&message).into());
+    }
+    if max_results == 0 {
+        return Err(JsError::new("max_results must be greater than 0").into());
+    }
+
+    let matches = (1..=u64::MAX)
+        .filter_map(|num| {
+            let digest = compute_hash_raw(num);
+            hash_bytes_end_with_zeros(&digest, zeros).then_some((num, digest))
+        })
+        .take(max_results);
+    let mut json = String::from("[");
+    for (i, (num, digest)) in matches.enumerate() {
+        if i > 0 {
+            json.push(',');
+        }
+        let _ = write!(json, "{{\"number\":{},\"hash\":\"{}\"}}", num, hex_encode(&digest));
+    }
+    json.push(']');
+    js_sys::JSON::parse(&json)
+}
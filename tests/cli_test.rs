@@ -1,3 +1,5 @@
+#![cfg(feature = "std")]
+
 use assert_cmd::Command;
 use predicates::prelude::*;
 
@@ -30,3 +32,1578 @@ fn test_cli_success() {
         .success()
         .stdout(predicate::str::contains("000\""));
 }
+
+#[test]
+fn test_cli_sha512() {
+    let mut cmd = Command::cargo_bin("rust-hash-finder").unwrap();
+    cmd.env("RUST_LOG", "off")
+        .args(["--algorithm", "sha512", "-N", "2", "-F", "1"])
+        .assert()
+        .success()
+        .stdout(predicate::str::is_match("^\\d+, \"[0-9a-f]{126}00\"\n$").unwrap());
+}
+
+#[test]
+fn test_cli_zeros_exceed_digest_length() {
+    let mut cmd = Command::cargo_bin("rust-hash-finder").unwrap();
+    cmd.env("RUST_LOG", "off")
+        .args(["-N", "65", "-F", "1"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("must not exceed 64"));
+}
+
+#[test]
+fn test_cli_sha1_rejects_long_difficulty() {
+    let mut cmd = Command::cargo_bin("rust-hash-finder").unwrap();
+    cmd.env("RUST_LOG", "off")
+        .args(["--algorithm", "sha1", "-N", "41", "-F", "1"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("must not exceed 40 for sha1"));
+}
+
+#[test]
+fn test_cli_blake2b() {
+    let mut cmd = Command::cargo_bin("rust-hash-finder").unwrap();
+    cmd.env("RUST_LOG", "off")
+        .args(["--algorithm", "blake2b", "-N", "2", "-F", "1"])
+        .assert()
+        .success()
+        .stdout(predicate::str::is_match("^\\d+, \"[0-9a-f]{126}00\"\n$").unwrap());
+}
+
+#[test]
+fn test_cli_start_offset() {
+    let mut cmd = Command::cargo_bin("rust-hash-finder").unwrap();
+    cmd.env("RUST_LOG", "off")
+        .args(["-N", "3", "-F", "1", "--start", "4164"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("000\"").and(predicate::str::starts_with("4163,").not()));
+
+    // 0 is a valid start and is hashed like any other number.
+    let mut cmd = Command::cargo_bin("rust-hash-finder").unwrap();
+    cmd.env("RUST_LOG", "off")
+        .args(["--top-k", "1", "--start", "0", "--end", "1"])
+        .assert()
+        .success()
+        .stdout(format!("0, \"{}\", trailing_zeros=0\n", rust_hash_finder::compute_hash(0)));
+}
+
+#[test]
+fn test_cli_ripemd160() {
+    let mut cmd = Command::cargo_bin("rust-hash-finder").unwrap();
+    cmd.env("RUST_LOG", "off")
+        .args(["--algorithm", "ripemd160", "-N", "2", "-F", "1"])
+        .assert()
+        .success()
+        .stdout(predicate::str::is_match("^\\d+, \"[0-9a-f]{38}00\"\n$").unwrap());
+}
+
+#[test]
+fn test_cli_ripemd160_rejects_long_difficulty() {
+    let mut cmd = Command::cargo_bin("rust-hash-finder").unwrap();
+    cmd.env("RUST_LOG", "off")
+        .args(["--algorithm", "ripemd160", "-N", "41", "-F", "1"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("must not exceed 40 for ripemd160"));
+}
+
+#[test]
+fn test_cli_verify_accepts_valid_results() {
+    let mut cmd = Command::cargo_bin("rust-hash-finder").unwrap();
+    cmd.env("RUST_LOG", "off")
+        .args(["verify", "-N", "3"])
+        .write_stdin(
+            "4163, \"95d4362bd3cd4315d0bbe38dfa5d7fb8f0aed5f1a31d98d510907279194e3000\"\n",
+        )
+        .assert()
+        .success()
+        .stdout(predicate::str::starts_with("OK 4163"));
+}
+
+#[test]
+fn test_cli_verify_rejects_forged_results() {
+    let mut cmd = Command::cargo_bin("rust-hash-finder").unwrap();
+    cmd.env("RUST_LOG", "off")
+        .args(["verify", "-N", "3"])
+        .write_stdin(
+            "4164, \"95d4362bd3cd4315d0bbe38dfa5d7fb8f0aed5f1a31d98d510907279194e3000\"\n",
+        )
+        .assert()
+        .failure()
+        .stdout(predicate::str::starts_with("FAIL 4164"));
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_cli_json_logs() {
+    use serde_json::Value;
+
+    let mut cmd = Command::cargo_bin("rust-hash-finder").unwrap();
+    let output = cmd
+        .env("RUST_LOG", "rust_hash_finder=debug")
+        .args(["-N", "2", "-F", "1", "--log-format", "json"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    let logs: Vec<Value> = stderr
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap_or_else(|e| panic!("{}: {}", e, line)))
+        .collect();
+    assert!(!logs.is_empty());
+    for log in &logs {
+        assert!(log["timestamp"].is_string(), "{}", log);
+        let level = log["level"].as_str().unwrap();
+        assert!(["TRACE", "DEBUG", "INFO", "WARN", "ERROR"].contains(&level), "{}", log);
+        assert!(log["fields"]["message"].is_string(), "{}", log);
+        // `spans` runs outermost first, so it ends with `span`; both are absent outside a span.
+        match log.get("span") {
+            Some(span) => assert_eq!(log["spans"].as_array().unwrap().last(), Some(span)),
+            None => assert!(log.get("spans").is_none(), "{}", log),
+        }
+    }
+
+    // Span fields are numbers, and matches carry the span of the search that found them.
+    let found = logs
+        .iter()
+        .find(|log| log["fields"]["message"] == "Found hash")
+        .expect("no Found hash event");
+    assert!(found["fields"]["num"].is_u64(), "{}", found);
+    assert!(found["fields"]["hash"].as_str().unwrap().ends_with("00"), "{}", found);
+    let span = &found["span"];
+    assert_eq!(span["name"], "run");
+    assert_eq!(span["zeros"], 2);
+    assert_eq!(span["max_results"], 1);
+    assert_eq!(span["start"], 1);
+    assert_eq!(span["algo"], "sha256");
+
+    // The logs stay off stdout, which holds just the result.
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let result: rust_hash_finder::HashResult = stdout.trim_end().parse().unwrap();
+    assert_eq!(Some(result.number), found["fields"]["num"].as_u64());
+}
+
+#[test]
+fn test_cli_unknown_log_format() {
+    let mut cmd = Command::cargo_bin("rust-hash-finder").unwrap();
+    cmd.env("RUST_LOG", "off")
+        .args(["-N", "2", "-F", "1", "--log-format", "xml"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("unknown log format 'xml'"));
+}
+
+#[test]
+fn test_cli_benchmark() {
+    let mut cmd = Command::cargo_bin("rust-hash-finder").unwrap();
+    let output = cmd
+        .env("RUST_LOG", "off")
+        .args(["benchmark", "--bench-seconds", "1"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(predicate::str::is_match(r"Hashes computed: [1-9]\d*\n").unwrap().eval(&stdout));
+    assert!(predicate::str::is_match(r"Hash rate: \d+ H/s\n").unwrap().eval(&stdout));
+    assert!(stdout.contains("\n1      16                 "));
+    assert!(stdout.contains("\n8      4294967296         "));
+    let table: Vec<&str> = stdout.lines().skip_while(|l| !l.starts_with("Zeros")).collect();
+    assert_eq!(table.len(), 9);
+
+    let mut cmd = Command::cargo_bin("rust-hash-finder").unwrap();
+    cmd.env("RUST_LOG", "off")
+        .args(["benchmark", "--bench-seconds", "0"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--bench-seconds must be greater than 0"));
+}
+
+#[test]
+fn test_cli_json_format() {
+    let mut cmd = Command::cargo_bin("rust-hash-finder").unwrap();
+    cmd.env("RUST_LOG", "off")
+        .args(["-N", "3", "-F", "2", "--format", "json"])
+        .assert()
+        .success()
+        .stdout(
+            predicate::str::is_match(
+                "^\\[\\{\"number\":\\d+,\"hash\":\"[0-9a-f]{61}000\"\\},\\{.*\\}\\]\n$",
+            )
+            .unwrap(),
+        );
+}
+
+#[test]
+fn test_cli_csv_format() {
+    let mut cmd = Command::cargo_bin("rust-hash-finder").unwrap();
+    cmd.env("RUST_LOG", "off")
+        .args(["-N", "3", "-F", "1", "--format", "csv"])
+        .assert()
+        .success()
+        .stdout(predicate::str::starts_with("number,hash\n").and(predicate::str::contains("000\n")));
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_cli_json_output_parses_with_default_logging() {
+    let mut cmd = Command::cargo_bin("rust-hash-finder").unwrap();
+    let output = cmd
+        .env_remove("RUST_LOG")
+        .args(["-N", "3", "-F", "2", "--format", "json"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    assert!(String::from_utf8(output.stderr).unwrap().contains("INFO"));
+    let results: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(results.as_array().map(Vec::len), Some(2));
+}
+
+#[test]
+fn test_cli_default_logging_pipes_into_verify() {
+    let mut cmd = Command::cargo_bin("rust-hash-finder").unwrap();
+    let output = cmd.env_remove("RUST_LOG").args(["-N", "3", "-F", "2"]).output().unwrap();
+    assert!(output.status.success());
+
+    let mut verify = Command::cargo_bin("rust-hash-finder").unwrap();
+    verify
+        .env("RUST_LOG", "off")
+        .args(["verify", "-N", "3"])
+        .write_stdin(output.stdout)
+        .assert()
+        .success();
+}
+
+#[test]
+fn test_cli_nonce_encoding_round_trips_through_verify() {
+    let mut cmd = Command::cargo_bin("rust-hash-finder").unwrap();
+    let output = cmd
+        .env("RUST_LOG", "off")
+        .args(["-N", "2", "-F", "2", "--nonce-encoding", "le-bytes"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let mut verify = Command::cargo_bin("rust-hash-finder").unwrap();
+    verify
+        .env("RUST_LOG", "off")
+        .args(["verify", "-N", "2", "--nonce-encoding", "le-bytes"])
+        .write_stdin(output.stdout.clone())
+        .assert()
+        .success();
+
+    let mut verify_decimal = Command::cargo_bin("rust-hash-finder").unwrap();
+    verify_decimal
+        .env("RUST_LOG", "off")
+        .args(["verify", "-N", "2"])
+        .write_stdin(output.stdout)
+        .assert()
+        .failure();
+}
+
+#[test]
+fn test_cli_salt_round_trips_through_verify() {
+    let mut cmd = Command::cargo_bin("rust-hash-finder").unwrap();
+    let output = cmd
+        .env("RUST_LOG", "off")
+        .args(["-N", "2", "-F", "2", "--salt", "hello"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let mut verify = Command::cargo_bin("rust-hash-finder").unwrap();
+    verify
+        .env("RUST_LOG", "off")
+        .args(["verify", "-N", "2", "--salt", "hello"])
+        .write_stdin(output.stdout.clone())
+        .assert()
+        .success();
+
+    let mut verify_unsalted = Command::cargo_bin("rust-hash-finder").unwrap();
+    verify_unsalted
+        .env("RUST_LOG", "off")
+        .args(["verify", "-N", "2"])
+        .write_stdin(output.stdout)
+        .assert()
+        .failure();
+}
+
+#[test]
+fn test_cli_threads() {
+    let mut cmd = Command::cargo_bin("rust-hash-finder").unwrap();
+    cmd.env("RUST_LOG", "off")
+        .args(["-N", "3", "-F", "1", "-T", "2"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("000\""));
+
+    let mut cmd = Command::cargo_bin("rust-hash-finder").unwrap();
+    cmd.args(["-N", "3", "-F", "1", "--threads", "0"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("threads must be greater than 0"));
+}
+
+#[cfg(feature = "checkpoint")]
+#[test]
+fn test_cli_checkpoint_resume() {
+    let path = std::env::temp_dir().join(format!("cli-{}.checkpoint", std::process::id()));
+    let path_arg = path.to_str().unwrap();
+
+    let mut cmd = Command::cargo_bin("rust-hash-finder").unwrap();
+    let first = cmd
+        .env("RUST_LOG", "off")
+        .args(["-N", "3", "-F", "1", "--checkpoint-file", path_arg])
+        .output()
+        .unwrap();
+    assert!(first.status.success());
+    assert!(path.exists());
+
+    let mut cmd = Command::cargo_bin("rust-hash-finder").unwrap();
+    let resumed = cmd
+        .env("RUST_LOG", "off")
+        .args(["-N", "3", "-F", "2", "--checkpoint-file", path_arg, "--resume"])
+        .output()
+        .unwrap();
+    assert!(resumed.status.success());
+
+    let first = String::from_utf8(first.stdout).unwrap();
+    let resumed = String::from_utf8(resumed.stdout).unwrap();
+    assert!(resumed.starts_with(&first));
+    assert_eq!(resumed.lines().count(), 2);
+
+    let mut cmd = Command::cargo_bin("rust-hash-finder").unwrap();
+    cmd.env("RUST_LOG", "off")
+        .args(["-N", "4", "-F", "2", "--checkpoint-file", path_arg, "--resume"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("checkpoint was saved for algorithm=sha256, zeros=3"));
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_cli_resume_requires_checkpoint_file() {
+    let mut cmd = Command::cargo_bin("rust-hash-finder").unwrap();
+    cmd.args(["-N", "3", "-F", "1", "--resume"])
+        .assert()
+        .failure();
+}
+
+#[test]
+fn test_cli_template_round_trips_through_verify() {
+    let mut cmd = Command::cargo_bin("rust-hash-finder").unwrap();
+    let output = cmd
+        .env("RUST_LOG", "off")
+        .args(["-N", "2", "-F", "2", "--template", "block-{n}-v2"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let mut verify = Command::cargo_bin("rust-hash-finder").unwrap();
+    verify
+        .env("RUST_LOG", "off")
+        .args(["verify", "-N", "2", "--template", "block-{n}-v2"])
+        .write_stdin(output.stdout)
+        .assert()
+        .success();
+}
+
+#[test]
+fn test_cli_rejects_template_without_placeholder() {
+    let mut cmd = Command::cargo_bin("rust-hash-finder").unwrap();
+    cmd.args(["-N", "2", "-F", "1", "--template", "block"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("exactly one {n} placeholder"));
+}
+
+#[test]
+fn test_cli_timeout_returns_partial_results() {
+    for timeout in ["1", "1s", "1000ms"] {
+        let mut cmd = Command::cargo_bin("rust-hash-finder").unwrap();
+        cmd.env("RUST_LOG", "off")
+            .args(["-N", "20", "-F", "1", "--timeout", timeout])
+            .timeout(std::time::Duration::from_secs(30))
+            .assert()
+            .code(124)
+            .stdout(predicate::str::is_empty())
+            .stderr(predicate::str::contains("Timed out after 1s with 0 of 1 requested results"));
+    }
+
+    // Sorted and chained searches report running out of time the same way.
+    for mode in ["--sort", "--chained"] {
+        let mut cmd = Command::cargo_bin("rust-hash-finder").unwrap();
+        cmd.env("RUST_LOG", "off")
+            .args(["-N", "20", "-F", "1", "--timeout", "1", mode])
+            .timeout(std::time::Duration::from_secs(30))
+            .assert()
+            .code(124)
+            .stderr(predicate::str::contains("Timed out after 1s with 0 of 1 requested results"));
+    }
+
+    // Finishing within the budget still succeeds.
+    let mut cmd = Command::cargo_bin("rust-hash-finder").unwrap();
+    cmd.env("RUST_LOG", "off")
+        .args(["-N", "2", "-F", "3", "--timeout", "1h30m"])
+        .assert()
+        .success();
+
+    let mut cmd = Command::cargo_bin("rust-hash-finder").unwrap();
+    cmd.args(["-N", "2", "-F", "3", "--timeout", "5x"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("expected e.g. 90s, 5m or 1h30m"));
+}
+
+#[test]
+fn test_cli_hmac_key_from_file_round_trips_through_verify() {
+    let key_path = std::env::temp_dir().join(format!("cli-{}.hmac-key", std::process::id()));
+    std::fs::write(&key_path, "4a656665\n").unwrap();
+    let key_arg = format!("@{}", key_path.display());
+
+    let mut cmd = Command::cargo_bin("rust-hash-finder").unwrap();
+    let output = cmd
+        .env("RUST_LOG", "debug")
+        .args(["-N", "2", "-F", "2", "--hmac-key", &key_arg])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("DEBUG"));
+    assert!(!stderr.contains("4a656665") && !stderr.contains("Jefe"));
+    let results = String::from_utf8(output.stdout).unwrap();
+    assert!(!results.contains("4a656665") && !results.contains("Jefe"));
+    assert_eq!(results.lines().count(), 2);
+
+    let mut verify = Command::cargo_bin("rust-hash-finder").unwrap();
+    verify
+        .env("RUST_LOG", "off")
+        .args(["verify", "-N", "2", "--hmac-key", "4a656665"])
+        .write_stdin(results.clone())
+        .assert()
+        .success();
+
+    let mut verify_unkeyed = Command::cargo_bin("rust-hash-finder").unwrap();
+    verify_unkeyed
+        .env("RUST_LOG", "off")
+        .args(["verify", "-N", "2"])
+        .write_stdin(results)
+        .assert()
+        .failure();
+    std::fs::remove_file(&key_path).unwrap();
+}
+
+#[test]
+fn test_cli_xxhash_bounds() {
+    let mut cmd = Command::cargo_bin("rust-hash-finder").unwrap();
+    cmd.env("RUST_LOG", "off")
+        .args(["-N", "1", "-F", "3", "--algorithm", "xxhash"])
+        .assert()
+        .success()
+        .stdout(predicate::str::is_match(r#"(?m)^\d+, "[0-9a-f]{15}0"$"#).unwrap());
+
+    let mut cmd = Command::cargo_bin("rust-hash-finder").unwrap();
+    cmd.args(["-N", "17", "-F", "1", "--algorithm", "xxhash"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("must not exceed 16"));
+}
+
+#[test]
+fn test_cli_crc32_caps_difficulty() {
+    let mut cmd = Command::cargo_bin("rust-hash-finder").unwrap();
+    cmd.env("RUST_LOG", "off")
+        .args(["-N", "1", "-F", "3", "--algorithm", "crc32"])
+        .assert()
+        .success()
+        .stdout(predicate::str::is_match(r#"(?m)^\d+, "[0-9a-f]{7}0"$"#).unwrap());
+
+    let mut cmd = Command::cargo_bin("rust-hash-finder").unwrap();
+    cmd.args(["-N", "9", "-F", "1", "--algorithm", "crc32"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("must not exceed 8"));
+}
+
+#[test]
+fn test_cli_hash_encodings() {
+    let mut cmd = Command::cargo_bin("rust-hash-finder").unwrap();
+    cmd.env("RUST_LOG", "off")
+        .args(["-N", "3", "-F", "2", "--hash-encoding", "upperhex"])
+        .assert()
+        .success()
+        .stdout(predicate::str::is_match(r#"(?m)^\d+, "[0-9A-F]{61}000"$"#).unwrap());
+
+    let mut cmd = Command::cargo_bin("rust-hash-finder").unwrap();
+    cmd.env("RUST_LOG", "off")
+        .args(["-N", "3", "-F", "2", "--hash-encoding", "base64"])
+        .assert()
+        .success()
+        .stdout(predicate::str::is_match(r#"(?m)^\d+, "[A-Za-z0-9+/]{43}="$"#).unwrap());
+}
+
+#[test]
+fn test_cli_raw_bytes_to_file() {
+    let path = std::env::temp_dir().join(format!("cli-{}.digests", std::process::id()));
+
+    let mut cmd = Command::cargo_bin("rust-hash-finder").unwrap();
+    let output = cmd
+        .env("RUST_LOG", "off")
+        .args(["-N", "3", "-F", "2", "--hash-encoding", "raw-bytes-to-file"])
+        .arg("--raw-file")
+        .arg(&path)
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let expected: Vec<u8> = String::from_utf8(output.stdout)
+        .unwrap()
+        .lines()
+        .flat_map(|line| {
+            let number = line.split(',').next().unwrap().parse().unwrap();
+            rust_hash_finder::compute_digest(number)
+        })
+        .collect();
+    assert_eq!(expected.len(), 64);
+    assert_eq!(std::fs::read(&path).unwrap(), expected);
+    std::fs::remove_file(&path).unwrap();
+
+    let mut cmd = Command::cargo_bin("rust-hash-finder").unwrap();
+    cmd.args(["-N", "3", "-F", "1", "--hash-encoding", "raw-bytes-to-file"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("requires --raw-file"));
+}
+
+#[test]
+fn test_cli_salt_hex_matches_text_salt() {
+    let mut cmd = Command::cargo_bin("rust-hash-finder").unwrap();
+    let output = cmd
+        .env("RUST_LOG", "off")
+        .args(["-N", "2", "-F", "2", "--salt-hex", "68656c6c6f"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let mut verify = Command::cargo_bin("rust-hash-finder").unwrap();
+    verify
+        .env("RUST_LOG", "off")
+        .args(["verify", "-N", "2", "--salt", "hello"])
+        .write_stdin(output.stdout)
+        .assert()
+        .success();
+
+    let mut cmd = Command::cargo_bin("rust-hash-finder").unwrap();
+    cmd.args(["-N", "2", "-F", "1", "--salt-hex", "xyz"])
+        .assert()
+        .failure();
+}
+
+#[test]
+fn test_cli_iterations_round_trip_through_verify() {
+    let mut cmd = Command::cargo_bin("rust-hash-finder").unwrap();
+    let output = cmd
+        .env("RUST_LOG", "off")
+        .args(["-N", "2", "-F", "2", "--iterations", "3"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let mut verify = Command::cargo_bin("rust-hash-finder").unwrap();
+    verify
+        .env("RUST_LOG", "off")
+        .args(["verify", "-N", "2", "--iterations", "3"])
+        .write_stdin(output.stdout.clone())
+        .assert()
+        .success();
+
+    let mut verify_single = Command::cargo_bin("rust-hash-finder").unwrap();
+    verify_single
+        .env("RUST_LOG", "off")
+        .args(["verify", "-N", "2"])
+        .write_stdin(output.stdout)
+        .assert()
+        .failure();
+
+    let mut cmd = Command::cargo_bin("rust-hash-finder").unwrap();
+    cmd.env("RUST_LOG", "off")
+        .args(["-N", "2", "-F", "1", "--iterations", "0"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("iterations must be greater than 0"));
+}
+
+#[test]
+fn test_cli_pad_width_round_trip_through_verify() {
+    let mut cmd = Command::cargo_bin("rust-hash-finder").unwrap();
+    let output = cmd
+        .env("RUST_LOG", "off")
+        .args(["-N", "2", "-F", "5", "--pad-width", "3", "--sort"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout.clone()).unwrap();
+    let numbers: Vec<&str> = stdout.lines().filter_map(|l| l.split(',').next()).collect();
+    assert_eq!(numbers, ["403", "932"]);
+
+    let mut verify = Command::cargo_bin("rust-hash-finder").unwrap();
+    verify
+        .env("RUST_LOG", "off")
+        .args(["verify", "-N", "2", "--pad-width", "3"])
+        .write_stdin(output.stdout.clone())
+        .assert()
+        .success();
+
+    // Three-digit matches hash the same unpadded, so check against a wider padding.
+    let mut verify_wider = Command::cargo_bin("rust-hash-finder").unwrap();
+    verify_wider
+        .env("RUST_LOG", "off")
+        .args(["verify", "-N", "2", "--pad-width", "4"])
+        .write_stdin(output.stdout)
+        .assert()
+        .failure();
+
+    let mut cmd = Command::cargo_bin("rust-hash-finder").unwrap();
+    cmd.env("RUST_LOG", "off")
+        .args(["-N", "2", "-F", "1", "--pad-width", "3", "--start", "1000"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("nonce 1000 does not fit in 3 digits"));
+}
+
+#[test]
+fn test_cli_stats_reports_to_stderr() {
+    let mut cmd = Command::cargo_bin("rust-hash-finder").unwrap();
+    cmd.env("RUST_LOG", "off")
+        .args(["-N", "3", "-F", "1", "--stats", "--sort"])
+        .assert()
+        .success()
+        .stdout(predicate::str::starts_with("4163, "))
+        .stderr(predicate::str::is_match(r"Elapsed: \d+\.\d{3}s\n").unwrap())
+        .stderr(predicate::str::is_match(r"Candidates checked: \d+\n").unwrap())
+        .stderr(predicate::str::is_match(r"Hash rate: \d+ H/s\n").unwrap())
+        .stderr(predicate::str::contains("Results found: 1\n"));
+}
+
+#[test]
+fn test_cli_any_suffix() {
+    let expected = "\
+2085, \"1e37a1cedc03621762af0b89ebabd2242a5e1597cbe775951c15e59cd5023aaa\", suffix=aaa
+3133, \"c0480cd61fb7be83ba01df278f2084f5da8766acf9be9755b66a793acd8e7aaa\", suffix=aaa
+4163, \"95d4362bd3cd4315d0bbe38dfa5d7fb8f0aed5f1a31d98d510907279194e3000\", suffix=000
+";
+    let mut cmd = Command::cargo_bin("rust-hash-finder").unwrap();
+    cmd.env("RUST_LOG", "off")
+        .args(["--suffix", "000,aaa", "--suffix", "fff", "-F", "3", "--sort"])
+        .assert()
+        .success()
+        .stdout(expected);
+
+    let mut cmd = Command::cargo_bin("rust-hash-finder").unwrap();
+    cmd.env("RUST_LOG", "off")
+        .args(["--suffix", "000,aaa,fff", "-F", "1", "--sort", "--format", "csv"])
+        .assert()
+        .success()
+        .stdout(
+            "number,hash,suffix\n\
+             2085,1e37a1cedc03621762af0b89ebabd2242a5e1597cbe775951c15e59cd5023aaa,aaa\n",
+        );
+}
+
+#[test]
+fn test_cli_suffix() {
+    let mut cmd = Command::cargo_bin("rust-hash-finder").unwrap();
+    let output = cmd
+        .env("RUST_LOG", "off")
+        .args(["--suffix", "ab", "-F", "2"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert_eq!(stdout.lines().count(), 2);
+    assert!(stdout.lines().all(|line| line.ends_with("ab\"")));
+
+    let mut cmd = Command::cargo_bin("rust-hash-finder").unwrap();
+    cmd.env("RUST_LOG", "off")
+        .args(["-N", "2", "--suffix", "ab", "-F", "1"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("cannot be used with"));
+
+    let mut cmd = Command::cargo_bin("rust-hash-finder").unwrap();
+    cmd.env("RUST_LOG", "off")
+        .args(["--suffix", "xyz", "-F", "1"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("invalid hex digit 'x' in 'xyz'"));
+
+    let mut cmd = Command::cargo_bin("rust-hash-finder").unwrap();
+    cmd.env("RUST_LOG", "off")
+        .args(["--suffix", "abcdef012", "--algorithm", "crc32", "-F", "1"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("needs 9 digits but crc32 hashes have only 8"));
+}
+
+#[test]
+fn test_cli_contains() {
+    let mut cmd = Command::cargo_bin("rust-hash-finder").unwrap();
+    let output = cmd
+        .env("RUST_LOG", "off")
+        .args(["--contains", "abc", "--prefix", "0", "-F", "2"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert_eq!(stdout.lines().count(), 2);
+    for line in stdout.lines() {
+        let result: rust_hash_finder::HashResult = line.parse().unwrap();
+        assert!(result.hash.starts_with('0') && result.hash.contains("abc"));
+        assert!(rust_hash_finder::verify_hash(result.number, &result.hash));
+    }
+
+    let mut cmd = Command::cargo_bin("rust-hash-finder").unwrap();
+    cmd.env("RUST_LOG", "off")
+        .args(["--contains", "xyz", "-F", "1"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("invalid hex digit 'x' in 'xyz'"));
+}
+
+#[test]
+fn test_cli_wildcard() {
+    for (args, start) in [
+        (vec!["--wildcard", "0?0"], false),
+        (vec!["--wildcard", "A?", "--wildcard-at-start"], true),
+    ] {
+        let mut cmd = Command::cargo_bin("rust-hash-finder").unwrap();
+        let output = cmd
+            .env("RUST_LOG", "off")
+            .args(&args)
+            .args(["-F", "3"])
+            .output()
+            .unwrap();
+        assert!(output.status.success());
+        let stdout = String::from_utf8(output.stdout).unwrap();
+        assert_eq!(stdout.lines().count(), 3);
+        for line in stdout.lines() {
+            let result: rust_hash_finder::HashResult = line.parse().unwrap();
+            let hash = result.hash.as_bytes();
+            if start {
+                assert_eq!(hash[0], b'a', "{}", result.hash);
+            } else {
+                assert_eq!((hash[61], hash[63]), (b'0', b'0'), "{}", result.hash);
+            }
+            assert!(rust_hash_finder::verify_hash(result.number, &result.hash));
+        }
+    }
+
+    let mut cmd = Command::cargo_bin("rust-hash-finder").unwrap();
+    cmd.env("RUST_LOG", "off")
+        .args(["--wildcard", "de*d", "-F", "1"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("invalid character '*' in 'de*d'"));
+
+    let mut cmd = Command::cargo_bin("rust-hash-finder").unwrap();
+    cmd.env("RUST_LOG", "off")
+        .args(["--wildcard", "0?0", "--suffix", "0", "-F", "1"])
+        .assert()
+        .failure();
+}
+
+#[test]
+fn test_cli_min_zero_count() {
+    let mut cmd = Command::cargo_bin("rust-hash-finder").unwrap();
+    cmd.env("RUST_LOG", "off")
+        .args(["--min-zero-count", "12", "-F", "2", "--sort"])
+        .assert()
+        .success()
+        .stdout(
+            "4051, \"880c2fc00805b3104264d490baf0090788b267e456c6d9f50aeca7e330b427b0\"\n\
+             6016, \"0c064516a0ec5302a2d2c2c270b509ca7a100b012445086f8900891310bdb9f3\"\n",
+        );
+
+    // Combined with -N, both must hold.
+    let mut cmd = Command::cargo_bin("rust-hash-finder").unwrap();
+    let output = cmd
+        .env("RUST_LOG", "off")
+        .args(["-N", "1", "--min-zero-count", "10", "-F", "3"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert_eq!(stdout.lines().count(), 3);
+    for line in stdout.lines() {
+        let result: rust_hash_finder::HashResult = line.parse().unwrap();
+        assert!(result.hash.ends_with('0'));
+        assert!(rust_hash_finder::hash_zero_count(&result.hash) >= 10);
+    }
+
+    let mut cmd = Command::cargo_bin("rust-hash-finder").unwrap();
+    cmd.env("RUST_LOG", "off")
+        .args(["--min-zero-count", "65", "-F", "1"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("zero count must not exceed 64 for sha256 (got 65)"));
+}
+
+#[test]
+fn test_cli_self_suffix() {
+    // Unsalted SHA-256 has no solution in reach; this salt has 2 and 4020.
+    let expected = "2, \"e4138697d4ba0413e7fed26c9eb8d424805a9f3198cdfd515d9827d09be992b2\"\n\
+                    4020, \"0c26eb85c9e8b8f5871cad8bf1fea3d2f51a24a6867f2c9e5e02d9d323c74020\"\n";
+    let mut cmd = Command::cargo_bin("rust-hash-finder").unwrap();
+    cmd.env("RUST_LOG", "off")
+        .args(["--self-suffix", "--salt", "self", "-F", "2", "--sort"])
+        .assert()
+        .success()
+        .stdout(expected);
+
+    let mut cmd = Command::cargo_bin("rust-hash-finder").unwrap();
+    cmd.env("RUST_LOG", "off")
+        .args(["verify", "--self-suffix", "--salt", "self"])
+        .write_stdin(expected)
+        .assert()
+        .success();
+
+    // A genuine hash that does not end with its number fails.
+    let mut cmd = Command::cargo_bin("rust-hash-finder").unwrap();
+    cmd.env("RUST_LOG", "off")
+        .args(["verify", "--self-suffix"])
+        .write_stdin(
+            "4163, \"95d4362bd3cd4315d0bbe38dfa5d7fb8f0aed5f1a31d98d510907279194e3000\"\n",
+        )
+        .assert()
+        .failure()
+        .stdout(predicate::str::starts_with("FAIL 4163"));
+}
+
+#[test]
+fn test_cli_palindrome() {
+    let mut cmd = Command::cargo_bin("rust-hash-finder").unwrap();
+    cmd.env("RUST_LOG", "off")
+        .args(["--palindrome", "4", "-F", "2", "--sort"])
+        .assert()
+        .success()
+        .stdout(
+            "527, \"e1bb74a7794720edf4935a8813538e8113491318168b1fa61a0ac3528e7b0440\"\n\
+             584, \"085bcb597bbd610a7f0f955301d0fe3734b92a7144e87f68e8b5beec1a09b55b\"\n",
+        );
+
+    // The whole digest needs --force.
+    let mut cmd = Command::cargo_bin("rust-hash-finder").unwrap();
+    cmd.env("RUST_LOG", "off")
+        .args(["--palindrome", "-F", "1"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--force"));
+
+    let mut cmd = Command::cargo_bin("rust-hash-finder").unwrap();
+    cmd.env("RUST_LOG", "off")
+        .args(["-N", "3", "--force", "-F", "1"])
+        .assert()
+        .failure();
+
+    let mut cmd = Command::cargo_bin("rust-hash-finder").unwrap();
+    cmd.env("RUST_LOG", "off")
+        .args(["--palindrome", "4", "--suffix", "0", "-F", "1"])
+        .assert()
+        .failure();
+}
+
+#[test]
+fn test_cli_target() {
+    let target = format!("0000{}", "f".repeat(60));
+    let mut cmd = Command::cargo_bin("rust-hash-finder").unwrap();
+    cmd.env("RUST_LOG", "off")
+        .args(["--target", &target, "-F", "1", "--sort"])
+        .assert()
+        .success()
+        .stdout("88484, \"0000a456e7b5a5eb059e721fb431436883143101275c4077f83fe70298f5623d\"\n");
+
+    let mut cmd = Command::cargo_bin("rust-hash-finder").unwrap();
+    cmd.env("RUST_LOG", "off")
+        .args(["--target", "00ff", "-F", "1"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("target must be 64 hex digits (got 4)"));
+
+    let mut cmd = Command::cargo_bin("rust-hash-finder").unwrap();
+    cmd.env("RUST_LOG", "off")
+        .args(["--target", &"0".repeat(64), "-F", "1"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("no digest is below an all-zero target"));
+}
+
+#[test]
+fn test_cli_bits() {
+    let mut cmd = Command::cargo_bin("rust-hash-finder").unwrap();
+    cmd.env("RUST_LOG", "off")
+        .args(["--bits", "9", "-F", "1", "--sort"])
+        .assert()
+        .success()
+        .stdout("286, \"00328ce57bbc14b33bd6695bc8eb32cdf2fb5f3a7d89ec14a42825e15d39df60\"\n");
+
+    let mut cmd = Command::cargo_bin("rust-hash-finder").unwrap();
+    cmd.env("RUST_LOG", "off")
+        .args(["--bits", "10", "--estimate"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Expected attempts: 1024\n"));
+
+    let mut cmd = Command::cargo_bin("rust-hash-finder").unwrap();
+    cmd.env("RUST_LOG", "off")
+        .args(["--bits", "9", "-N", "3", "-F", "1"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("cannot be used with"));
+
+    let mut cmd = Command::cargo_bin("rust-hash-finder").unwrap();
+    cmd.env("RUST_LOG", "off")
+        .args(["--bits", "33", "--algorithm", "crc32", "-F", "1"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("must not exceed 32 for crc32"));
+}
+
+#[test]
+fn test_cli_prefix() {
+    let mut cmd = Command::cargo_bin("rust-hash-finder").unwrap();
+    let output = cmd
+        .env("RUST_LOG", "off")
+        .args(["--prefix", "00", "-F", "2"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert_eq!(stdout.lines().count(), 2);
+    assert!(stdout.lines().all(|line| line.contains(", \"00")));
+
+    // --prefix and --suffix combine; both must match.
+    let mut cmd = Command::cargo_bin("rust-hash-finder").unwrap();
+    let output = cmd
+        .env("RUST_LOG", "off")
+        .args(["--prefix", "00", "--suffix", "0", "-F", "1"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains(", \"00") && stdout.trim_end().ends_with("0\""));
+
+    // -N only counts trailing zeros, so it cannot be combined with a pattern.
+    let mut cmd = Command::cargo_bin("rust-hash-finder").unwrap();
+    cmd.env("RUST_LOG", "off")
+        .args(["-N", "2", "--prefix", "00", "-F", "1"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("cannot be used with"));
+}
+
+#[test]
+fn test_cli_chunk_size() {
+    let mut cmd = Command::cargo_bin("rust-hash-finder").unwrap();
+    cmd.env("RUST_LOG", "off")
+        .args(["-N", "3", "-F", "2", "--chunk-size", "1"])
+        .assert()
+        .success()
+        .stdout(predicate::str::starts_with("4163, "))
+        .stdout(predicate::str::contains("11848, "));
+
+    let mut cmd = Command::cargo_bin("rust-hash-finder").unwrap();
+    cmd.env("RUST_LOG", "off")
+        .args(["-N", "3", "-F", "2", "--chunk-size", "0"])
+        .assert()
+        .failure();
+}
+
+#[test]
+fn test_cli_randomize() {
+    let run = || {
+        let mut cmd = Command::cargo_bin("rust-hash-finder").unwrap();
+        let args = ["-N", "3", "-F", "1", "--end", "20000", "--chunk-size", "1000"];
+        let output = cmd
+            .env("RUST_LOG", "off")
+            .args(args)
+            .args(["--randomize", "--seed", "7"])
+            .output()
+            .unwrap();
+        assert!(output.status.success());
+        String::from_utf8(output.stdout).unwrap()
+    };
+    let first = run();
+    assert_eq!(run(), first);
+    let (number, hash) = first.trim_end().split_once(", ").unwrap();
+    assert!(["4163", "11848", "12843", "13467"].contains(&number), "{}", first);
+    assert!(hash.trim_matches('"').ends_with("000"));
+
+    let mut cmd = Command::cargo_bin("rust-hash-finder").unwrap();
+    cmd.env("RUST_LOG", "info")
+        .args(["-N", "2", "-F", "1", "--randomize"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("Randomized block order with seed"));
+
+    let mut cmd = Command::cargo_bin("rust-hash-finder").unwrap();
+    cmd.args(["-N", "2", "-F", "1", "--seed", "7"]).assert().failure();
+}
+
+#[test]
+#[cfg(feature = "sha3")]
+fn test_cli_short_algorithm_flag() {
+    let mut cmd = Command::cargo_bin("rust-hash-finder").unwrap();
+    cmd.env("RUST_LOG", "off")
+        .args(["-A", "sha3-256", "-N", "2", "-F", "1"])
+        .assert()
+        .success()
+        .stdout(predicate::str::is_match(r#"^\d+, "[0-9a-f]{62}00"\n$"#).unwrap());
+}
+
+#[test]
+#[cfg(not(feature = "blake3"))]
+fn test_cli_algorithm_not_compiled_in() {
+    let mut cmd = Command::cargo_bin("rust-hash-finder").unwrap();
+    cmd.env("RUST_LOG", "off")
+        .args(["-A", "blake3", "-N", "2", "-F", "1"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("rebuild with `--features blake3`"))
+        .stderr(predicate::str::contains("available: sha256"));
+}
+
+#[test]
+#[cfg(feature = "regex")]
+fn test_cli_pattern() {
+    let mut cmd = Command::cargo_bin("rust-hash-finder").unwrap();
+    let by_zeros = cmd
+        .env("RUST_LOG", "off")
+        .args(["-N", "2", "-F", "3", "--sort"])
+        .output()
+        .unwrap();
+    let mut cmd = Command::cargo_bin("rust-hash-finder").unwrap();
+    let by_regex = cmd
+        .env("RUST_LOG", "off")
+        .args(["--pattern", "00$", "-F", "3", "--sort"])
+        .output()
+        .unwrap();
+    assert!(by_regex.status.success());
+    assert_eq!(by_regex.stdout, by_zeros.stdout);
+
+    let mut cmd = Command::cargo_bin("rust-hash-finder").unwrap();
+    cmd.env("RUST_LOG", "off")
+        .args(["--pattern", "(00", "-F", "1"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("invalid regex '(00'"));
+
+    let mut cmd = Command::cargo_bin("rust-hash-finder").unwrap();
+    cmd.env("RUST_LOG", "off")
+        .args(["--pattern", "00$", "-N", "2", "-F", "1"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("cannot be used with"));
+}
+
+#[test]
+fn test_cli_chained_prints_heights() {
+    let mut cmd = Command::cargo_bin("rust-hash-finder").unwrap();
+    let output = cmd
+        .env("RUST_LOG", "off")
+        .args(["-N", "2", "-F", "3", "--chained", "--format", "csv"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let mut lines = stdout.lines();
+    assert_eq!(lines.next(), Some("height,number,hash"));
+    let links: Vec<Vec<&str>> = lines.map(|line| line.split(',').collect()).collect();
+    assert_eq!(links.len(), 3);
+    for (height, link) in links.iter().enumerate() {
+        assert_eq!(link[0], height.to_string());
+        assert!(link[2].ends_with("00"));
+    }
+}
+
+#[test]
+fn test_cli_progress_reports_to_stderr() {
+    let mut cmd = Command::cargo_bin("rust-hash-finder").unwrap();
+    let output = cmd
+        .env("RUST_LOG", "off")
+        .args(["-N", "8", "-F", "1000", "--algorithm", "crc32", "--timeout", "3"])
+        .args(["--progress", "--progress-interval", "1"])
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(124));
+
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    let line = stderr.lines().find(|l| l.starts_with("Progress: ")).unwrap();
+    assert!(line.contains(" hashes, "));
+    assert!(line.contains(" H/s, "));
+    assert!(line.ends_with(" found"));
+
+    let mut cmd = Command::cargo_bin("rust-hash-finder").unwrap();
+    cmd.args(["-N", "2", "-F", "1", "--progress-interval", "1"])
+        .assert()
+        .failure();
+}
+
+#[test]
+fn test_cli_also_prints_both_hashes() {
+    let mut cmd = Command::cargo_bin("rust-hash-finder").unwrap();
+    let output = cmd
+        .env("RUST_LOG", "off")
+        .args(["-N", "2", "-F", "2", "--also", "sha512:1", "--format", "csv"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let mut lines = stdout.lines();
+    assert_eq!(lines.next(), Some("number,hash,sha512"));
+    let rows: Vec<&str> = lines.collect();
+    assert_eq!(rows.len(), 2);
+    for row in rows {
+        let fields: Vec<&str> = row.split(',').collect();
+        assert_eq!(fields[1].len(), 64);
+        assert!(fields[1].ends_with("00"));
+        assert_eq!(fields[2].len(), 128);
+        assert!(fields[2].ends_with('0'));
+    }
+
+    let mut cmd = Command::cargo_bin("rust-hash-finder").unwrap();
+    cmd.args(["-N", "2", "-F", "1", "--also", "sha512"])
+        .assert()
+        .failure();
+}
+
+#[test]
+fn test_cli_sort_is_reproducible() {
+    let mut cmd = Command::cargo_bin("rust-hash-finder").unwrap();
+    cmd.env("RUST_LOG", "off")
+        .args(["-N", "3", "-F", "4", "--sort"])
+        .assert()
+        .success()
+        .stdout(predicate::str::starts_with(
+            "4163, \"95d4362bd3cd4315d0bbe38dfa5d7fb8f0aed5f1a31d98d510907279194e3000\"\n11848, ",
+        ))
+        .stdout(predicate::function(|out: &str| {
+            let numbers: Vec<&str> = out.lines().filter_map(|l| l.split(',').next()).collect();
+            numbers == ["4163", "11848", "12843", "13467"]
+        }));
+}
+
+#[cfg(feature = "argon2")]
+#[test]
+fn test_cli_argon2_reports_attempts() {
+    let argon2 = ["--algorithm", "argon2", "--argon2-mem", "8KiB", "--argon2-iters", "1"];
+    let mut cmd = Command::cargo_bin("rust-hash-finder").unwrap();
+    let output = cmd
+        .env("RUST_LOG", "off")
+        .args(["-N", "1", "-F", "2"])
+        .args(argon2)
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout.clone()).unwrap();
+    assert_eq!(stdout.lines().count(), 2);
+    assert!(stdout.lines().all(|line| line.contains(", attempts=")));
+
+    let mut verify = Command::cargo_bin("rust-hash-finder").unwrap();
+    verify
+        .env("RUST_LOG", "off")
+        .args(["verify", "-N", "1"])
+        .args(argon2)
+        .write_stdin(output.stdout)
+        .assert()
+        .success();
+
+    let mut cmd = Command::cargo_bin("rust-hash-finder").unwrap();
+    cmd.args(["-N", "1", "-F", "1", "--algorithm", "argon2", "--argon2-mem", "4KiB"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("invalid memory size"));
+}
+
+#[test]
+fn test_cli_estimate_prints_expectations_without_searching() {
+    let mut cmd = Command::cargo_bin("rust-hash-finder").unwrap();
+    cmd.env("RUST_LOG", "off")
+        .args(["-N", "7", "--estimate", "--algorithm", "xxhash"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Expected attempts: 268435456\n"))
+        .stdout(predicate::str::is_match(r"Measured throughput: \d+ H/s\n").unwrap())
+        .stdout(predicate::str::contains("Expected time: "));
+
+    let mut cmd = Command::cargo_bin("rust-hash-finder").unwrap();
+    cmd.env("RUST_LOG", "off")
+        .args(["-N", "0", "--estimate"])
+        .assert()
+        .failure();
+}
+
+#[test]
+fn test_cli_output_file() {
+    let path = std::env::temp_dir().join(format!("cli-{}.results", std::process::id()));
+    let search = || {
+        let mut cmd = Command::cargo_bin("rust-hash-finder").unwrap();
+        cmd.env("RUST_LOG", "off").args(["-N", "2", "-F", "2"]);
+        cmd
+    };
+    let expected = String::from_utf8(search().output().unwrap().stdout).unwrap();
+    assert_eq!(expected.lines().count(), 2);
+
+    std::fs::write(&path, "stale\n").unwrap();
+    search().arg("--output-file").arg(&path).assert().success().stdout("");
+    assert_eq!(std::fs::read_to_string(&path).unwrap(), expected);
+
+    search().arg("--output-file").arg(&path).arg("--append").assert().success();
+    assert_eq!(std::fs::read_to_string(&path).unwrap(), expected.repeat(2));
+    std::fs::remove_file(&path).unwrap();
+
+    let mut cmd = Command::cargo_bin("rust-hash-finder").unwrap();
+    cmd.args(["-N", "2", "-F", "1", "--append"]).assert().failure();
+}
+
+#[test]
+#[cfg(feature = "serde")]
+fn test_cli_json_output_file_loads_back() {
+    let path = std::env::temp_dir().join(format!("cli-{}.results.json", std::process::id()));
+    let mut cmd = Command::cargo_bin("rust-hash-finder").unwrap();
+    cmd.env("RUST_LOG", "off")
+        .args(["-N", "3", "-F", "2", "--sort", "--format", "json", "--output-file"])
+        .arg(&path)
+        .assert()
+        .success()
+        .stdout("");
+
+    let results = rust_hash_finder::load_results(&path).unwrap();
+    let numbers: Vec<u64> = results.iter().map(|r| r.number).collect();
+    assert_eq!(numbers, [4163, 11848]);
+    assert_eq!(results[0].hash, rust_hash_finder::compute_hash(4163));
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_cli_salt_file() {
+    let path = std::env::temp_dir().join(format!("cli-{}.salts", std::process::id()));
+    std::fs::write(&path, "msg-a:\n\nmsg-b:\n").unwrap();
+
+    let mut cmd = Command::cargo_bin("rust-hash-finder").unwrap();
+    let output = cmd
+        .env("RUST_LOG", "off")
+        .args(["-N", "2", "-F", "2", "--salt-file"])
+        .arg(&path)
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let mut found = std::collections::HashMap::<&str, Vec<u64>>::new();
+    for row in stdout.lines() {
+        let (line, result) = row.split_once(": ").unwrap();
+        let (number, hash) = result.split_once(", ").unwrap();
+        let salt = match line {
+            "1" => "msg-a:",
+            "3" => "msg-b:",
+            other => panic!("unexpected line {}", other),
+        };
+        let expected = rust_hash_finder::compute_hash_str(&format!("{}{}", salt, number));
+        assert_eq!(hash.trim_matches('"'), expected);
+        assert!(expected.ends_with("00"));
+        found.entry(line).or_default().push(number.parse().unwrap());
+    }
+    assert_eq!(found["1"].len(), 2);
+    assert_eq!(found["3"].len(), 2);
+    assert_ne!(found["1"], found["3"]);
+    std::fs::remove_file(&path).unwrap();
+
+    let mut cmd = Command::cargo_bin("rust-hash-finder").unwrap();
+    cmd.args(["-N", "2", "-F", "1", "--salt", "x", "--salt-file", "salts.txt"])
+        .assert()
+        .failure();
+}
+
+#[test]
+fn test_cli_wordlist() {
+    let path = std::env::temp_dir().join(format!("cli-{}.words", std::process::id()));
+    std::fs::write(&path, "apple\nword1690\n\n4163\nbanana\n").unwrap();
+
+    let mut cmd = Command::cargo_bin("rust-hash-finder").unwrap();
+    cmd.env("RUST_LOG", "off")
+        .args(["-N", "3", "--wordlist"])
+        .arg(&path)
+        .assert()
+        .success()
+        .stdout(
+            "word1690, \"b19ae1a13f766130f6e57fe746c5dea21c0c950ff456c8ade5137807d2db0000\"\n\
+             4163, \"95d4362bd3cd4315d0bbe38dfa5d7fb8f0aed5f1a31d98d510907279194e3000\"\n",
+        );
+
+    // The salt is hashed before each word; -F keeps the first matches in file order.
+    let mut cmd = Command::cargo_bin("rust-hash-finder").unwrap();
+    cmd.env("RUST_LOG", "off")
+        .args(["-N", "1", "-F", "2", "--salt", "f", "--wordlist"])
+        .arg(&path)
+        .assert()
+        .success()
+        .stdout(format!(
+            "word1690, \"{}\"\n4163, \"{}\"\n",
+            rust_hash_finder::compute_hash_str("fword1690"),
+            rust_hash_finder::compute_hash_str("f4163")
+        ));
+    std::fs::remove_file(&path).unwrap();
+
+    let mut cmd = Command::cargo_bin("rust-hash-finder").unwrap();
+    cmd.args(["-N", "3", "--wordlist", "words.txt", "--self-suffix"])
+        .assert()
+        .failure();
+}
+
+#[test]
+fn test_cli_stdin() {
+    let input = "13467\n1\nabc\n4163\n\n12843\n";
+    let mut cmd = Command::cargo_bin("rust-hash-finder").unwrap();
+    cmd.env("RUST_LOG", "off")
+        .args(["-N", "3", "--stdin"])
+        .write_stdin(input)
+        .assert()
+        .success()
+        .stdout(predicate::str::is_match("^13467, .*\n4163, \"95d4362b.*\"\n12843, .*\n$").unwrap())
+        .stderr(predicate::str::contains("Warning: skipping line 3"));
+
+    let mut cmd = Command::cargo_bin("rust-hash-finder").unwrap();
+    cmd.env("RUST_LOG", "off")
+        .args(["-N", "3", "-F", "2", "--stdin"])
+        .write_stdin(input)
+        .assert()
+        .success()
+        .stdout(predicate::str::is_match("^13467, .*\n4163, .*\n$").unwrap());
+
+    let mut cmd = Command::cargo_bin("rust-hash-finder").unwrap();
+    cmd.env("RUST_LOG", "off")
+        .args(["-N", "3", "--stdin", "--strict"])
+        .write_stdin(input)
+        .assert()
+        .failure()
+        .stdout("")
+        .stderr(predicate::str::contains("Error: line 3"));
+}
+
+#[test]
+fn test_cli_input_prefix_hashes_namespaced_input() {
+    let mut cmd = Command::cargo_bin("rust-hash-finder").unwrap();
+    let output = cmd
+        .env("RUST_LOG", "off")
+        .args(["-N", "2", "-F", "2", "--input-prefix", "myapp:"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    for line in stdout.lines() {
+        let (number, hash) = line.split_once(", ").unwrap();
+        let number: u64 = number.parse().unwrap();
+        let expected = rust_hash_finder::compute_hash_str(&format!("myapp:{}", number));
+        assert_eq!(hash.trim_matches('"'), expected);
+        assert!(expected.ends_with("00"));
+    }
+}
+
+#[test]
+fn test_cli_collide() {
+    let mut cmd = Command::cargo_bin("rust-hash-finder").unwrap();
+    let output = cmd.env("RUST_LOG", "off").args(["collide", "-K", "4"]).output().unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let results: Vec<rust_hash_finder::HashResult> =
+        stdout.lines().map(|line| line.parse().unwrap()).collect();
+    assert_eq!(results.len(), 2);
+    assert_ne!(results[0].number, results[1].number);
+    assert_eq!(results[0].hash[60..], results[1].hash[60..]);
+    for result in &results {
+        assert_eq!(result.hash, rust_hash_finder::compute_hash(result.number));
+    }
+
+    let mut cmd = Command::cargo_bin("rust-hash-finder").unwrap();
+    cmd.args(["collide", "-K", "12"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("pass --force"));
+}
+
+#[test]
+fn test_cli_end_reports_shortfall() {
+    let mut cmd = Command::cargo_bin("rust-hash-finder").unwrap();
+    let output = cmd
+        .env("RUST_LOG", "off")
+        .args(["-N", "3", "-F", "10", "--end", "20000"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let mut numbers: Vec<u64> =
+        stdout.lines().map(|line| line.split(',').next().unwrap().parse().unwrap()).collect();
+    numbers.sort_unstable();
+    assert_eq!(numbers, [4163, 11848, 12843, 13467]);
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("Found 4 of 10 requested results in 1..20000"), "{}", stderr);
+
+    let mut cmd = Command::cargo_bin("rust-hash-finder").unwrap();
+    cmd.env("RUST_LOG", "off")
+        .args(["-N", "3", "-F", "2", "--end", "20000"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("requested").not());
+}
+
+#[test]
+fn test_cli_start_near_u64_max_stops_at_the_end() {
+    let mut cmd = Command::cargo_bin("rust-hash-finder").unwrap();
+    let output = cmd
+        .env("RUST_LOG", "rust_hash_finder=warn")
+        .args(["-N", "1", "-F", "100", "--start", "18446744073709551415"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(
+        stderr.contains("Search space exhausted at u64::MAX with 9 of 100 results"),
+        "{}",
+        stderr
+    );
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert_eq!(stdout.lines().count(), 9);
+    assert!(stdout.lines().all(|line| line.starts_with("18446744073709551")), "{}", stdout);
+}
+
+#[test]
+fn test_cli_shards_cover_the_range() {
+    let mut union = Vec::new();
+    for index in ["0", "1", "2"] {
+        let mut cmd = Command::cargo_bin("rust-hash-finder").unwrap();
+        let output = cmd
+            .env("RUST_LOG", "off")
+            .args(["-N", "3", "-F", "10", "--end", "20000", "--chunk-size", "1000"])
+            .args(["--shard-count", "3", "--shard-index", index])
+            .output()
+            .unwrap();
+        assert!(output.status.success());
+        for line in String::from_utf8(output.stdout).unwrap().lines() {
+            let result: rust_hash_finder::HashResult = line.parse().unwrap();
+            assert_eq!(result.shard.unwrap().to_string(), format!("{}/3", index));
+            union.push(result.number);
+        }
+    }
+    union.sort_unstable();
+    assert_eq!(union, [4163, 11848, 12843, 13467]);
+
+    let mut cmd = Command::cargo_bin("rust-hash-finder").unwrap();
+    cmd.args(["-N", "3", "-F", "1", "--shard-count", "3", "--shard-index", "3"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("shard index must be below the shard count"));
+
+    let mut cmd = Command::cargo_bin("rust-hash-finder").unwrap();
+    cmd.args(["-N", "3", "-F", "1", "--shard-count", "3"]).assert().failure();
+}
+
+#[test]
+fn test_cli_count_only() {
+    let mut cmd = Command::cargo_bin("rust-hash-finder").unwrap();
+    cmd.env("RUST_LOG", "off")
+        .args(["-N", "3", "--end", "20000", "--count-only"])
+        .assert()
+        .success()
+        .stdout("4, 1..20000, 3\n");
+
+    let mut cmd = Command::cargo_bin("rust-hash-finder").unwrap();
+    cmd.env("RUST_LOG", "off")
+        .args(["-N", "2", "--start", "4000", "--end", "20000", "--count-only"])
+        .assert()
+        .success()
+        .stdout(predicate::str::ends_with(", 4000..20000, 2\n"));
+
+    let mut cmd = Command::cargo_bin("rust-hash-finder").unwrap();
+    cmd.args(["-N", "3", "--count-only"]).assert().failure();
+}
+
+#[test]
+fn test_cli_top_k() {
+    let mut cmd = Command::cargo_bin("rust-hash-finder").unwrap();
+    let output = cmd
+        .env("RUST_LOG", "off")
+        .args(["--top-k", "5", "--end", "20000"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let results: Vec<rust_hash_finder::HashResult> = String::from_utf8(output.stdout)
+        .unwrap()
+        .lines()
+        .map(|line| line.parse().unwrap())
+        .collect();
+    let numbers: Vec<u64> = results.iter().map(|r| r.number).collect();
+    assert_eq!(numbers, [4163, 11848, 12843, 13467, 403]);
+    assert_eq!(results[0].trailing_zeros, Some(3));
+    assert_eq!(results[4].trailing_zeros, Some(2));
+
+    let mut cmd = Command::cargo_bin("rust-hash-finder").unwrap();
+    cmd.args(["--top-k", "5"]).assert().failure();
+    let mut cmd = Command::cargo_bin("rust-hash-finder").unwrap();
+    cmd.args(["--top-k", "5", "--end", "100", "-N", "3"]).assert().failure();
+}
+
+#[test]
+fn test_cli_histogram() {
+    let mut cmd = Command::cargo_bin("rust-hash-finder").unwrap();
+    cmd.env("RUST_LOG", "off")
+        .args(["histogram", "-M", "20000", "--json"])
+        .assert()
+        .success()
+        .stdout("{\"total\":20000,\"buckets\":[18753,1175,68,4,0,0,0,0,0,0,0,0,0,0,0,0,0]}\n");
+
+    let mut cmd = Command::cargo_bin("rust-hash-finder").unwrap();
+    let output = cmd.env("RUST_LOG", "off").args(["histogram", "-M", "20000"]).output().unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let rows: Vec<&str> = stdout.lines().collect();
+    assert_eq!(rows.len(), 5, "{}", stdout);
+    assert!(rows[1].starts_with("0      18753       18750.0"), "{}", rows[1]);
+    assert!(rows[4].starts_with("3      4 "), "{}", rows[4]);
+}
+
+#[test]
+#[cfg(feature = "serde")]
+fn test_cli_dry_run() {
+    let path = std::env::temp_dir().join(format!("cli-{}.dry-run", std::process::id()));
+    std::fs::write(&path, "kept\n").unwrap();
+
+    let mut cmd = Command::cargo_bin("rust-hash-finder").unwrap();
+    cmd.env("RUST_LOG", "off")
+        .args(["-N", "3", "-F", "2", "--start", "4163", "--salt-hex", "ff00"])
+        .arg("--output-file")
+        .arg(&path)
+        .arg("--dry-run")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("\"algorithm\": \"sha256\""))
+        .stdout(predicate::str::contains("\"salt\": \"ff00\""))
+        .stdout(predicate::str::contains("\"number\": 4163"));
+    assert_eq!(std::fs::read_to_string(&path).unwrap(), "kept\n");
+    std::fs::remove_file(&path).unwrap();
+
+    let mut cmd = Command::cargo_bin("rust-hash-finder").unwrap();
+    cmd.env("RUST_LOG", "off")
+        .args(["-N", "3", "-F", "2", "--start", "4163", "--dry-run"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "95d4362bd3cd4315d0bbe38dfa5d7fb8f0aed5f1a31d98d510907279194e3000",
+        ));
+
+    let mut cmd = Command::cargo_bin("rust-hash-finder").unwrap();
+    cmd.args(["-N", "0", "-F", "2", "--dry-run"]).assert().failure();
+}
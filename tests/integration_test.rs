@@ -1,4 +1,11 @@
-use rust_hash_finder::{compute_hash, hash_ends_with_zeros};
+#![cfg(feature = "std")]
+
+use rust_hash_finder::{
+    compute_hash, compute_hash_with_algo, count_matches_in_range, find_hashes_from,
+    find_hashes_in_range, find_hashes_with_algo, hash_matches_difficulty, HashAlgorithm,
+    HashFinderError, HashResult,
+};
+use std::time::{Duration, Instant};
 
 #[test]
 fn test_integration_hash_computation() {
@@ -9,7 +16,7 @@ fn test_integration_hash_computation() {
 #[test]
 fn test_integration_known_value() {
     let hash = compute_hash(4163);
-    assert!(hash_ends_with_zeros(&hash, 3));
+    assert_eq!(hash_matches_difficulty(&hash, 3), Ok(true));
 }
 
 #[test]
@@ -20,3 +27,89 @@ fn test_integration_hash_consistency() {
         assert_eq!(hash1, hash2, "Hash для {} должен быть консистентным", i);
     }
 }
+
+#[test]
+fn test_integration_find_hashes_from_skips_known_result() {
+    let results = find_hashes_from(3, 1, 4164).unwrap();
+    assert_eq!(results.len(), 1);
+    let result = &results[0];
+    assert!(result.number > 4163);
+    assert_eq!(result.hash, compute_hash(result.number));
+    assert_eq!(hash_matches_difficulty(&result.hash, 3), Ok(true));
+}
+
+#[test]
+fn test_integration_find_hashes_in_range() {
+    let results = find_hashes_in_range(4000, 4200, 3).unwrap();
+    assert_eq!(results, vec![HashResult::new(4163, compute_hash(4163))]);
+    assert!(find_hashes_in_range(4164, 11848, 3).unwrap().is_empty());
+    assert!(matches!(
+        find_hashes_in_range(4200, 4000, 3),
+        Err(HashFinderError::InvalidRange { start: 4200, end: 4000 })
+    ));
+    assert!(matches!(
+        find_hashes_in_range(1, 100, 65),
+        Err(HashFinderError::InvalidZeroCount { zeros: 65, max: 64 })
+    ));
+}
+
+#[test]
+fn test_integration_find_hashes_in_range_exhaustive() {
+    let numbers = |start, end, zeros| -> Vec<u64> {
+        let results = find_hashes_in_range(start, end, zeros).unwrap();
+        results.iter().map(|r| r.number).collect()
+    };
+    assert_eq!(numbers(1, 20_000, 3), [4163, 11848, 12843, 13467]);
+    assert_eq!(numbers(1, 1300, 2), [403, 932, 1270]);
+    assert!(numbers(1, 31_214, 4).is_empty());
+}
+
+#[test]
+fn test_integration_count_matches_in_range() {
+    for (start, end, zeros) in [(1, 20_000, 3), (1, 20_000, 2), (4000, 4200, 3)] {
+        let count = count_matches_in_range(start, end, zeros).unwrap();
+        assert_eq!(count, find_hashes_in_range(start, end, zeros).unwrap().len() as u64);
+        assert_eq!(count_matches_in_range(start, end, zeros).unwrap(), count);
+    }
+    assert_eq!(count_matches_in_range(1, 20_000, 2).unwrap(), 72);
+    assert!(matches!(
+        count_matches_in_range(4200, 4000, 3),
+        Err(HashFinderError::InvalidRange { .. })
+    ));
+    assert!(matches!(
+        count_matches_in_range(1, 20_000, 0),
+        Err(HashFinderError::InvalidZeroCount { zeros: 0, .. })
+    ));
+}
+
+/// Ethereum's `web3.utils.sha3("1")`, i.e. Keccak-256 of the string "1".
+#[test]
+#[cfg(feature = "sha3")]
+fn test_integration_keccak256_matches_ethereum() {
+    assert_eq!(
+        compute_hash_with_algo(1, HashAlgorithm::Keccak256),
+        "c89efdaa54c0f20c7adf612882df0950f5a951637e0307cdcb4c672f298b8bc6"
+    );
+    // web3.utils.sha3("") is the well-known empty Keccak-256 digest.
+    assert_eq!(
+        HashAlgorithm::Keccak256.hash_hex(b""),
+        "c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470"
+    );
+    assert_ne!(
+        compute_hash_with_algo(1, HashAlgorithm::Keccak256),
+        compute_hash_with_algo(1, HashAlgorithm::Sha3_256)
+    );
+}
+
+#[test]
+fn test_integration_xxhash_many_results() {
+    let start = Instant::now();
+    let results = find_hashes_with_algo(1, 50, HashAlgorithm::XxHash64).unwrap();
+    assert!(start.elapsed() < Duration::from_secs(1));
+
+    assert_eq!(results.len(), 50);
+    for result in &results {
+        assert_eq!(result.hash, compute_hash_with_algo(result.number, HashAlgorithm::XxHash64));
+        assert_eq!(hash_matches_difficulty(&result.hash, 1), Ok(true));
+    }
+}
@@ -0,0 +1,76 @@
+#![cfg(feature = "std")]
+
+use proptest::prelude::*;
+use rust_hash_finder::{find_hashes_exact, hash_matches_difficulty, verify_hash};
+use std::collections::HashSet;
+
+#[allow(deprecated)]
+fn ends_with_zeros(hash: &str, zeros: usize) -> bool {
+    rust_hash_finder::hash_ends_with_zeros(hash, zeros)
+}
+
+/// Hex strings ending in a run of zeros, so that matches are common.
+fn zero_tailed_hash() -> impl Strategy<Value = String> {
+    ("[0-9a-f]{0,60}", 0usize..=8).prop_map(|(head, zeros)| head + &"0".repeat(zeros))
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(32))]
+
+    #[test]
+    fn test_find_hashes_exact_returns_max_results(
+        zeros in 1usize..=2,
+        max_results in 1usize..=200,
+    ) {
+        let results = find_hashes_exact(zeros, max_results).unwrap();
+        prop_assert_eq!(results.len(), max_results);
+
+        let numbers: HashSet<u64> = results.iter().map(|r| r.number).collect();
+        prop_assert_eq!(numbers.len(), max_results);
+        for result in &results {
+            prop_assert_eq!(hash_matches_difficulty(&result.hash, zeros), Ok(true));
+            prop_assert!(verify_hash(result.number, &result.hash));
+        }
+    }
+}
+
+proptest! {
+    #[test]
+    fn test_hash_ends_with_zeros_implies_fewer(
+        hash in zero_tailed_hash(),
+        zeros in 2usize..=70,
+    ) {
+        // Stops at 1, since a `zeros` of 0 never matches.
+        if ends_with_zeros(&hash, zeros) {
+            prop_assert!(ends_with_zeros(&hash, zeros - 1));
+        }
+        prop_assert_eq!(
+            ends_with_zeros(&hash, zeros),
+            zeros <= hash.len() && hash.bytes().rev().take(zeros).all(|b| b == b'0')
+        );
+    }
+
+    #[test]
+    fn test_hash_ends_with_zeros_broken_by_nonzero_digit(
+        hash in zero_tailed_hash(),
+        zeros in 1usize..=8,
+        digit in "[1-9a-f]",
+    ) {
+        if ends_with_zeros(&hash, zeros) {
+            prop_assert!(!ends_with_zeros(&(hash + &digit), zeros));
+        }
+    }
+
+    #[test]
+    fn test_hash_ends_with_zeros_rejects_short_hash(
+        hash in "0{0,16}|[0-9a-f]{0,16}",
+        extra in 1usize..=8,
+    ) {
+        prop_assert!(!ends_with_zeros(&hash, hash.len() + extra));
+    }
+
+    #[test]
+    fn test_hash_ends_with_zeros_never_matches_zero(hash in "[0-9a-f]{0,64}|0{0,64}") {
+        prop_assert!(!ends_with_zeros(&hash, 0));
+    }
+}
@@ -0,0 +1,30 @@
+// Smoke test for the WebAssembly bindings. Build them first:
+//   wasm-pack build wasm --target nodejs --out-dir ../tests/wasm/pkg
+//   node tests/wasm/smoke.js
+const assert = require("node:assert/strict");
+const {
+  compute_hash_wasm,
+  hash_ends_with_zeros_wasm,
+  find_hashes_wasm,
+} = require("./pkg/rust_hash_finder_wasm.js");
+
+const hash = compute_hash_wasm(4163);
+assert.equal(hash, "95d4362bd3cd4315d0bbe38dfa5d7fb8f0aed5f1a31d98d510907279194e3000");
+assert.ok(hash_ends_with_zeros_wasm(hash, 3));
+assert.ok(!hash_ends_with_zeros_wasm(hash, 4));
+assert.throws(() => hash_ends_with_zeros_wasm(hash, 0), /at least 1 zero/);
+assert.throws(() => hash_ends_with_zeros_wasm("xyz000", 3), /invalid hex digit/);
+
+const results = find_hashes_wasm(3, 2);
+assert.deepEqual(
+  results.map((result) => result.number),
+  [4163, 11848],
+);
+for (const result of results) {
+  assert.equal(result.hash, compute_hash_wasm(result.number));
+}
+
+assert.throws(() => find_hashes_wasm(0, 1), /zeros must be between 1 and 64/);
+assert.throws(() => find_hashes_wasm(3, 0), /max_results must be greater than 0/);
+
+console.log("wasm smoke test passed");
@@ -0,0 +1,3 @@
+//! The `wasm` bindings of `rust-hash-finder`, built with `wasm-pack build wasm`.
+
+pub use rust_hash_finder::{compute_hash_wasm, find_hashes_wasm, hash_ends_with_zeros_wasm};